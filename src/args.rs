@@ -1,45 +1,657 @@
-pico_args_helpgen::define_app! {
-    app_name: "Report Generator",
-    app_description: "Cyber Security Report Generator",
-    app_version: "v2.0.0",
-
-    help_args: "-h, --help",
-    version_args: "-V, --version",
-
-    struct AppArgs {
-        subcommand: Option<String>, "new, compile, new-section, new-finding", "The subcommand to execute",
-        dir: Option<std::path::PathBuf>, "[directory]", "Report directory",
-        output: Option<String>, "-o", "\tOutput file",
-        name: Option<String>, "--name", "New section/finding name",
-        template: Option<String>, "--template", "New section/finding template",
-    }
+use std::path::PathBuf;
+
+use clap::{builder::PossibleValuesParser, Args, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::engagement::ENGAGEMENTS;
+use crate::partials;
+use crate::report::Kind;
+
+// Command line of the binary. Shell completions, the man page and `--schema`
+// are generated from these definitions, doc comments are the help texts.
+
+#[derive(Parser)]
+#[command(
+    name = env!("CARGO_PKG_NAME"),
+    version,
+    about = "Cyber Security Report Generator",
+    long_about = "Compiles penetration test reports from typst sections and findings. Without a subcommand the desktop GUI is started when built with the gui feature.",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Report directory opened in the GUI
+    pub dir: Option<PathBuf>,
+
+    /// Guarantee no network access, enrichment only uses cached responses and --nvd dumps
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Print this CLI description as JSON
+    #[arg(long)]
+    pub schema: bool,
 }
 
-fn parse_args() -> Result<AppArgs, pico_args_helpgen::Error> {
-    let mut pargs = pico_args_helpgen::Arguments::from_env();
+/// Flags changing what compile builds, passed on to the compiles of watch, serve and ci
+#[derive(Args, Clone, Default)]
+pub struct BuildFlags {
+    /// Stamp a diagonal DRAFT watermark on every page
+    #[arg(long)]
+    pub draft: bool,
 
-    handle_help_version();
+    /// Show the current git commit (marked -dirty with uncommitted changes) in the footer
+    #[arg(long)]
+    pub tag: bool,
 
-    let args = AppArgs {
-        subcommand: pargs.subcommand()?,
-        dir: pargs.opt_free_from_str()?,
-        output: pargs.opt_value_from_str("-o")?,
-        name: pargs.opt_value_from_str("--name")?,
-        template: pargs.opt_value_from_str("--template")?,
-    };
+    /// Use the clean-result variant, selected automatically without findings at or above clean_result_threshold
+    #[arg(long)]
+    pub clean_result: bool,
 
-    let remaining = pargs.finish();
-    if !remaining.is_empty() {
-        eprintln!("Unexpected argument(s): {:?}", remaining);
-        std::process::exit(1);
+    /// Only warn about {{ placeholders }}, CHANGE ME, TODO and #lorem( left in the report instead of failing
+    #[arg(long)]
+    pub allow_placeholders: bool,
+
+    /// Branding profile from $XDG_CONFIG_HOME/reportgenerator/brands/<name>.toml, replacing the configured brand
+    #[arg(long, value_name = "NAME")]
+    pub brand: Option<String>,
+
+    /// Built-in theme replacing the configured one
+    #[arg(long, value_name = "NAME", value_parser = PossibleValuesParser::new(partials::themes()))]
+    pub theme: Option<String>,
+}
+
+impl BuildFlags {
+    /// The flags as arguments of a compile child process
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(ref brand) = self.brand {
+            args.extend(["--brand".to_string(), brand.clone()]);
+        }
+        if let Some(ref theme) = self.theme {
+            args.extend(["--theme".to_string(), theme.clone()]);
+        }
+        for (set, flag) in [
+            (self.draft, "--draft"),
+            (self.tag, "--tag"),
+            (self.clean_result, "--clean-result"),
+            (self.allow_placeholders, "--allow-placeholders"),
+        ] {
+            if set {
+                args.push(flag.to_string());
+            }
+        }
+        args
     }
+}
 
-    Ok(args)
+/// `section`, `finding` or `appendix`, or their short forms
+fn parse_kind(kind: &str) -> Result<Kind, String> {
+    Kind::parse(kind).ok_or(format!(
+        "unknown kind \"{kind}\", expected section, finding or appendix"
+    ))
 }
 
-pub fn get_args() -> AppArgs {
-    parse_args().unwrap_or_else(|e| {
-        eprintln!("Error: {e}");
-        std::process::exit(1);
-    })
+const CHECK_FORMATS: [&str; 2] = ["text", "json"];
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create a new report, with --kind with the methodology and sections of an engagement type, with --interactive tailored to the client, engagement type, dates and scope asked for
+    New {
+        /// Directory of the new report
+        dir: Option<PathBuf>,
+        /// Client profile from $XDG_CONFIG_HOME/reportgenerator/clients/<name>.toml prefilling the metadata
+        #[arg(long, value_name = "NAME")]
+        client: Option<String>,
+        /// Initialize a git repository with a .gitignore for compiled reports and local state
+        #[arg(long)]
+        git: bool,
+        /// Engagement type choosing the methodology and default sections
+        #[arg(long = "kind", value_name = "TYPE", value_parser = PossibleValuesParser::new(ENGAGEMENTS.iter().map(|e| e.name)))]
+        engagement: Option<String>,
+        /// Ask for the client, engagement type unless given by --kind, dates and scope
+        #[arg(long)]
+        interactive: bool,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compile the report to PDF using typst
+    Compile(CompileArgs),
+    /// Recompile the report whenever its files or watch_paths change
+    Watch {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+        /// Comma-separated formats to compile concurrently (pdf, png, svg, html)
+        #[arg(long)]
+        format: Option<String>,
+        #[command(flatten)]
+        build: BuildFlags,
+        /// Write MANIFEST.txt with the SHA-256 hashes of the compiled report and its sources, checked by verify
+        #[arg(long)]
+        manifest: bool,
+    },
+    /// Show the report in the browser at http://127.0.0.1:8000, reloaded whenever its files or watch_paths change, or with --api compile reports posted by compile --remote
+    Serve {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Port the server listens on, 8000 by default
+        #[arg(long)]
+        port: Option<u16>,
+        /// Compile reports posted by compile --remote, sharing the token in REPORTGEN_TOKEN
+        #[arg(long)]
+        api: bool,
+        /// Address the compile server listens on, 127.0.0.1 by default, 0.0.0.0 for every interface
+        #[arg(long, requires = "api")]
+        bind: Option<String>,
+        #[command(flatten)]
+        build: BuildFlags,
+    },
+    /// Add a new section
+    NewSection {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Name of the new section
+        #[arg(long)]
+        name: Option<String>,
+        /// Template of the new section
+        #[arg(long)]
+        template: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add a new appendix for long raw output, rendered after the findings
+    NewAppendix {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Name of the new appendix
+        #[arg(long)]
+        name: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add a new finding
+    NewFinding {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Name of the new finding
+        #[arg(long)]
+        name: Option<String>,
+        /// Template of the new finding
+        #[arg(long)]
+        template: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export tagged ATT&CK techniques as a Navigator layer
+    AttackLayer {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Fetch NVD details for CVEs referenced in findings
+    Enrich {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Offline NVD JSON dump
+        #[arg(long, value_name = "FILE")]
+        nvd: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Interactive terminal UI
+    Tui {
+        /// Report directory
+        dir: Option<PathBuf>,
+    },
+    /// Revert the last operation that modified the report
+    Undo {
+        /// Report directory
+        dir: Option<PathBuf>,
+    },
+    /// Language server for report files over stdio
+    Lsp,
+    /// Report problems in front-matter and placeholders
+    Check {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_parser = CHECK_FORMATS)]
+        format: Option<String>,
+        /// Score findings on completeness instead of reporting problems
+        #[arg(long)]
+        quality: bool,
+    },
+    /// Pre-flight checks for CI, fails on any error
+    Validate {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_parser = CHECK_FORMATS)]
+        format: Option<String>,
+    },
+    /// Spell-check with hunspell and report passive voice, TODO markers and double spaces
+    Lint {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_parser = CHECK_FORMATS)]
+        format: Option<String>,
+    },
+    /// Render PNG previews of the built-in templates, or migrate a report to renamed placeholders
+    Templates {
+        #[arg(value_parser = ["gallery", "migrate"])]
+        action: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output directory of the gallery
+        #[arg(short, value_name = "DIR")]
+        output: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Scaffold a guided sample engagement, or check its progress
+    Tutorial {
+        /// Directory of the tutorial report
+        dir: Option<PathBuf>,
+    },
+    /// Summarize findings per severity, CWE, OWASP category and asset, evidence items and estimated pages, as a table or JSON
+    Stats {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_parser = CHECK_FORMATS)]
+        format: Option<String>,
+        /// Summarize the time spent per engagement phase instead
+        #[arg(long)]
+        effort: bool,
+    },
+    /// Print the generated executive summary paragraph for editing
+    Summary {
+        /// Report directory
+        dir: Option<PathBuf>,
+    },
+    /// List sections and findings with their severity, status, review state, word count and last change, or the reports of a workspace, as text or JSON
+    List {
+        /// Report or workspace directory
+        dir: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_parser = CHECK_FORMATS)]
+        format: Option<String>,
+    },
+    /// Set the review state of a section or finding
+    Review {
+        /// Section or finding, e.g. s3 or f7
+        target: String,
+        #[arg(value_parser = ["unreviewed", "reviewed", "approved"])]
+        state: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Reviewer name
+        #[arg(long, value_name = "NAME")]
+        reviewer: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Pack sources, compiled PDF and a hash manifest into an encrypted 7z archive
+    Pack {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+        /// Password of the archive, asked for if missing
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Extract a packed report and verify it against its manifest
+    Unpack {
+        archive: String,
+        /// Directory to extract to
+        dir: Option<PathBuf>,
+        /// Password of the archive, asked for if missing
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Bump the report version and add an entry to the version history
+    Bump {
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Bump the major instead of the minor version
+        #[arg(long, conflicts_with = "minor")]
+        major: bool,
+        /// Bump the minor version, the default
+        #[arg(long)]
+        minor: bool,
+        /// Changes of the new version
+        #[arg(long, value_name = "TEXT")]
+        note: Option<String>,
+        /// Author of the new version, defaults to prepared_by
+        #[arg(long, value_name = "NAME")]
+        author: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Commit the current state of the report to its git repository
+    Snapshot {
+        message: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+    },
+    /// Mark an item of the QA checklist as passed or not passed
+    Qa {
+        #[arg(value_parser = ["set", "unset"])]
+        action: String,
+        item: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Set the report state, delivered requires the QA checklist to pass
+    State {
+        #[arg(value_parser = ["set"])]
+        action: String,
+        #[arg(value_parser = ["draft", "qa", "delivered"])]
+        state: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compare two report directories or git revisions of a report by section and finding
+    Diff {
+        old: String,
+        new: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+    },
+    /// Weekly summary of new findings, severity counts and state of active engagements as Markdown or HTML
+    Digest {
+        /// Report or workspace directory
+        dir: Option<PathBuf>,
+        /// Digest every report below the directory that isn't delivered yet
+        #[arg(long)]
+        workspace: bool,
+        /// Output format
+        #[arg(long, value_parser = ["md", "html"])]
+        format: Option<String>,
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Import testssl.sh or sslyze JSON results into a TLS section, optionally with findings for serious issues
+    ImportTls {
+        file: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Also create a finding for every critical or high TLS issue, listing its hosts
+        #[arg(long)]
+        findings: bool,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create a finding per row of a spreadsheet exported as CSV, columns mapped by [csv_columns] of the config
+    ImportCsv {
+        file: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge amass or subfinder results into attack-surface.tsv for the attack surface appendix
+    ImportSubdomains {
+        file: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Copy the findings register as TSV (default) or CSV to the system clipboard, print the report as versioned JSON, or the findings as DefectDojo Generic Findings Import JSON or SARIF 2.1.0
+    Export {
+        #[arg(value_parser = ["clipboard", "json", "defectdojo", "sarif"])]
+        target: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Format of the findings register
+        #[arg(long, value_parser = ["tsv", "csv"])]
+        format: Option<String>,
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Create an issue tracker ticket per open finding without one and store its key in the front-matter of the finding
+    Push {
+        #[arg(value_parser = ["jira", "github", "gitlab"])]
+        target: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Jira project key, jira_project of the config by default
+        #[arg(long, value_name = "KEY")]
+        project: Option<String>,
+        /// GitHub repository or GitLab project, github_repo or gitlab_repo of the config by default
+        #[arg(long, value_name = "OWNER/NAME")]
+        repo: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Copy a tool output file into the report and add an appendix showing it as a highlighted code block
+    Attach {
+        file: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// What to attach the file as
+        #[arg(long = "as", value_name = "KIND", value_parser = ["appendix"])]
+        kind: Option<String>,
+        /// Language highlighting the attached file, e.g. bash or json
+        #[arg(long, value_name = "LANGUAGE")]
+        syntax: Option<String>,
+        /// Lines of the attached file shown in the appendix, the first and last ones, 400 by default and 0 for all
+        #[arg(long, value_name = "NUMBER")]
+        max_lines: Option<usize>,
+        /// Name of the appendix instead of one from the file name
+        #[arg(long)]
+        name: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Browse a delivered report, packed archive or unpacked directory read-only: findings, search and evidence
+    View {
+        /// Packed archive or report directory
+        target: String,
+        /// Password of the archive, asked for if missing
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Compile a single section, finding or appendix with the template's styling to preview.pdf for fast iteration
+    Preview {
+        #[arg(value_parser = parse_kind)]
+        kind: Kind,
+        id: usize,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Print the shell completion script of the given shell
+    Completions { shell: Shell },
+    /// Print the man page in roff format, or write it to a file
+    Manpage {
+        /// Output file
+        #[arg(short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Renumber sections or findings from 1, closing gaps
+    Reorder {
+        /// sections or findings
+        #[arg(value_parser = parse_kind)]
+        kind: Kind,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move a section or finding to a new position
+    Move {
+        /// Section or finding, e.g. s3 or f7
+        target: String,
+        position: usize,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Retitle a section, finding or appendix: renames its file keeping the number, replaces its heading and updates references to the file name
+    Rename {
+        #[arg(value_parser = parse_kind)]
+        kind: Kind,
+        id: usize,
+        title: String,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete a section, finding or appendix, number the following ones down and delete evidence only it referred to
+    Rm {
+        #[arg(value_parser = parse_kind)]
+        kind: Kind,
+        id: usize,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Keep evidence files only the removed entry referred to
+        #[arg(long)]
+        keep_evidence: bool,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Copy a finding, section or appendix with its front-matter as the next number, named --name or like the original
+    Dup {
+        #[arg(value_parser = parse_kind)]
+        kind: Kind,
+        id: usize,
+        /// Report directory
+        dir: Option<PathBuf>,
+        /// Name of the copy
+        #[arg(long)]
+        name: Option<String>,
+        /// Print planned file changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check a delivered report against its MANIFEST.txt, reporting altered, missing and added files
+    Verify {
+        /// Report directory
+        dir: Option<PathBuf>,
+    },
+    /// Lint, validate and compile every report of a workspace in parallel without prompts, printing the results and artifacts as JSON and exiting with 2, 3 or 4 when linting, validation or compiling failed
+    Ci {
+        /// Report or workspace directory
+        dir: Option<PathBuf>,
+        /// Comma-separated formats to compile concurrently (pdf, png, svg, html)
+        #[arg(long)]
+        format: Option<String>,
+        /// Fail unless every section and finding is approved
+        #[arg(long = "final")]
+        final_report: bool,
+        #[command(flatten)]
+        build: BuildFlags,
+        /// Compile even if the report, its files and the typst command are unchanged since the last build
+        #[arg(long)]
+        force: bool,
+        /// Write MANIFEST.txt with the SHA-256 hashes of the compiled report and its sources, checked by verify
+        #[arg(long)]
+        manifest: bool,
+        /// Don't post the builds to compile_webhook of the config
+        #[arg(long)]
+        no_notify: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct CompileArgs {
+    /// Report directory, or the workspace with --all
+    pub dir: Option<PathBuf>,
+
+    /// Output file
+    #[arg(short, value_name = "FILE", conflicts_with = "all")]
+    pub output: Option<String>,
+
+    /// Comma-separated formats to compile concurrently (pdf, png, svg, html)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Fail unless every section and finding is approved
+    #[arg(long = "final")]
+    pub final_report: bool,
+
+    #[command(flatten)]
+    pub build: BuildFlags,
+
+    /// Compile even if the report, its files and the typst command are unchanged since the last build
+    #[arg(long)]
+    pub force: bool,
+
+    /// Write the fully substituted typst document to a file, or stdout for -, instead of compiling, leftover placeholders only warn and nothing is written into the report
+    #[arg(long, value_name = "PATH", conflicts_with = "all")]
+    pub emit_typ: Option<String>,
+
+    /// Compile every report below the directory in parallel
+    #[arg(long)]
+    pub all: bool,
+
+    /// Encrypt the PDF with AES-256 using qpdf, without print/copy/edit permissions
+    #[arg(long)]
+    pub encrypt: bool,
+
+    /// Password of --encrypt, read from REPORTGEN_PDF_PASSWORD or asked for if missing
+    #[arg(long, requires = "encrypt")]
+    pub password: Option<String>,
+
+    /// Sign the PDF with the certificate and key of a PKCS#12 file using pyHanko
+    // pyHanko asks for the certificate password of every report, encrypting
+    // would break the signature
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["all", "encrypt"])]
+    pub sign: Option<PathBuf>,
+
+    /// Write MANIFEST.txt with the SHA-256 hashes of the compiled report and its sources, checked by verify
+    #[arg(long)]
+    pub manifest: bool,
+
+    /// Don't post the build to compile_webhook of the config
+    #[arg(long)]
+    pub no_notify: bool,
+
+    /// Compile on the compile server at this URL, sharing the token in REPORTGEN_TOKEN
+    #[arg(
+        long,
+        value_name = "URL",
+        conflicts_with_all = ["all", "format", "emit_typ", "encrypt", "sign", "manifest", "tag"]
+    )]
+    pub remote: Option<String>,
 }
@@ -0,0 +1,152 @@
+use std::{
+    fs::read_to_string,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use ::toml::Value;
+
+use crate::consts::ASSETS_FILE;
+use crate::report::{Entry, Report};
+use crate::toml::{self, TableExt};
+use crate::utils::typst_str;
+use crate::xref::IdScheme;
+
+// In-scope hosts and applications, described in `assets.toml` of the report:
+//
+//  [web01]
+//  name = "Customer portal"
+//  address = "portal.example.com"
+//  type = "application"
+//
+// Findings name the assets they affect by ID, e.g. `assets = ["web01"]`,
+// shown as `{{ affected_assets }}` and in `{{ asset_matrix }}`.
+
+pub const FRONT_MATTER_KEY: &str = "assets";
+
+pub struct Asset {
+    pub id: String,
+    pub name: String,
+    pub address: String,
+    pub kind: String,
+}
+
+impl Asset {
+    fn name_or_id(&self) -> String {
+        match self.name.is_empty() {
+            true => self.id.clone(),
+            false => format!("{} ({})", self.name, self.id),
+        }
+    }
+}
+
+/// Assets in the order of `assets.toml`, none without the file
+pub fn load(report_path: &Path) -> Result<Vec<Asset>, String> {
+    let path = report_path.join(ASSETS_FILE);
+    let content = match read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+    let table = toml::parse(&content).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let mut assets = Vec::new();
+    for id in table.keys() {
+        let Some(Value::Table(asset)) = table.get(id) else {
+            return Err(format!(
+                "{}: {id} has to be a table like [{id}]",
+                path.display()
+            ));
+        };
+        let string = |key: &str| asset.get_str(key).unwrap_or_default().to_string();
+        assets.push(Asset {
+            id: id.to_string(),
+            name: string("name"),
+            address: string("address"),
+            kind: string("type"),
+        });
+    }
+    Ok(assets)
+}
+
+/// Asset references of findings that aren't described in `assets.toml`
+pub fn unknown_assets(report: &Report, assets: &[Asset]) -> Vec<(PathBuf, String)> {
+    let mut unknown = Vec::new();
+    for finding in &report.findings {
+        for id in finding.meta.get_str_list(FRONT_MATTER_KEY) {
+            if !assets.iter().any(|a| a.id == id) {
+                unknown.push((finding.path.clone(), id));
+            }
+        }
+    }
+    unknown
+}
+
+/// `{{ affected_assets }}` of a finding, empty if it names no assets
+pub fn render(finding: &Entry, assets: &[Asset]) -> String {
+    let affected: Vec<&Asset> = finding
+        .meta
+        .get_str_list(FRONT_MATTER_KEY)
+        .iter()
+        .filter_map(|id| assets.iter().find(|a| a.id == *id))
+        .collect();
+    if affected.is_empty() {
+        return String::new();
+    }
+
+    let mut table = String::from(
+        "#table(\n  columns: (auto, 1fr, auto, auto),\n  \
+         table.header(table.cell(colspan: 4)[*Affected assets*], [*ID*], [*Name*], [*Address*], [*Type*]),\n",
+    );
+    for asset in affected {
+        table.push_str(&format!(
+            "  {}, {}, {}, {},\n",
+            typst_str(&asset.id),
+            typst_str(&asset.name),
+            typst_str(&asset.address),
+            typst_str(&asset.kind)
+        ));
+    }
+    table.push_str(")\n");
+    table
+}
+
+/// Matrix of assets by finding, empty if no finding names an asset
+pub fn render_matrix(report: &Report, assets: &[Asset], ids: &IdScheme) -> String {
+    let findings: Vec<(&Entry, Vec<String>)> = report
+        .findings
+        .iter()
+        .map(|finding| (finding, finding.meta.get_str_list(FRONT_MATTER_KEY)))
+        .filter(|(_, affected)| !affected.is_empty())
+        .collect();
+    if findings.is_empty() {
+        return String::new();
+    }
+
+    let mut header = vec!["[*Asset*]".to_string()];
+    header.extend(
+        findings
+            .iter()
+            .map(|(finding, _)| format!("strong({})", typst_str(&ids.id(finding)))),
+    );
+    let mut matrix = format!(
+        "#pagebreak()\n= Appendix: Affected Assets\n#table(\n  columns: {},\n  table.header({}),\n",
+        findings.len() + 1,
+        header.join(", ")
+    );
+    for asset in assets {
+        let mut row = vec![typst_str(&asset.name_or_id())];
+        for (_, affected) in &findings {
+            let mark = if affected.contains(&asset.id) {
+                "✓"
+            } else {
+                ""
+            };
+            row.push(typst_str(mark));
+        }
+        matrix.push_str(&format!("  {},\n", row.join(", ")));
+    }
+    matrix.push_str(")\n");
+
+    matrix
+}
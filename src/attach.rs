@@ -0,0 +1,167 @@
+use std::{
+    error::Error,
+    fs::{read, read_dir},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use crate::changes::Changes;
+use crate::consts::ATTACHMENTS_DIR;
+use crate::report::{self, Report};
+use crate::utils::typst_escape;
+
+// Raw tool output (scanner logs, command transcripts) attached to the report.
+// The file is copied to `attachments/` so it is delivered with `pack`, and an
+// appendix shows it as a highlighted raw block. Huge files keep only their
+// first and last lines in the appendix, typst wraps the long lines.
+
+const DEFAULT_MAX_LINES: usize = 400;
+const KINDS: &[&str] = &["appendix"];
+
+/// Text without terminal escape sequences like colors, which tools print
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+        // CSI sequences end at their first letter
+        if chars.next_if_eq(&'[').is_some() {
+            while chars.next().is_some_and(|c| !c.is_ascii_alphabetic()) {}
+        }
+    }
+    stripped.replace('\r', "")
+}
+
+/// First and last lines of `text`, at most `max_lines` of them (0 keeps all),
+/// with a note about the omitted ones in between
+fn shorten(text: &str, max_lines: usize, attachment: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    if max_lines == 0 || lines.len() <= max_lines {
+        return lines.join("\n");
+    }
+    let head = max_lines.div_ceil(2);
+    let tail = max_lines - head;
+    let mut kept = lines[..head].to_vec();
+    let note = format!(
+        "[… {} lines omitted, full output in {attachment} …]",
+        lines.len() - max_lines
+    );
+    kept.push(&note);
+    kept.extend(&lines[lines.len() - tail..]);
+    kept.join("\n")
+}
+
+/// Name of the appendix derived from the attached file name
+fn slug(file_name: &str) -> String {
+    let stem = Path::new(file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(file_name);
+    let slug: String = stem
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '-',
+        })
+        .collect();
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn appendix(file_name: &str, output: &str, syntax: Option<&str>, attachment: &str) -> String {
+    // The fence has to be longer than any backtick run of the output
+    let longest = output.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "= {}\nFull output in {}\n{fence}{}\n{output}\n{fence}\n",
+        typst_escape(file_name),
+        typst_escape(attachment),
+        syntax.unwrap_or(""),
+    )
+}
+
+pub fn attach(
+    file: String,
+    report_dir: Option<PathBuf>,
+    kind: Option<String>,
+    syntax: Option<String>,
+    max_lines: Option<usize>,
+    name: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let kind = kind.unwrap_or("appendix".to_string());
+    if !KINDS.contains(&kind.as_str()) {
+        eprintln!(
+            "ERROR: Unknown attachment kind \"{kind}\", expected {}",
+            KINDS.join(" or ")
+        );
+        exit(1);
+    }
+    if let Some(ref syntax) = syntax {
+        if syntax.is_empty()
+            || !syntax
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-#".contains(c))
+        {
+            eprintln!("ERROR: Invalid syntax \"{syntax}\", e.g. bash or json");
+            exit(1);
+        }
+    }
+
+    let report_path = report::resolve_path(report_dir);
+    Report::load_or_exit(&report_path);
+
+    let source = Path::new(&file);
+    let content = read(source).unwrap_or_else(|e| {
+        eprintln!("ERROR: Failed to read {file}: {e}");
+        exit(1);
+    });
+    let Some(file_name) = source.file_name().and_then(|n| n.to_str()) else {
+        eprintln!("ERROR: Invalid file name {file}");
+        exit(1);
+    };
+    let attachment = format!("{ATTACHMENTS_DIR}/{file_name}");
+    if report_path.join(&attachment).exists() {
+        eprintln!("ERROR: {attachment} already exists in the report");
+        exit(1);
+    }
+
+    let output = strip_ansi(&String::from_utf8_lossy(&content));
+    let output = shorten(&output, max_lines.unwrap_or(DEFAULT_MAX_LINES), &attachment);
+
+    let mut changes = Changes::for_report(&report_path);
+    let attachments = report_path.join(ATTACHMENTS_DIR);
+    if !attachments.is_dir() {
+        changes.create_dir(attachments.clone());
+    }
+    changes.copy(source.to_path_buf(), attachments.join(file_name));
+
+    let appendices = report_path.join("appendices");
+    let appendices_count = match appendices.is_dir() {
+        true => read_dir(&appendices)?.count(),
+        false => {
+            changes.create_dir(appendices.clone());
+            0
+        }
+    };
+    let name = name.unwrap_or_else(|| slug(file_name));
+    let appendix_fname = format!("{}.{name}.typ", appendices_count + 1);
+    changes.create(
+        appendices.join(&appendix_fname),
+        &appendix(file_name, &output, syntax.as_deref(), &attachment),
+    );
+    changes.apply(dry_run)?;
+
+    if dry_run {
+        return Ok(());
+    }
+
+    println!("Attached {file_name} as appendix \"{appendix_fname}\"");
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use crate::consts::ATTACK_TECHNIQUES;
+use crate::report::{Entry, Report};
+use crate::toml::TableExt;
+use crate::utils::typst_str;
+use crate::xref::IdScheme;
+
+// Front-matter key holding the ATT&CK technique IDs of a section/finding
+pub const FRONT_MATTER_KEY: &str = "attack";
+
+pub struct Technique {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub tactics: &'static str,
+}
+
+/// All techniques of the embedded ATT&CK dataset
+pub fn techniques() -> impl Iterator<Item = Technique> {
+    ATTACK_TECHNIQUES
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let mut split = line.split('\t');
+            Technique {
+                id: split.next().unwrap_or_default(),
+                name: split.next().unwrap_or_default(),
+                tactics: split.next().unwrap_or_default(),
+            }
+        })
+}
+
+/// Looks up a technique in the embedded ATT&CK dataset
+pub fn lookup(id: &str) -> Option<Technique> {
+    techniques().find(|t| t.id.eq_ignore_ascii_case(id))
+}
+
+/// Returns every tagged technique ID (normalized to upper case) with the entries referencing it
+pub fn tagged(report: &Report) -> Vec<(String, Vec<&Entry>)> {
+    let mut techniques: Vec<(String, Vec<&Entry>)> = Vec::new();
+    for entry in report.entries() {
+        for id in entry.meta.get_str_list(FRONT_MATTER_KEY) {
+            let id = id.trim().to_uppercase();
+            match techniques.iter_mut().find(|(t, _)| *t == id) {
+                Some((_, entries)) => entries.push(entry),
+                None => techniques.push((id, vec![entry])),
+            }
+        }
+    }
+    techniques.sort_by(|a, b| a.0.cmp(&b.0));
+    techniques
+}
+
+/// Returns technique IDs not present in the embedded dataset with the file referencing them
+pub fn unknown_techniques(report: &Report) -> Vec<(PathBuf, String)> {
+    let mut unknown = Vec::new();
+    for (id, entries) in tagged(report) {
+        if lookup(&id).is_none() {
+            for entry in entries {
+                unknown.push((entry.path.clone(), id.clone()));
+            }
+        }
+    }
+    unknown
+}
+
+/// Renders the techniques appendix, empty if nothing is tagged
+pub fn render_appendix(report: &Report) -> String {
+    let techniques = tagged(report);
+    if techniques.is_empty() {
+        return String::new();
+    }
+    let ids = IdScheme::of(report);
+
+    let mut appendix = String::from(
+        "#pagebreak()\n= Appendix: MITRE ATT&CK Techniques\n\
+         #table(\n  columns: 4,\n  [*ID*], [*Technique*], [*Tactics*], [*Referenced in*],\n",
+    );
+    for (id, entries) in techniques {
+        let technique = lookup(&id).expect("techniques are validated before rendering");
+        let referenced_in: Vec<String> =
+            entries.iter().map(|e| ids.entry_title(report, e)).collect();
+        appendix.push_str(&format!(
+            "  {}, {}, {}, {},\n",
+            typst_str(technique.id),
+            typst_str(technique.name),
+            typst_str(technique.tactics),
+            typst_str(&referenced_in.join(", ")),
+        ));
+    }
+    appendix.push_str(")\n");
+
+    appendix
+}
@@ -0,0 +1,71 @@
+use std::{error::Error, fs::write, path::PathBuf, process::exit};
+
+use serde_json::json;
+
+use crate::attack;
+use crate::consts::*;
+use crate::report::{self, Report};
+use crate::xref::IdScheme;
+
+pub fn attack_layer(
+    report_dir: Option<PathBuf>,
+    output: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    let unknown = attack::unknown_techniques(&report);
+    if !unknown.is_empty() {
+        for (path, id) in unknown {
+            eprintln!("ERROR: Unknown ATT&CK technique {id} in {}", path.display());
+        }
+        exit(1);
+    }
+
+    let tagged = attack::tagged(&report);
+    let ids = IdScheme::of(&report);
+    let max_score = tagged.iter().map(|(_, e)| e.len()).max().unwrap_or(1);
+
+    let techniques = tagged
+        .iter()
+        .map(|(id, entries)| {
+            let titles: Vec<String> = entries
+                .iter()
+                .map(|e| ids.entry_title(&report, e))
+                .collect();
+            json!({
+                "techniqueID": id,
+                "score": entries.len(),
+                "comment": titles.join(", "),
+                "enabled": true,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let name = report.metadata_value("report_title").unwrap_or("Report");
+
+    // ATT&CK Navigator layer format 4.5
+    let layer = json!({
+        "name": name,
+        "versions": {
+            "attack": "14",
+            "navigator": "4.9.1",
+            "layer": "4.5",
+        },
+        "domain": "enterprise-attack",
+        "description": format!("Techniques used during \"{name}\""),
+        "techniques": techniques,
+        "gradient": {
+            "colors": ["#ffe766ff", "#ff6666ff"],
+            "minValue": 0,
+            "maxValue": max_score,
+        },
+    });
+
+    let output_file = output.as_deref().unwrap_or(DEFAULT_ATTACK_LAYER_FILE);
+    write(output_file, serde_json::to_string_pretty(&layer)?)?;
+
+    println!("ATT&CK Navigator layer written to \"{output_file}\"");
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use crate::consts::ATTACHMENTS_DIR;
+use crate::report::{Entry, Report};
+use crate::sha256;
+
+// Huge encoded blobs in raw evidence (base64, hex, JWTs) are unreadable and
+// fill pages. Those longer than `blob_max_length` of the config (512
+// characters by default, 0 keeps them) are cut short in the compiled report
+// with a reference to the full value, written to `attachments/` of the report
+// so it is delivered with `pack`.
+
+pub const DEFAULT_MAX_LENGTH: usize = 512;
+// Characters of a blob kept in the report
+const KEPT: usize = 48;
+
+pub struct Attachment {
+    pub name: String,
+    pub value: String,
+}
+
+fn is_blob(token: &str, max_length: usize) -> bool {
+    token.len() > max_length
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+/=_-.".contains(c))
+}
+
+/// Raw text with its blobs cut short
+fn truncate_raw(text: &str, max_length: usize, attachments: &mut Vec<Attachment>) -> String {
+    text.split(' ')
+        .map(|token| {
+            if !is_blob(token, max_length) {
+                return token.to_string();
+            }
+            let name = format!("blob-{}.txt", &sha256::hex_digest(token.as_bytes())[..12]);
+            let reference = format!(
+                "{}… ({} characters, full value in {ATTACHMENTS_DIR}/{name})",
+                &token[..KEPT.min(max_length)],
+                token.len()
+            );
+            if !attachments.iter().any(|a| a.name == name) {
+                attachments.push(Attachment {
+                    name,
+                    value: token.to_string(),
+                });
+            }
+            reference
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Body with the blobs of raw blocks and inline raw text cut short
+fn truncate_body(body: &str, max_length: usize, attachments: &mut Vec<Attachment>) -> String {
+    let mut in_raw = false;
+    let mut truncated = String::with_capacity(body.len());
+    for line in body.split_inclusive('\n') {
+        let (text, newline) = match line.strip_suffix('\n') {
+            Some(text) => (text, "\n"),
+            None => (line, ""),
+        };
+        if text.trim_start().starts_with("```") {
+            in_raw = !in_raw;
+        } else if in_raw {
+            truncated.push_str(&truncate_raw(text, max_length, attachments));
+            truncated.push_str(newline);
+            continue;
+        }
+        // Odd parts are inline raw text between backticks
+        let parts: Vec<String> = text
+            .split('`')
+            .enumerate()
+            .map(|(i, part)| match i % 2 {
+                1 => truncate_raw(part, max_length, attachments),
+                _ => part.to_string(),
+            })
+            .collect();
+        truncated.push_str(&parts.join("`"));
+        truncated.push_str(newline);
+    }
+    truncated
+}
+
+fn truncate_entries(entries: &mut [Entry], max_length: usize, attachments: &mut Vec<Attachment>) {
+    for entry in entries {
+        entry.body = truncate_body(&entry.body, max_length, attachments);
+        truncate_entries(&mut entry.children, max_length, attachments);
+    }
+}
+
+/// Cuts the blobs of every section and finding short, returning their full values
+pub fn truncate(report: &mut Report, max_length: usize) -> Vec<Attachment> {
+    let mut attachments = Vec::new();
+    if max_length == 0 {
+        return attachments;
+    }
+    truncate_entries(&mut report.sections, max_length, &mut attachments);
+    truncate_entries(&mut report.findings, max_length, &mut attachments);
+    truncate_entries(&mut report.appendices, max_length, &mut attachments);
+    attachments
+}
+
+/// Writes the attachments to the report, leaving unchanged ones alone
+pub fn write_attachments(report_path: &Path, attachments: &[Attachment]) -> Result<(), String> {
+    if attachments.is_empty() {
+        return Ok(());
+    }
+    let dir = report_path.join(ATTACHMENTS_DIR);
+    create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    for attachment in attachments {
+        let path = dir.join(&attachment.name);
+        if read_to_string(&path).is_ok_and(|content| content == attachment.value) {
+            continue;
+        }
+        write(&path, &attachment.value)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::{
+    fs::{copy, read, read_to_string},
+    path::{Path, PathBuf},
+};
+
+use crate::config;
+use crate::consts::BRANDS_DIR;
+use crate::severity;
+use crate::toml;
+use crate::toml::TableExt;
+use crate::utils::{typst_escape, typst_str};
+
+// Branding profiles kept in `$XDG_CONFIG_HOME/reportgenerator/brands/<name>.toml`,
+// used by `compile --brand <name>` or `brand = "<name>"` in the config. The
+// built-in templates read them from the `{{ brand_* }}` placeholders:
+//
+//  logo = "acme.png"
+//  accent_color = "#b00020"
+//  font = "Source Sans 3"
+//  heading_font = "Source Serif 4"
+//  footer = "ACME Security - Strictly Confidential"
+
+const DEFAULT_LOGO: &str = "Penetration Test Report Logo";
+const DEFAULT_ACCENT_COLOR: &str = "#0074d9";
+const DEFAULT_FONT: &str = "Noto Sans";
+const DEFAULT_FOOTER: &str = "Client Confidential";
+
+#[derive(Default)]
+pub struct Brand {
+    // Relative paths are resolved against the brands directory
+    pub logo: Option<PathBuf>,
+    pub accent_color: Option<String>,
+    pub font: Option<String>,
+    pub heading_font: Option<String>,
+    pub footer: Option<String>,
+}
+
+pub fn load(name: &str) -> Result<Brand, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("Invalid brand name \"{name}\""));
+    }
+    let dir = config::config_home()
+        .ok_or("Config directory not found, set XDG_CONFIG_HOME or HOME")?
+        .join(BRANDS_DIR);
+    let path = dir.join(format!("{name}.toml"));
+    let content = read_to_string(&path)
+        .map_err(|e| format!("Brand profile {} not readable: {e}", path.display()))?;
+    let table = toml::parse(&content).map_err(|e| format!("{}: {e}", path.display()))?;
+    let string = |key: &str| table.get_str(key).map(str::to_string);
+
+    let accent_color = string("accent_color");
+    if let Some(ref color) = accent_color {
+        if !severity::is_color(color) {
+            return Err(format!(
+                "{}: accent_color has to be a #rrggbb color, got \"{color}\"",
+                path.display()
+            ));
+        }
+    }
+
+    Ok(Brand {
+        logo: string("logo").map(|logo| dir.join(logo)),
+        accent_color,
+        font: string("font"),
+        heading_font: string("heading_font"),
+        footer: string("footer"),
+    })
+}
+
+/// Copies an image into the report as `<stem>.<ext>` so typst can access
+/// it, only when changed so repeated compiles leave the report alone.
+/// Without `write` only its name is returned, e.g. for `--emit-typ`.
+pub fn copy_image(
+    image: &Path,
+    report_path: &Path,
+    stem: &str,
+    write: bool,
+) -> Result<String, String> {
+    let extension = image.extension().unwrap_or_default().to_string_lossy();
+    let file_name = format!("{stem}.{extension}");
+    let target = report_path.join(&file_name);
+    let content = read(image).map_err(|e| format!("{} not readable: {e}", image.display()))?;
+    if write && read(&target).ok() != Some(content) {
+        copy(image, &target)
+            .map_err(|e| format!("{} can't be copied into the report: {e}", image.display()))?;
+    }
+    Ok(file_name)
+}
+
+impl Brand {
+    /// `{{ brand_* }}` placeholders, the logo is copied into the report
+    /// unless `write` is off
+    pub fn context(
+        &self,
+        report_path: &Path,
+        write: bool,
+    ) -> Result<Vec<(String, String)>, String> {
+        let logo = match self.logo {
+            Some(ref logo) => {
+                let file_name = copy_image(logo, report_path, "brand-logo", write)
+                    .map_err(|e| format!("Brand logo {e}"))?;
+                format!("#image({}, height: 1cm)", typst_str(&file_name))
+            }
+            None => DEFAULT_LOGO.to_string(),
+        };
+        let font = self.font.as_deref().unwrap_or(DEFAULT_FONT);
+
+        Ok(vec![
+            ("brand_logo".to_string(), logo),
+            (
+                "brand_accent_color".to_string(),
+                self.accent_color
+                    .as_deref()
+                    .unwrap_or(DEFAULT_ACCENT_COLOR)
+                    .to_string(),
+            ),
+            ("brand_font".to_string(), typst_str(font)),
+            (
+                "brand_heading_font".to_string(),
+                typst_str(self.heading_font.as_deref().unwrap_or(font)),
+            ),
+            (
+                "brand_footer".to_string(),
+                typst_escape(self.footer.as_deref().unwrap_or(DEFAULT_FOOTER)),
+            ),
+        ])
+    }
+}
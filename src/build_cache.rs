@@ -0,0 +1,135 @@
+use std::{
+    fs::{create_dir_all, metadata, read_dir, read_to_string, remove_file, write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use crate::consts::{BUILD_CACHE_DIR, STATE_DIR};
+use crate::sha256;
+
+// Skips typst when nothing a build depends on changed since the last one.
+// Assembling the document is plain string substitution and takes no time,
+// typst rendering the pages and embedding images is what makes large reports
+// slow. The fingerprint of a build covers the assembled document, the typst
+// command and every file of the report by size and modification time, so
+// replaced images are picked up without hashing them. Fingerprints are kept
+// per format in `.reportgenerator/build/`.
+
+// Extensions of the formats typst compiles to
+const OUTPUT_EXTENSIONS: &[&str] = &["pdf", "png", "svg", "html"];
+
+/// Whether a file name matches `pattern`, `{p}` standing for a page number
+fn matches_page_pattern(name: &str, pattern: &str) -> bool {
+    match pattern.split_once("{p}") {
+        Some((prefix, suffix)) => name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .is_some_and(|page| !page.is_empty() && page.chars().all(|c| c.is_ascii_digit())),
+        None => name == pattern,
+    }
+}
+
+/// Files written by builds of `output` in any format, e.g. report.pdf and
+/// report-1.png, their changes don't count
+fn is_output(path: &Path, output: &str) -> bool {
+    let stem = Path::new(output)
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let stem = stem.trim_end_matches("-{p}");
+    let (Some(name), Some(extension)) = (path.file_stem(), path.extension()) else {
+        return false;
+    };
+    let name = name.to_string_lossy();
+    OUTPUT_EXTENSIONS.contains(&extension.to_string_lossy().as_ref())
+        && (name == stem || matches_page_pattern(&name, &format!("{stem}-{{p}}")))
+}
+
+/// Path, size and modification time of every file below `dir`
+fn list_files(dir: &Path, output: &str, listing: &mut Vec<String>) {
+    let Ok(entries) = read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.file_name().is_some_and(|name| name == STATE_DIR) || is_output(&path, output) {
+            continue;
+        }
+        if path.is_dir() {
+            list_files(&path, output, listing);
+        } else if let Ok(meta) = metadata(&path) {
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |time| time.as_nanos());
+            listing.push(format!("{} {} {modified}", path.display(), meta.len()));
+        }
+    }
+}
+
+/// Fingerprint of compiling `document` to `output`
+pub fn fingerprint(
+    report_path: &Path,
+    document: &str,
+    output: &str,
+    format: &str,
+    typst: &str,
+    font_paths: &[PathBuf],
+) -> String {
+    let mut listing = vec![
+        typst.to_string(),
+        format.to_string(),
+        output.to_string(),
+        format!("{font_paths:?}"),
+    ];
+    list_files(report_path, output, &mut listing);
+    listing.sort();
+    sha256::hex_digest(format!("{}\n{document}", listing.join("\n")).as_bytes())
+}
+
+fn cache_file(report_path: &Path, format: &str) -> PathBuf {
+    report_path
+        .join(STATE_DIR)
+        .join(BUILD_CACHE_DIR)
+        .join(format)
+}
+
+/// Whether the last build of `format` had this fingerprint and its output is still there
+pub fn is_fresh(report_path: &Path, format: &str, fingerprint: &str, output: &str) -> bool {
+    let output_exists = match output.contains("{p}") {
+        true => Path::new(output)
+            .parent()
+            .map(|dir| match dir.as_os_str().is_empty() {
+                true => Path::new("."),
+                false => dir,
+            })
+            .and_then(|dir| read_dir(dir).ok())
+            .is_some_and(|entries| {
+                let pattern = Path::new(output).file_name().unwrap_or_default();
+                entries.flatten().any(|entry| {
+                    matches_page_pattern(
+                        &entry.file_name().to_string_lossy(),
+                        &pattern.to_string_lossy(),
+                    )
+                })
+            }),
+        false => Path::new(output).is_file(),
+    };
+    output_exists
+        && read_to_string(cache_file(report_path, format))
+            .is_ok_and(|cached| cached.trim() == fingerprint)
+}
+
+pub fn store(report_path: &Path, format: &str, fingerprint: &str) -> Result<(), String> {
+    let path = cache_file(report_path, format);
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+    write(&path, fingerprint).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Forgets the last build of `format`, e.g. after an encrypted one
+pub fn clear(report_path: &Path, format: &str) {
+    let _ = remove_file(cache_file(report_path, format));
+}
@@ -0,0 +1,107 @@
+use std::{
+    error::Error,
+    fs::{copy, create_dir_all, remove_file, rename, write, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::undo;
+
+// File system changes planned by a command. Commands that create, modify or
+// delete several files collect them here so `--dry-run` can print the plan
+// instead of touching the disk.
+pub enum Change {
+    CreateDir(PathBuf),
+    // Fails if the file already exists
+    Create(PathBuf, String),
+    Write(PathBuf, String),
+    Rename(PathBuf, PathBuf),
+    // Copies a file from outside, e.g. a logo
+    Copy(PathBuf, PathBuf),
+    Remove(PathBuf),
+}
+
+#[derive(Default)]
+pub struct Changes {
+    changes: Vec<Change>,
+    // Report whose undo stack records the changes
+    report_path: Option<PathBuf>,
+}
+
+impl Changes {
+    /// Changes inside an existing report, recorded on its undo stack when applied
+    pub fn for_report(report_path: &Path) -> Self {
+        Self {
+            changes: Vec::new(),
+            report_path: Some(report_path.to_path_buf()),
+        }
+    }
+
+    pub fn create_dir(&mut self, path: PathBuf) {
+        self.changes.push(Change::CreateDir(path));
+    }
+
+    pub fn create(&mut self, path: PathBuf, content: &str) {
+        self.changes.push(Change::Create(path, content.to_string()));
+    }
+
+    pub fn write(&mut self, path: PathBuf, content: &str) {
+        self.changes.push(Change::Write(path, content.to_string()));
+    }
+
+    pub fn rename(&mut self, from: PathBuf, to: PathBuf) {
+        self.changes.push(Change::Rename(from, to));
+    }
+
+    pub fn copy(&mut self, from: PathBuf, to: PathBuf) {
+        self.changes.push(Change::Copy(from, to));
+    }
+
+    pub fn remove(&mut self, path: PathBuf) {
+        self.changes.push(Change::Remove(path));
+    }
+
+    /// Applies all changes in order, or only prints them when `dry_run` is set
+    pub fn apply(self, dry_run: bool) -> Result<(), Box<dyn Error>> {
+        if let (false, Some(report_path)) = (dry_run, &self.report_path) {
+            undo::record(report_path, &self.changes)?;
+        }
+
+        for change in self.changes {
+            if dry_run {
+                println!("[dry-run] {}", describe(&change));
+                continue;
+            }
+
+            match change {
+                Change::CreateDir(path) => create_dir_all(path)?,
+                Change::Create(path, content) => {
+                    File::create_new(path)?.write_all(content.as_bytes())?
+                }
+                Change::Write(path, content) => write(path, content)?,
+                Change::Rename(from, to) => rename(from, to)?,
+                Change::Copy(from, to) => {
+                    copy(from, to)?;
+                }
+                Change::Remove(path) => remove_file(path)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn describe(change: &Change) -> String {
+    match change {
+        Change::CreateDir(path) => format!("create directory {}", path.display()),
+        Change::Create(path, content) => {
+            format!("create {} ({} bytes)", path.display(), content.len())
+        }
+        Change::Write(path, content) => {
+            format!("write {} ({} bytes)", path.display(), content.len())
+        }
+        Change::Rename(from, to) => format!("rename {} -> {}", from.display(), to.display()),
+        Change::Copy(from, to) => format!("copy {} -> {}", from.display(), to.display()),
+        Change::Remove(path) => format!("remove {}", path.display()),
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    error::Error,
+    fs::{read, read_dir},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use serde_json::{json, Value};
+
+use crate::assets;
+use crate::config::Config;
+use crate::diagnostics::{self, Diagnostic, Level};
+use crate::quality;
+use crate::report::{self, Report};
+use crate::scope;
+
+// Version of the `--format json` output, bump on incompatible changes
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "typ") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Section, finding and appendix files of a report including chapter directories, sorted by path
+pub fn report_files(report_path: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    for dir in ["sections", "findings"] {
+        collect_files(&report_path.join(dir), &mut files)?;
+    }
+    // Reports without appendices have no directory for them
+    if report_path.join("appendices").is_dir() {
+        collect_files(&report_path.join("appendices"), &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Diagnostics of every section and finding file
+pub fn check_report(report_path: &Path) -> Result<Vec<Diagnostic>, Box<dyn Error>> {
+    let config = Config::load_or_exit(report_path);
+    let severities = config.severities();
+    let presentation = config.severity_presentation();
+    // Finding numbers for references, a report that doesn't load has bigger problems
+    let findings: Option<Vec<usize>> = Report::load(report_path)
+        .ok()
+        .map(|report| report.findings.iter().map(|f| f.id).collect());
+    let assets: Vec<String> = match assets::load(report_path) {
+        Ok(assets) => assets.into_iter().map(|a| a.id).collect(),
+        Err(e) => {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        }
+    };
+    let scope = scope::load(report_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let mut diagnostics = Vec::new();
+    for path in report_files(report_path)? {
+        let message = match read(&path).map(String::from_utf8) {
+            Ok(Ok(text)) => {
+                diagnostics.extend(diagnostics::check_file(&path, &text, &severities));
+                diagnostics.extend(diagnostics::check_assets(&path, &text, &assets));
+                diagnostics.extend(diagnostics::check_score(&path, &text, &presentation));
+                if let Some(ref scope) = scope {
+                    diagnostics.extend(diagnostics::check_scope(&path, &text, scope));
+                }
+                if let Some(ref findings) = findings {
+                    diagnostics.extend(diagnostics::check_references(&path, &text, findings));
+                }
+                continue;
+            }
+            Ok(Err(_)) => "File is not valid UTF-8".to_string(),
+            Err(e) => format!("Failed to read file: {e}"),
+        };
+        diagnostics.push(Diagnostic::error(path, 0, message));
+    }
+    Ok(diagnostics)
+}
+
+/// Prints diagnostics as `file:line:column: severity: message` or as JSON
+pub fn print_diagnostics(
+    report_path: &Path,
+    diagnostics: &[Diagnostic],
+    format: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let count = |level| diagnostics.iter().filter(|d| d.level == level).count();
+
+    match format.unwrap_or("text") {
+        "text" => {
+            for d in diagnostics {
+                let file = d.path.strip_prefix(report_path).unwrap_or(&d.path);
+                println!(
+                    "{}:{}:{}: {}: {}",
+                    file.display(),
+                    d.line + 1,
+                    d.start + 1,
+                    d.level.as_str(),
+                    d.message
+                );
+            }
+            println!(
+                "{} error(s), {} warning(s)",
+                count(Level::Error),
+                count(Level::Warning)
+            );
+        }
+        "json" => {
+            let diagnostics: Vec<Value> =
+                diagnostics.iter().map(|d| d.to_json(report_path)).collect();
+            let output = json!({
+                "version": JSON_FORMAT_VERSION,
+                "diagnostics": diagnostics,
+                "errors": count(Level::Error),
+                "warnings": count(Level::Warning),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format => {
+            eprintln!("ERROR: Unknown format \"{format}\", expected text or json");
+            exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the findings ordered from the weakest write-up to the most complete
+fn print_quality(report: &Report, format: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut scored: Vec<_> = report
+        .findings
+        .iter()
+        .map(|finding| (finding, quality::score(finding)))
+        .collect();
+    scored.sort_by_key(|(finding, quality)| (quality.score, finding.id));
+
+    match format.unwrap_or("text") {
+        "text" => {
+            println!("Finding quality (weakest first):");
+            for (finding, quality) in &scored {
+                let missing = if quality.missing.is_empty() {
+                    String::new()
+                } else {
+                    format!("  missing: {}", quality.missing.join(", "))
+                };
+                println!(
+                    "  {:>3}/100  f{:<4} {}{missing}",
+                    quality.score,
+                    finding.id,
+                    finding.title()
+                );
+            }
+        }
+        "json" => {
+            let findings = scored
+                .iter()
+                .map(|(finding, quality)| {
+                    let file = finding
+                        .path
+                        .strip_prefix(&report.path)
+                        .unwrap_or(&finding.path);
+                    json!({
+                        "file": file.to_string_lossy(),
+                        "title": finding.title(),
+                        "score": quality.score,
+                        "missing": quality.missing,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let output = json!({
+                "version": JSON_FORMAT_VERSION,
+                "findings": findings,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format => {
+            eprintln!("ERROR: Unknown format \"{format}\", expected text or json");
+            exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn check(
+    report_dir: Option<PathBuf>,
+    format: Option<String>,
+    quality: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+
+    if quality {
+        let report = Report::load_or_exit(&report_path);
+        return print_quality(&report, format.as_deref());
+    }
+
+    let diagnostics = check_report(&report_path)?;
+    print_diagnostics(&report_path, &diagnostics, format.as_deref())?;
+
+    // Fail on errors so CI can gate on the check
+    if diagnostics.iter().any(|d| d.level == Level::Error) {
+        exit(1);
+    }
+
+    Ok(())
+}
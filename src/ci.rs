@@ -0,0 +1,214 @@
+use std::{
+    env,
+    error::Error,
+    path::{Path, PathBuf},
+    process::{exit, Command, Stdio},
+    thread,
+};
+
+use serde_json::{json, Value};
+
+use crate::check::JSON_FORMAT_VERSION;
+use crate::compile_report::format_output;
+use crate::config::Config;
+use crate::consts::DEFAULT_REPORT_FILE;
+use crate::workspace;
+
+// Report QA gate for pipelines: lints, validates and compiles every report of
+// a workspace (or a single report), several reports at a time, and prints the
+// results as JSON on stdout. Nothing is asked for, children get no stdin.
+//
+//  report-qa:
+//    script: report-generator ci reports/ > ci.json
+//    artifacts:
+//      when: always
+//      paths: [ci.json, "reports/**/*.pdf"]
+//
+// Reports failing validation aren't compiled. The exit code names the first
+// stage that failed in any report.
+
+const STAGES: &[&str] = &["lint", "validate", "compile"];
+// Exit codes of failed stages, in the order of STAGES
+const EXIT_CODES: &[i32] = &[2, 3, 4];
+
+struct Stage {
+    // passed, failed or skipped
+    status: &'static str,
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl Stage {
+    fn passed(&self) -> bool {
+        self.status != "failed"
+    }
+
+    fn to_json(&self) -> Value {
+        let mut fields = vec![("status", json!(self.status))];
+        fields.extend(self.fields.iter().cloned());
+        fields.into_iter().collect()
+    }
+}
+
+fn run(exe: &Path, report_path: &Path, args: &[String]) -> Result<(bool, String, String), String> {
+    let output = Command::new(exe)
+        .args(args)
+        .current_dir(report_path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run {}: {e}", args[0]))?;
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    ))
+}
+
+/// Runs lint or validate with JSON output, keeping their diagnostics
+fn check_stage(exe: &Path, report_path: &Path, command: &str) -> Stage {
+    let args = [command, ".", "--format", "json"].map(String::from);
+    let (ok, stdout, stderr) = match run(exe, report_path, &args) {
+        Ok(result) => result,
+        Err(e) => (false, String::new(), e),
+    };
+    let mut fields = Vec::new();
+    match serde_json::from_str::<Value>(&stdout) {
+        Ok(json) => {
+            for key in ["errors", "warnings", "diagnostics"] {
+                if let Some(value) = json.get(key) {
+                    fields.push((key, value.clone()));
+                }
+            }
+        }
+        // Config errors and the like end the command before any diagnostics
+        Err(_) => fields.push(("log", json!(stderr.trim()))),
+    }
+    Stage {
+        status: if ok { "passed" } else { "failed" },
+        fields,
+    }
+}
+
+/// Files compile writes for a report, relative to the current directory
+fn artifacts(report_path: &Path, formats: Option<&str>) -> Vec<String> {
+    let output = Config::load(report_path)
+        .ok()
+        .and_then(|config| config.output)
+        .unwrap_or(DEFAULT_REPORT_FILE.to_string());
+    let output = report_path.join(output).display().to_string();
+    match formats {
+        // A single PDF keeps the output name as is
+        None | Some("pdf") => vec![output],
+        Some(formats) => formats
+            .split(',')
+            .map(|format| format_output(&output, format.trim()))
+            .collect(),
+    }
+}
+
+fn compile_stage(
+    exe: &Path,
+    report_path: &Path,
+    formats: Option<&str>,
+    compile_args: &[String],
+) -> Stage {
+    let mut args = vec!["compile".to_string(), ".".to_string()];
+    args.extend(compile_args.iter().cloned());
+    let (ok, _, stderr) = match run(exe, report_path, &args) {
+        Ok(result) => result,
+        Err(e) => (false, String::new(), e),
+    };
+    let mut fields = vec![("log", json!(stderr.trim()))];
+    if ok {
+        fields.push(("artifacts", json!(artifacts(report_path, formats))));
+    }
+    Stage {
+        status: if ok { "passed" } else { "failed" },
+        fields,
+    }
+}
+
+fn stages(
+    exe: &Path,
+    report_path: &Path,
+    formats: Option<&str>,
+    compile_args: &[String],
+) -> Vec<Stage> {
+    let lint = check_stage(exe, report_path, "lint");
+    let validate = check_stage(exe, report_path, "validate");
+    let compile = match validate.passed() {
+        true => compile_stage(exe, report_path, formats, compile_args),
+        false => Stage {
+            status: "skipped",
+            fields: Vec::new(),
+        },
+    };
+    vec![lint, validate, compile]
+}
+
+/// Runs the QA stages on every report in `dir`, compiling with `compile_args`
+pub fn ci(
+    dir: Option<PathBuf>,
+    formats: Option<String>,
+    compile_args: Vec<String>,
+) -> Result<(), Box<dyn Error>> {
+    let dir = dir.unwrap_or_else(|| ".".into());
+    let reports = workspace::find_reports(&dir);
+    if reports.is_empty() {
+        eprintln!("ERROR: No reports found in {}", dir.display());
+        exit(1);
+    }
+
+    let exe = env::current_exe()?;
+    let jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut results = Vec::new();
+    for batch in reports.chunks(jobs) {
+        let batch_results: Vec<Vec<Stage>> = thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|report_path| {
+                    let (exe, formats, compile_args) = (&exe, formats.as_deref(), &compile_args);
+                    scope.spawn(move || stages(exe, report_path, formats, compile_args))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Failed to join a CI stage"))
+                .collect()
+        });
+        results.extend(batch.iter().zip(batch_results));
+    }
+
+    let failed_stage =
+        (0..STAGES.len()).find(|&i| results.iter().any(|(_, stages)| !stages[i].passed()));
+    let exit_code = failed_stage.map_or(0, |i| EXIT_CODES[i]);
+    let reports: Vec<Value> = results
+        .iter()
+        .map(|(report_path, stages)| {
+            let passed = stages.iter().all(Stage::passed);
+            let stages: Value = STAGES
+                .iter()
+                .zip(stages)
+                .map(|(name, stage)| (*name, stage.to_json()))
+                .collect();
+            json!({
+                "report": report_path.display().to_string(),
+                "passed": passed,
+                "stages": stages,
+            })
+        })
+        .collect();
+    let output = json!({
+        "version": JSON_FORMAT_VERSION,
+        "passed": exit_code == 0,
+        "failed_stage": failed_stage.map(|i| STAGES[i]),
+        "exit_code": exit_code,
+        "reports": reports,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    if exit_code != 0 {
+        exit(exit_code);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::{fs::read_to_string, path::PathBuf};
+
+use crate::config;
+use crate::consts::CLIENTS_DIR;
+use crate::toml;
+use crate::toml::TableExt;
+use crate::utils::typst_escape;
+
+// Client profiles kept in `$XDG_CONFIG_HOME/reportgenerator/clients/<name>.toml`
+// and used by `new --client <name>` to prefill the metadata:
+//
+//  legal_name = "ACME Corporation Ltd."
+//  address = "1 Main Street, Springfield"
+//  logo = "acme.png"
+//  contacts = ["Jane Doe <jane@acme.example>"]
+//  scope_notes = "Production systems may only be tested after 6pm."
+
+pub struct Profile {
+    pub legal_name: String,
+    pub address: Option<String>,
+    // Relative paths are resolved against the profile directory
+    pub logo: Option<PathBuf>,
+    pub contacts: Vec<String>,
+    pub scope_notes: Option<String>,
+}
+
+pub fn load(name: &str) -> Result<Profile, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("Invalid client name \"{name}\""));
+    }
+    let dir = config::config_home()
+        .ok_or("Config directory not found, set XDG_CONFIG_HOME or HOME")?
+        .join(CLIENTS_DIR);
+    let path = dir.join(format!("{name}.toml"));
+    let content = read_to_string(&path)
+        .map_err(|e| format!("Client profile {} not readable: {e}", path.display()))?;
+    let table = toml::parse(&content).map_err(|e| format!("{}: {e}", path.display()))?;
+    let string = |key: &str| table.get_str(key).map(str::to_string);
+
+    Ok(Profile {
+        legal_name: string("legal_name")
+            .ok_or(format!("{}: legal_name is missing", path.display()))?,
+        address: string("address"),
+        logo: string("logo").map(|logo| dir.join(logo)),
+        contacts: table.get_str_list("contacts"),
+        scope_notes: string("scope_notes"),
+    })
+}
+
+impl Profile {
+    /// `metadata` with the client name filled in and the client keys appended,
+    /// `logo` is the file name of the copied logo inside the report
+    pub fn metadata(&self, metadata: &str, logo: Option<&str>) -> String {
+        // Metadata values are single lines of Typst markup
+        let value = |s: &str| typst_escape(&s.lines().collect::<Vec<_>>().join(", "));
+
+        let mut prefilled: String = metadata
+            .lines()
+            .map(|line| match line.split_once(':') {
+                Some(("prepared_for", _)) => format!("prepared_for:{}\n", value(&self.legal_name)),
+                _ => format!("{line}\n"),
+            })
+            .collect();
+        if let Some(ref address) = self.address {
+            prefilled.push_str(&format!("client_address:{}\n", value(address)));
+        }
+        if !self.contacts.is_empty() {
+            prefilled.push_str(&format!(
+                "client_contacts:{}\n",
+                value(&self.contacts.join(", "))
+            ));
+        }
+        if let Some(logo) = logo {
+            prefilled.push_str(&format!("client_logo:{logo}\n"));
+        }
+        prefilled
+    }
+}
@@ -1,120 +1,1152 @@
 use std::{
+    env,
     error::Error,
-    fs::{read_dir, read_to_string, remove_file, File, OpenOptions},
+    fs::{read_to_string, write},
     io::Write,
-    path::PathBuf,
-    process::{exit, Command},
+    path::{self, Path, PathBuf},
+    process::{exit, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
 };
 
+use crate::assets::{self, Asset};
+use crate::attack;
+use crate::blobs;
+use crate::brand::{self, Brand};
+use crate::build_cache;
+use crate::config::{Config, PdfMetadata};
 use crate::consts::*;
+use crate::cover;
+use crate::encrypt;
+use crate::environment;
+use crate::evidence;
+use crate::fonts;
+use crate::git;
+use crate::hosts;
+use crate::http_evidence;
+use crate::manifest;
+use crate::notify::Notification;
+use crate::nvd;
+use crate::page;
+use crate::partials;
+use crate::relations;
+use crate::report::{self, Entry, Report};
+use crate::review;
+use crate::risk;
+use crate::roadmap;
+use crate::scope;
+use crate::secrets;
+use crate::severity::Presentation;
+use crate::sign;
+use crate::signoff;
+use crate::snippets;
+use crate::source_map;
+use crate::summary;
+use crate::surface;
 use crate::template::Template;
-use crate::utils::get_current_date;
-
-fn compile_to_file(report: &str, output: &Option<String>) -> Result<(), Box<dyn Error>> {
-    // Write report to temporary file
-    let mut tmp_file = OpenOptions::new()
-        .write(true)
-        .create_new(true)
-        .open(TMP_FILE)
-        .expect("Failed to open temporary file");
-    tmp_file.write_all(report.as_bytes())?;
-
-    // Close file
-    drop(tmp_file);
-
-    // User provided output file or DEFAULT_REPORT_FILE as fallback
-    let output_file = output.as_deref().unwrap_or(DEFAULT_REPORT_FILE);
-
-    // Use typst to compile the file
-    Command::new("typst")
-        .args(["compile", TMP_FILE, output_file])
+use crate::toml::TableExt;
+use crate::trend;
+use crate::utils::{get_report_date, typst_escape, typst_str};
+use crate::versions;
+use crate::workspace;
+use crate::xref;
+
+// Output formats supported by typst
+const FORMATS: &[&str] = &["pdf", "png", "svg", "html"];
+
+/// Output file of `format`, image formats get one file per page
+pub fn format_output(output: &str, format: &str) -> String {
+    let path = Path::new(output);
+    let path = match format {
+        "png" | "svg" => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            path.with_file_name(format!("{stem}-{{p}}.{format}"))
+        }
+        _ => path.with_extension(format),
+    };
+    path.display().to_string()
+}
+
+/// Compiles `report` with typst running in `report_path`, so images and
+/// copied logos resolve against the report wherever compile is run from
+pub fn compile_to_file(
+    report_path: &Path,
+    report: &str,
+    output: &str,
+    format: &str,
+    typst: &str,
+    font_paths: &[PathBuf],
+    password: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    // Paths given relative to the working directory have to survive the change
+    let absolute = |path: &Path| path::absolute(path).unwrap_or(path.to_path_buf());
+    let typst = match typst.contains(path::MAIN_SEPARATOR) {
+        true => absolute(Path::new(typst)),
+        false => PathBuf::from(typst),
+    };
+    let font_paths: Vec<PathBuf> = font_paths.iter().map(|path| absolute(path)).collect();
+    let mut command = Command::new(typst);
+    command.current_dir(report_path).args(["compile", "-"]);
+    command.arg(absolute(Path::new(output)));
+    command.args(["--format", format]);
+    command.args(fonts::args(&font_paths));
+    // HTML export is still experimental in typst
+    if format == "html" {
+        command.args(["--features", "html"]);
+    }
+
+    // Pass the report on stdin, relative paths resolve against the working directory
+    let mut child = command
+        .args(["--diagnostic-format", "short"])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()
-        .expect("Failed to execute typst\nEnsure you have 'typst' installed on your system")
-        .wait()
-        .expect("Failed to wait for typst");
+        .expect("Failed to execute typst\nEnsure you have 'typst' installed on your system");
+
+    // Close stdin once written so typst starts compiling
+    let mut stdin = child.stdin.take().expect("Failed to open typst stdin");
+    let written = stdin.write_all(report.as_bytes());
+    drop(stdin);
+
+    let result = child.wait_with_output().expect("Failed to wait for typst");
+    written?;
+
+    // Errors and warnings point at the section/finding files instead of stdin
+    let diagnostics = String::from_utf8_lossy(&result.stderr);
+    eprint!("{}", source_map::translate(&diagnostics, report));
+
+    if !result.status.success() {
+        return Err(format!("typst failed to compile the report to {format}").into());
+    }
 
-    // Remove the temporary file
-    remove_file(TMP_FILE).expect("Failed to remove temporary file");
+    if let (Some(password), "pdf") = (password, format) {
+        encrypt::encrypt_pdf(output, password)?;
+    }
 
     Ok(())
 }
 
+/// Compiles `report` unless the last build of the same inputs is still
+/// there, `Ok(false)` if it was skipped. Encrypted builds are never skipped.
+fn build(
+    report_path: &Path,
+    report: &str,
+    output: &str,
+    format: &str,
+    config: &Config,
+    password: Option<&str>,
+    force: bool,
+) -> Result<bool, Box<dyn Error>> {
+    let typst = config.typst();
+    let font_paths = config.font_paths.clone().unwrap_or_default();
+    if password.is_some() {
+        build_cache::clear(report_path, format);
+        compile_to_file(
+            report_path,
+            report,
+            output,
+            format,
+            typst,
+            &font_paths,
+            password,
+        )?;
+        return Ok(true);
+    }
+    let fingerprint =
+        build_cache::fingerprint(report_path, report, output, format, typst, &font_paths);
+    if !force && build_cache::is_fresh(report_path, format, &fingerprint, output) {
+        return Ok(false);
+    }
+    compile_to_file(
+        report_path,
+        report,
+        output,
+        format,
+        typst,
+        &font_paths,
+        None,
+    )?;
+    if let Err(e) = build_cache::store(report_path, format, &fingerprint) {
+        eprintln!("WARNING: {e}");
+    }
+    Ok(true)
+}
+
+/// Signs the compiled PDF, the next build without `--sign` replaces it
+fn sign_output(report_path: &Path, output: &str, certificate: &Path) {
+    build_cache::clear(report_path, "pdf");
+    if let Err(e) = sign::sign_pdf(output, certificate) {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+    println!("Signed {output}");
+}
+
+/// Writes MANIFEST.txt, last as any later change would break it
+fn write_manifest(report_path: &Path, outputs: &[String]) {
+    if let Err(e) = manifest::write_manifest(report_path, outputs) {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+    println!(
+        "Hashes written to {MANIFEST_FILE}",
+        MANIFEST_FILE = manifest::MANIFEST_FILE
+    );
+}
+
+/// Parts of `line` outside of raw text, `raw` tracks ``` blocks spanning lines
+fn prose<'a>(line: &'a str, raw: &mut bool) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut inline = false;
+    let mut start = 0;
+    let mut index = 0;
+    while index < line.len() {
+        // Bytes, backticks never occur within multi-byte characters
+        let fence = line.as_bytes()[index..].starts_with(b"```");
+        if fence || (!*raw && line.as_bytes()[index] == b'`') {
+            if !*raw && !inline {
+                parts.push(&line[start..index]);
+            }
+            match fence {
+                true => *raw = !*raw,
+                false => inline = !inline,
+            }
+            index += if fence { 3 } else { 1 };
+            start = index;
+        } else {
+            index += 1;
+        }
+    }
+    if !*raw && !inline {
+        parts.push(&line[start..]);
+    }
+    parts
+}
+
+/// Whether the inside of `{{ }}` names a placeholder like `client` or
+/// `include:disclaimer`, unlike code such as `{{7*7}}`
+fn is_placeholder_name(name: &str) -> bool {
+    let name = name.trim();
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_:-".contains(c))
+}
+
+/// `{{ name }}` tokens left after substitution and boilerplate text like
+/// CHANGE ME in `text`, with their 1-based line. Raw text is evidence and
+/// code, left alone.
+fn unreplaced_placeholders(document: &str) -> Vec<(usize, &str)> {
+    let mut placeholders = Vec::new();
+    let mut raw = false;
+    for (index, line) in document.lines().enumerate() {
+        for part in prose(line, &mut raw) {
+            for text in PLACEHOLDER_TEXT {
+                if part.contains(text) {
+                    placeholders.push((index + 1, *text));
+                }
+            }
+            let mut rest = part;
+            while let Some(start) = rest.find("{{") {
+                let Some(end) = rest[start..].find("}}") else {
+                    break;
+                };
+                let token = &rest[start..start + end + 2];
+                if is_placeholder_name(&token[2..token.len() - 2]) {
+                    placeholders.push((index + 1, token));
+                }
+                rest = &rest[start + end + 2..];
+            }
+        }
+    }
+    placeholders
+}
+
+/// `#set document(..)` rule with the configured PDF metadata, empty if nothing is set
+fn document_metadata(pdf: &PdfMetadata) -> String {
+    let mut fields = Vec::new();
+    if let Some(ref title) = pdf.title {
+        fields.push(format!("title: {}", typst_str(title)));
+    }
+    if let Some(ref author) = pdf.author {
+        fields.push(format!("author: {}", typst_str(author)));
+    }
+    if let Some(ref keywords) = pdf.keywords {
+        let keywords: Vec<String> = keywords.iter().map(|k| typst_str(k)).collect();
+        // Trailing comma keeps a single keyword an array
+        fields.push(format!("keywords: ({},)", keywords.join(", ")));
+    }
+
+    if fields.is_empty() {
+        String::new()
+    } else {
+        format!("#set document({})\n", fields.join(", "))
+    }
+}
+
+/// Page rules stamping a diagonal DRAFT watermark and classification banners at the
+/// top and bottom of every page. Background and foreground are left alone by templates.
+fn page_markings(draft: bool, classification: Option<&str>) -> String {
+    let mut markings = String::new();
+    if draft {
+        markings.push_str(
+            "#set page(background: rotate(-45deg, text(96pt, weight: \"bold\", fill: luma(225))[DRAFT]))\n",
+        );
+    }
+    if let Some(classification) = classification.filter(|c| !c.is_empty()) {
+        let banner = format!(
+            "text(8pt, weight: \"bold\", fill: red, {})",
+            typst_str(classification)
+        );
+        markings.push_str(&format!(
+            "#set page(foreground: [#place(top + center, dy: 8pt, {banner}) #place(bottom + center, dy: -8pt, {banner})])\n"
+        ));
+    }
+    markings
+}
+
+/// Moves every heading `depth` levels down so nested sections fit under their chapter
+fn shift_headings(body: &str, depth: usize) -> String {
+    if depth == 0 {
+        return body.to_string();
+    }
+    let prefix = "=".repeat(depth);
+    body.lines()
+        .map(|line| {
+            if line.starts_with('=') {
+                format!("{prefix}{line}\n")
+            } else {
+                format!("{line}\n")
+            }
+        })
+        .collect()
+}
+
+/// Renders a section followed by its children when it is a chapter
+pub fn render_section(section: &Entry, depth: usize) -> String {
+    let mut content = if section.is_chapter() {
+        shift_headings(&section.body, depth)
+    } else {
+        let body = source_map::mark_placeholders(section, &section.body);
+        source_map::wrap(section, &shift_headings(&body, depth))
+    };
+    for child in &section.children {
+        content.push('\n');
+        content.push_str(&render_section(child, depth + 1));
+    }
+    content
+}
+
+/// Renders findings with their placeholders, e.g. `{{ affected_hosts }}`, filled in
+pub struct FindingRenderer<'a> {
+    presentation: Presentation,
+    ids: xref::IdScheme,
+    host_layout: hosts::Layout,
+    assets: &'a [Asset],
+    redacted_headers: Vec<String>,
+    // Finding partial, `{{ finding }}` standing for the finding
+    layout: String,
+}
+
+impl<'a> FindingRenderer<'a> {
+    pub fn new(report: &Report, config: &Config, assets: &'a [Asset]) -> Result<Self, String> {
+        Ok(FindingRenderer {
+            presentation: config.severity_presentation(),
+            ids: xref::IdScheme::of(report),
+            host_layout: config.host_layout(),
+            assets,
+            redacted_headers: config.redact_headers(),
+            layout: partials::partial("finding", config)?,
+        })
+    }
+
+    pub fn render(&self, report: &Report, finding: &Entry) -> String {
+        let mut finding_context = nvd::finding_context(&report.path, finding);
+        let severity = finding.meta.get_str("severity");
+        finding_context.push((
+            "finding_id".to_string(),
+            typst_escape(&self.ids.id(finding)),
+        ));
+        finding_context.push((
+            "affected_hosts".to_string(),
+            hosts::render(finding, &self.host_layout),
+        ));
+        finding_context.push((
+            "affected_assets".to_string(),
+            assets::render(finding, self.assets),
+        ));
+        finding_context.push((
+            "http_evidence".to_string(),
+            http_evidence::render(finding, &self.redacted_headers),
+        ));
+        finding_context.push((
+            "severity_label".to_string(),
+            severity.map_or(String::new(), |s| typst_escape(&self.presentation.label(s))),
+        ));
+        finding_context.push((
+            "severity_badge".to_string(),
+            severity.map_or(String::new(), |s| self.presentation.badge(s)),
+        ));
+        let context: Vec<(&str, &str)> = finding_context
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        let mut content = xref::anchor(
+            finding,
+            &Template::from_str(&source_map::mark_placeholders(finding, &finding.body))
+                .render(&context),
+            &self.ids,
+        );
+        content.push_str(&relations::render(report, finding));
+        // Outside the source markers, the layout's lines aren't the finding's
+        Template::from_str(&self.layout).render(&vec![(
+            "finding",
+            source_map::wrap(finding, &content).trim_end(),
+        )])
+    }
+}
+
+/// Flags of compile besides the report, output and formats
+#[derive(Default)]
+pub struct Options {
+    // Require every section and finding to be approved
+    pub final_report: bool,
+    // Encrypt PDFs with this password
+    pub password: Option<String>,
+    pub draft: bool,
+    // Show the current git commit in the footer
+    pub tag: bool,
+    // Use the clean-result variant even with findings above the threshold
+    pub clean_result: bool,
+    // Only warn about leftover placeholders
+    pub allow_placeholders: bool,
+    // Branding profile replacing the configured one
+    pub brand: Option<String>,
+    // Theme replacing the configured one
+    pub theme: Option<String>,
+    // PKCS#12 certificate signing the PDF
+    pub sign: Option<PathBuf>,
+    // Write MANIFEST.txt after compiling
+    pub manifest: bool,
+    // Compile even if nothing changed since the last build
+    pub force: bool,
+    // Post builds to compile_webhook of the config
+    pub notify: bool,
+    // Send no network requests, the compile webhook included
+    pub offline: bool,
+    // Write the assembled typst source here instead of compiling, `-` for stdout
+    pub emit_typ: Option<String>,
+}
+
 pub fn compile_report(
     report_dir: Option<PathBuf>,
     output: Option<String>,
+    formats: Option<String>,
+    options: Options,
 ) -> Result<(), Box<dyn Error>> {
+    let Options {
+        final_report,
+        password,
+        draft,
+        tag,
+        clean_result,
+        allow_placeholders,
+        brand,
+        theme,
+        sign,
+        manifest,
+        force,
+        notify,
+        offline,
+        emit_typ,
+    } = options;
+
+    // Comma-separated list of output formats, a PDF by default
+    let formats: Vec<String> = formats
+        .as_deref()
+        .unwrap_or("pdf")
+        .split(',')
+        .map(|f| f.trim().to_lowercase())
+        .collect();
+    for format in &formats {
+        if !FORMATS.contains(&format.as_str()) {
+            eprintln!(
+                "ERROR: Unknown output format \"{format}\", expected {}",
+                FORMATS.join(", ")
+            );
+            exit(1);
+        }
+    }
+
+    if let Some(ref certificate) = sign {
+        if !formats.iter().any(|f| f == "pdf") {
+            eprintln!("ERROR: --sign requires the pdf format");
+            exit(1);
+        }
+        if !certificate.is_file() {
+            eprintln!("ERROR: Certificate {} not found", certificate.display());
+            exit(1);
+        }
+    }
+
     // Ensure user provided the report path or use current directory as default
-    let report_path = report_dir.unwrap_or_else(|| {
-        if File::open("metadata.typ").is_err() {
-            eprintln!("ERROR: current directory is not a valid report");
+    let report_path = report::resolve_path(report_dir);
+
+    let mut report = Report::load_or_exit(&report_path);
+    let mut config = Config::load_or_exit(&report_path);
+    if theme.is_some() {
+        config.theme = theme;
+    }
+
+    // Ensure all ATT&CK technique tags are known
+    let unknown = attack::unknown_techniques(&report);
+    if !unknown.is_empty() {
+        for (path, id) in unknown {
+            eprintln!("ERROR: Unknown ATT&CK technique {id} in {}", path.display());
+        }
+        exit(1);
+    }
+
+    // Real credentials of the client mustn't ship in the PDF
+    let secrets_mode = config.secrets.as_deref().unwrap_or("error");
+    if secrets_mode != "off" {
+        let found = secrets::scan(&report_path, &config.redact_headers()).unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        });
+        let level = if secrets_mode == "warn" {
+            "WARNING"
+        } else {
+            "ERROR"
+        };
+        for secret in &found {
+            eprintln!(
+                "{level}: Possible secret ({}) {} in {}:{}",
+                secret.rule,
+                secret.masked(),
+                secret.path.display(),
+                secret.line
+            );
+        }
+        if !found.is_empty() && secrets_mode == "error" {
+            eprintln!("Remove them or add known values to {SECRETS_ALLOWLIST_FILE} of the report");
             exit(1);
         }
-        ".".into()
+    }
+
+    let brand = match brand.or(config.brand.clone()) {
+        Some(name) => brand::load(&name),
+        None => Ok(Brand::default()),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
     });
 
-    // If directory doesn't exist, error out
-    if !report_path.exists() {
-        eprintln!("ERROR: Directory doesn't exist");
+    // Typst would silently fall back to a default font
+    let font_paths = config.font_paths.clone().unwrap_or_default();
+    let mut required_fonts = config.fonts.clone().unwrap_or_default();
+    required_fonts.extend(brand.font.iter().chain(&brand.heading_font).cloned());
+    match fonts::missing(config.typst(), &font_paths, &required_fonts) {
+        Ok(missing) if missing.is_empty() => {}
+        Ok(missing) => {
+            for font in missing {
+                eprintln!("ERROR: Font \"{font}\" not found, install it or add its directory to font_paths in the config");
+            }
+            exit(1);
+        }
+        Err(e) => {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        }
+    }
+
+    // Shared snippets go in before anything looks at the content
+    if let Err(e) = snippets::resolve(&mut report, &config) {
+        eprintln!("ERROR: {e}");
         exit(1);
     }
 
-    // Handle sections
-    let mut sections = vec![String::new(); read_dir(report_path.join("sections"))?.count()];
-    for section in read_dir(report_path.join("sections"))? {
-        let section = section?;
-        let content = read_to_string(section.path())?;
-        let id = section
-            .file_name()
-            .to_str()
-            .unwrap()
-            .split('.')
-            .next()
-            .unwrap()
-            .parse::<usize>()?;
-        sections[id - 1] = format!("\n#pagebreak()\n{content}");
-    }
-
-    // Handle findings
-    let mut findings = vec![String::new(); read_dir(report_path.join("findings"))?.count()];
-    for finding in read_dir(report_path.join("findings"))? {
-        let finding = finding?;
-        let content = read_to_string(finding.path())?;
-        let id = finding
-            .file_name()
-            .to_str()
-            .unwrap()
-            .split('.')
-            .next()
-            .unwrap()
-            .parse::<usize>()?;
-        findings[id - 1] = format!("\n#pagebreak()\n{content}");
+    // Blobs are cut short before rendering, their full values are kept as attachments
+    // The emitted source is a dump, the report isn't written to for it
+    let attachments = blobs::truncate(&mut report, config.blob_max_length());
+    if emit_typ.is_none() {
+        if let Err(e) = blobs::write_attachments(&report.path, &attachments) {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        }
+    }
+
+    // Assets referenced by findings have to be described in assets.toml
+    let assets = assets::load(&report_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let unknown = assets::unknown_assets(&report, &assets);
+    if !unknown.is_empty() {
+        for (path, id) in unknown {
+            eprintln!(
+                "ERROR: Unknown asset {id} in {}, describe it in {ASSETS_FILE}",
+                path.display()
+            );
+        }
+        exit(1);
+    }
+
+    // Hosts outside of scope.toml are likely typos or a test gone too far
+    let scope = scope::load(&report_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    if let Some(ref scope) = scope {
+        for (path, host, reason) in scope.violations(&report, &assets) {
+            eprintln!(
+                "WARNING: {host} in {} {reason} {SCOPE_FILE}",
+                path.display()
+            );
+        }
     }
 
+    // Relationships to findings that don't exist are left out
+    for finding in &report.findings {
+        for (key, _) in relations::KEYS {
+            for id in relations::targets(&report, finding, key).1 {
+                eprintln!(
+                    "WARNING: {} refers to finding {id} in {key}, which doesn't exist",
+                    finding.path.display()
+                );
+            }
+        }
+    }
+
+    // Final reports may only contain approved sections and findings
+    if final_report {
+        let unapproved = review::unapproved(&report);
+        if !unapproved.is_empty() {
+            for entry in unapproved {
+                eprintln!(
+                    "ERROR: {} is {}, final reports require approval",
+                    entry.path.display(),
+                    review::state(entry)
+                );
+            }
+            exit(1);
+        }
+    }
+
+    // Handle sections
+    let sections: Vec<String> = report
+        .sections
+        .iter()
+        .map(|section| format!("\n#pagebreak()\n{}", render_section(section, 0)))
+        .collect();
+
+    // Handle findings, each rendered with its enriched CVE details and severity
+    let presentation = config.severity_presentation();
+    let ids = xref::IdScheme::of(&report);
+    let host_layout = config.host_layout();
+    let renderer = FindingRenderer::new(&report, &config, &assets).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let findings: Vec<String> = report
+        .findings
+        .iter()
+        .map(|finding| format!("\n#pagebreak()\n{}", renderer.render(&report, finding)))
+        .collect();
+
+    // Appendices are lettered, their subheadings numbered like A.1
+    let appendices = match report.appendices.is_empty() {
+        true => String::new(),
+        false => format!(
+            "#[\n#counter(heading).update(0)\n#set heading(numbering: (..n) => if n.pos().len() == 1 {{ \"Appendix \" + numbering(\"A:\", ..n) }} else {{ numbering(\"A.1\", ..n) }})\n{}\n]\n",
+            report
+                .appendices
+                .iter()
+                .map(|appendix| format!("\n#pagebreak()\n{}", render_section(appendix, 0)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    };
     let sections = sections.join("\n");
     let findings = findings.join("\n");
-    let current_date = get_current_date();
+    // Metadata of the report takes precedence over the config
+    let current_date = get_report_date(
+        report.metadata_value("report_date"),
+        report
+            .metadata_value("date_format")
+            .or(config.date_format.as_deref()),
+        report.metadata_value("locale").or(config.locale.as_deref()),
+    );
+    let attack_appendix = attack::render_appendix(&report);
+    let environment_matrix = environment::render_matrix(&report);
+    let host_appendix = hosts::render_appendix(&report, &ids, &host_layout);
+    let asset_matrix = assets::render_matrix(&report, &assets, &ids);
+    let screenshot_gallery = match config.screenshot_gallery {
+        Some(true) => evidence::render_gallery(&report, &ids),
+        _ => String::new(),
+    };
+    let attack_surface = match surface::load(&report.path) {
+        Ok(subdomains) => surface::render_appendix(&subdomains),
+        Err(e) => {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        }
+    };
+    let scope_definition = scope.as_ref().map(scope::Scope::render).unwrap_or_default();
+    let document_control = versions::render_document_control(&report);
+    let sign_off = signoff::render(&report);
+    let cover_logo = cover::logo(&report, emit_typ.is_none()).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    // Commit the PDF was compiled from, shown in the footer
+    let git_commit = match tag {
+        true => match git::commit_label(&report.path) {
+            Ok(label) => format!("Commit {label}"),
+            Err(e) => {
+                eprintln!("ERROR: {e}");
+                exit(1);
+            }
+        },
+        false => String::new(),
+    };
+    let threshold = config.clean_result_threshold().unwrap_or_else(|e| {
+        eprintln!("ERROR: Invalid config {e}");
+        exit(1);
+    });
+    let clean = summary::is_clean(&report.findings, &config.severities(), &threshold);
+    if clean && !clean_result {
+        let message =
+            format!("No findings rated {threshold} or higher, using the clean-result variant");
+        // Stdout may carry the emitted source
+        match emit_typ.as_deref() {
+            Some("-") => eprintln!("{message}"),
+            _ => println!("{message}"),
+        }
+    }
+    let clean_result = clean_result || clean;
+    let assurance_statement = summary::assurance_statement(
+        report.metadata_value("prepared_for"),
+        &presentation.label(&threshold),
+    );
+    let observations = match report.findings.is_empty() {
+        true => String::new(),
+        false => format!("#pagebreak()\n= Observations\n{findings}"),
+    };
+    let executive_summary =
+        summary::executive_summary(&report, &config.severities(), &presentation);
+    let statistics = summary::statistics(&report, &config.severities(), &presentation);
+    let findings_overview = summary::findings_overview(&report, &ids, &presentation);
+    let risk_matrix = risk::render_matrix(&report, &ids);
+    let severity_legend = presentation.legend(&config.severities());
+    let severity_trend = trend::render_chart(&report, &config.severities(), &presentation)
+        .unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        });
+    let remediation_roadmap = roadmap::render_appendix(
+        &report,
+        &config.severities(),
+        &config.remediation_timelines(),
+        &ids,
+        &presentation,
+    );
+
+    let brand = brand
+        .context(&report.path, emit_typ.is_none())
+        .unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        });
+    let page_layout = page::context(Some(&report), &config).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
 
     let mut context: Vec<(&str, &str)> = vec![
         ("sections", &sections),
         ("findings", &findings),
+        ("appendices", &appendices),
         ("current_date", &current_date),
+        ("attack_appendix", &attack_appendix),
+        ("environment_matrix", &environment_matrix),
+        ("host_appendix", &host_appendix),
+        ("asset_matrix", &asset_matrix),
+        ("attack_surface", &attack_surface),
+        ("scope_definition", &scope_definition),
+        ("screenshot_gallery", &screenshot_gallery),
+        ("document_control", &document_control),
+        ("cover_logo", &cover_logo),
+        ("sign_off", &sign_off),
+        ("git_commit", &git_commit),
+        ("executive_summary", &executive_summary),
+        ("findings_overview", &findings_overview),
+        ("risk_matrix", &risk_matrix),
+        ("severity_trend", &severity_trend),
+        ("severity_legend", &severity_legend),
+        ("remediation_roadmap", &remediation_roadmap),
+        ("assurance_statement", &assurance_statement),
+        ("observations", &observations),
     ];
 
+    // Finding statistics, usable in sections as well
+    for (key, value) in &statistics {
+        context.push((key, value));
+    }
+    for (key, value) in brand.iter().chain(&page_layout) {
+        context.push((key, value));
+    }
+
     // Handle metadata file
-    let metadata_file = read_to_string(report_path.join("metadata.typ"))?;
-    for line in metadata_file.lines() {
-        let split: Vec<&str> = line.split(':').collect();
-        if split.len() < 2 {
-            continue;
+    for (key, value) in &report.metadata {
+        context.push((key, value));
+    }
+
+    // Main template from the config or the built-in one, reports without
+    // relevant findings get the clean-result variant
+    let main_template = match (clean_result, &config.clean_template, &config.template) {
+        (true, Some(path), _) | (false, _, Some(path)) => read_to_string(path)
+            .map_err(|e| format!("Failed to read template {}: {e}", path.display())),
+        (true, None, _) => Ok(CLEAN_RESULT_TEMPLATE.to_string()),
+        (false, _, None) => Ok(MAIN_TEMPLATE.to_string()),
+    }
+    .and_then(|template| partials::resolve(&template, &config))
+    .map(|template| Template::from_str(&template))
+    .unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let markings = page_markings(draft, report.metadata_value("classification"));
+    let document = document_metadata(&config.pdf) + &markings + &main_template.render(&context);
+
+    let notification = notify
+        .then(|| Notification::of(&report_path, &report, &config))
+        .flatten();
+
+    // Boilerplate left in the metadata, located by key as values span no lines of their own
+    let metadata_placeholders: Vec<(String, String)> = report
+        .metadata
+        .iter()
+        .flat_map(|(key, value)| {
+            unreplaced_placeholders(value)
+                .into_iter()
+                .map(move |(_, placeholder)| {
+                    (format!("metadata.typ ({key})"), placeholder.to_string())
+                })
+        })
+        .collect();
+
+    // References to findings that don't exist are errors regardless of the config
+    let report = xref::resolve(&document, &report).unwrap_or_else(|dangling| {
+        for (line, label) in dangling {
+            let location = match source_map::locate(&document, line) {
+                Some((path, line)) => format!("{path}:{line}"),
+                None => "main template".to_string(),
+            };
+            eprintln!("ERROR: Reference to unknown finding {label} in {location}");
         }
-        context.push((split[0], split[1]));
+        exit(1);
+    });
+
+    // Typos in placeholders and unfinished boilerplate would otherwise reach the
+    // client. Only what was written for the report counts, not the templates.
+    let unreplaced: Vec<(String, String)> = unreplaced_placeholders(&report)
+        .into_iter()
+        .filter_map(|(line, placeholder)| {
+            let (path, line) = source_map::locate(&report, line)?;
+            Some((format!("{path}:{line}"), placeholder.to_string()))
+        })
+        .chain(metadata_placeholders)
+        .collect();
+    // The emitted source is for inspection, placeholders don't stop it
+    let warn_only =
+        allow_placeholders || emit_typ.is_some() || config.warn_unreplaced.unwrap_or(false);
+    for (location, placeholder) in &unreplaced {
+        let level = if warn_only { "WARNING" } else { "ERROR" };
+        eprintln!("{level}: Leftover placeholder {placeholder} in {location}");
+    }
+    if !unreplaced.is_empty() && !warn_only {
+        exit(1);
+    }
+
+    match emit_typ.as_deref() {
+        Some("-") => {
+            print!("{report}");
+            return Ok(());
+        }
+        Some(path) => {
+            if let Err(e) = write(path, &report) {
+                eprintln!("ERROR: Failed to write {path}: {e}");
+                exit(1);
+            }
+            println!("Typst source written to {path}");
+            return Ok(());
+        }
+        None => {}
     }
 
-    let report = Template::from_str(MAIN_TEMPLATE).render(&context);
+    // User provided output file, the configured one or DEFAULT_REPORT_FILE as fallback
+    let output = output
+        .or(config.output.clone())
+        .unwrap_or(DEFAULT_REPORT_FILE.to_string());
+
+    let manifest = manifest || config.manifest.unwrap_or(false);
+
+    // Signed PDFs are always rebuilt, signing them again would fail
+    let force = force || sign.is_some();
+
+    // A single PDF keeps the output name as is
+    if formats == ["pdf"] {
+        let built = match build(
+            &report_path,
+            &report,
+            &output,
+            "pdf",
+            &config,
+            password.as_deref(),
+            force,
+        ) {
+            Ok(true) => {
+                println!("Report compiled successfully");
+                true
+            }
+            Ok(false) => {
+                println!("Report is up to date");
+                false
+            }
+            Err(e) => {
+                eprintln!("ERROR: {e}");
+                exit(1);
+            }
+        };
+        if let Some(certificate) = sign {
+            sign_output(&report_path, &output, &certificate);
+        }
+        let outputs = [output];
+        if manifest {
+            write_manifest(&report_path, &outputs);
+        }
+        if let (Some(notification), true) = (notification, built) {
+            notification.send(&outputs, offline);
+        }
+        return Ok(());
+    }
 
-    compile_to_file(&report, &output)?;
+    // Every format is compiled by its own typst process at the same time
+    let results: Vec<(String, String, Duration, Result<bool, String>)> = thread::scope(|scope| {
+        let handles: Vec<_> = formats
+            .iter()
+            .map(|format| {
+                let output = format_output(&output, format);
+                let report = &report;
+                let config = &config;
+                let password = password.as_deref();
+                let report_path = &report_path;
+                scope.spawn(move || {
+                    let start = Instant::now();
+                    let result = build(
+                        report_path,
+                        report,
+                        &output,
+                        format,
+                        config,
+                        password,
+                        force,
+                    )
+                    .map_err(|e| e.to_string());
+                    (format.clone(), output, start.elapsed(), result)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Compile thread panicked"))
+            .collect()
+    });
+
+    let mut failed = false;
+    let mut built = false;
+    for (format, output, elapsed, result) in results {
+        match result {
+            Ok(true) => {
+                println!("{format}: {output} ({:.2}s)", elapsed.as_secs_f64());
+                built = true;
+            }
+            Ok(false) => println!("{format}: {output} is up to date"),
+            Err(e) => {
+                eprintln!("ERROR: {e}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    // After the other formats, pyHanko asks for the password on the terminal
+    if let (Some(certificate), true) = (sign, formats.iter().any(|f| f == "pdf")) {
+        sign_output(&report_path, &format_output(&output, "pdf"), &certificate);
+    }
+    let outputs: Vec<String> = formats
+        .iter()
+        .map(|format| format_output(&output, format))
+        .collect();
+    if manifest {
+        write_manifest(&report_path, &outputs);
+    }
+    if let (Some(notification), true) = (notification, built) {
+        notification.send(&outputs, offline);
+    }
 
     println!("Report compiled successfully");
 
     Ok(())
 }
+
+/// Compiles every report of a workspace, each in its own directory, in parallel
+pub fn compile_all(
+    workspace_dir: Option<PathBuf>,
+    formats: Option<String>,
+    options: Options,
+) -> Result<(), Box<dyn Error>> {
+    let Options {
+        final_report,
+        password,
+        draft,
+        tag,
+        clean_result,
+        allow_placeholders,
+        brand,
+        theme,
+        sign: _,
+        manifest,
+        force,
+        notify,
+        offline,
+        emit_typ: _,
+    } = options;
+    let workspace_path = workspace_dir.unwrap_or_else(|| ".".into());
+    let reports = workspace::find_reports(&workspace_path);
+    if reports.is_empty() {
+        eprintln!("ERROR: No reports found in {}", workspace_path.display());
+        exit(1);
+    }
+
+    // Reports are compiled by child processes, as many at a time as there are cores
+    let exe = env::current_exe()?;
+    let jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut failed = 0;
+    for batch in reports.chunks(jobs) {
+        let children = batch
+            .iter()
+            .map(|report_path| {
+                let mut command = Command::new(&exe);
+                command.args(["compile", "."]).current_dir(report_path);
+                if final_report {
+                    command.arg("--final");
+                }
+                if draft {
+                    command.arg("--draft");
+                }
+                if tag {
+                    command.arg("--tag");
+                }
+                if clean_result {
+                    command.arg("--clean-result");
+                }
+                if allow_placeholders {
+                    command.arg("--allow-placeholders");
+                }
+                if force {
+                    command.arg("--force");
+                }
+                if manifest {
+                    command.arg("--manifest");
+                }
+                if !notify {
+                    command.arg("--no-notify");
+                }
+                if offline {
+                    command.arg("--offline");
+                }
+                if let Some(ref brand) = brand {
+                    command.args(["--brand", brand]);
+                }
+                if let Some(ref theme) = theme {
+                    command.args(["--theme", theme]);
+                }
+                if let Some(ref formats) = formats {
+                    command.args(["--format", formats]);
+                }
+                if let Some(ref password) = password {
+                    command
+                        .arg("--encrypt")
+                        .env(encrypt::PASSWORD_VAR, password);
+                }
+                command
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (report_path, child) in batch.iter().zip(children) {
+            let output = child.wait_with_output()?;
+            if output.status.success() {
+                println!("{}: compiled", report_path.display());
+            } else {
+                failed += 1;
+                println!("{}: failed", report_path.display());
+                for line in String::from_utf8_lossy(&output.stderr).lines() {
+                    println!("  {line}");
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} of {} reports compiled",
+        reports.len() - failed,
+        reports.len()
+    );
+    if failed > 0 {
+        exit(1);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leftover_placeholders_are_found() {
+        let document = "Prepared for {{ client }}\nTODO: scope\n{{include:disclaimer}}";
+        assert_eq!(
+            unreplaced_placeholders(document),
+            vec![
+                (1, "{{ client }}"),
+                (2, "TODO"),
+                (3, "{{include:disclaimer}}")
+            ]
+        );
+    }
+
+    #[test]
+    fn template_injection_payloads_are_not_placeholders() {
+        // SSTI payloads are typical evidence of findings
+        let document = "The name field rendered {{7*7}} as 49\n{{ config.__class__ }}{{'a'*3}}";
+        assert!(unreplaced_placeholders(document).is_empty());
+    }
+
+    #[test]
+    fn raw_text_is_skipped() {
+        let document =
+            "Sent `{{ user }}` TODO\n```\nGET /?q={{ name }} # TODO\n```\nLeft {{ name }}";
+        assert_eq!(
+            unreplaced_placeholders(document),
+            vec![(1, "TODO"), (5, "{{ name }}")]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    error::Error,
+    fs::write,
+    io::{stdout, Write},
+};
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+use crate::args::Cli;
+
+// Shell completions and the man page, generated from the command line
+// definitions of args.rs so they never drift from `--help`:
+//
+//  report-generator completions zsh > ~/.zfunc/_report-generator
+//  report-generator manpage -o report-generator.1
+
+const BIN: &str = env!("CARGO_PKG_NAME");
+
+/// Prints the completion script of `shell`
+pub fn completions(shell: Shell) -> Result<(), Box<dyn Error>> {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), BIN, &mut script);
+    stdout().write_all(&script)?;
+    Ok(())
+}
+
+/// The man page of the binary with a section per subcommand, in roff
+fn manpage() -> Result<Vec<u8>, Box<dyn Error>> {
+    let command = Cli::command();
+    let mut page = Vec::new();
+    let man = clap_mangen::Man::new(command.clone());
+    man.render_title(&mut page)?;
+    man.render_name_section(&mut page)?;
+    man.render_synopsis_section(&mut page)?;
+    man.render_description_section(&mut page)?;
+    man.render_options_section(&mut page)?;
+    writeln!(page, ".SH COMMANDS")?;
+    for subcommand in command.get_subcommands() {
+        let mut synopsis = Vec::new();
+        clap_mangen::Man::new(
+            subcommand
+                .clone()
+                .bin_name(format!("{BIN} {}", subcommand.get_name())),
+        )
+        .render_synopsis_section(&mut synopsis)?;
+        let synopsis = String::from_utf8_lossy(&synopsis);
+        // The section heading of the synopsis becomes a sub-heading
+        let synopsis = synopsis.replacen(".SH SYNOPSIS", ".SS", 1);
+        page.extend_from_slice(synopsis.as_bytes());
+        if let Some(about) = subcommand.get_about() {
+            writeln!(page, "{}", roff(&about.to_string()))?;
+        }
+        for arg in subcommand
+            .get_arguments()
+            .filter(|arg| !arg.is_positional())
+        {
+            let name = match (arg.get_long(), arg.get_short()) {
+                (Some(long), _) => format!("\\-\\-{}", roff(long)),
+                (None, Some(short)) => format!("\\-{short}"),
+                (None, None) => continue,
+            };
+            writeln!(page, ".TP\n.B {name}")?;
+            if let Some(help) = arg.get_help() {
+                writeln!(page, "{}", roff(&help.to_string()))?;
+            }
+        }
+    }
+    man.render_version_section(&mut page)?;
+    Ok(page)
+}
+
+/// Text escaped for roff, lines starting with a dot would be requests
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    match escaped.starts_with(['.', '\'']) {
+        true => format!("\\&{escaped}"),
+        false => escaped,
+    }
+}
+
+/// Prints the man page or writes it to `output`
+pub fn man(output: Option<String>) -> Result<(), Box<dyn Error>> {
+    let page = manpage()?;
+    match output {
+        Some(path) => {
+            write(&path, page).map_err(|e| format!("Failed to write {path}: {e}"))?;
+            println!("Man page written to {path}");
+        }
+        None => stdout().write_all(&page)?,
+    }
+    Ok(())
+}
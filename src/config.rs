@@ -0,0 +1,785 @@
+use std::{
+    env,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use ::toml::{Table, Value};
+
+use crate::blobs;
+use crate::consts::*;
+use crate::hosts;
+use crate::http_evidence;
+use crate::qa;
+use crate::roadmap;
+use crate::schema::Key;
+use crate::secrets;
+use crate::severity;
+use crate::toml::{self, TableExt};
+
+// Defaults read from the global config (`$XDG_CONFIG_HOME/reportgenerator/config.toml`)
+// and `reportgen.toml` in the report directory, the latter taking precedence.
+// Command line flags override both.
+//
+//  output = "report.pdf"
+//  template = "templates/main.typ"
+//  typst = "/usr/local/bin/typst"
+//  locale = "de"
+//  date_format = "dd.MM.yyyy"
+//  severities = ["critical", "high", "medium", "low", "info"]
+//  unreplaced_placeholders = "warn"
+//  secrets = "warn"
+//  clean_template = "templates/clean.typ"
+//  partials = "templates/partials"
+//  snippets = "../shared/snippets"
+//  theme = "modern"
+//  clean_result_threshold = "medium"
+//  qa_checklist = ["spelling", "severity_review", "redaction"]
+//  host_columns = 4
+//  host_appendix_threshold = 50
+//  screenshot_gallery = true
+//  manifest = true
+//  lint_dictionary = "en_GB"
+//  lint_ignore = ["Kerberoasting", "subdomain"]
+//  redact_headers = ["Authorization", "Cookie", "X-Api-Key"]
+//  sla_webhook = "https://hooks.slack.com/services/..."
+//  compile_webhook = "https://hooks.slack.com/services/..."
+//  jira_url = "https://acme.atlassian.net"
+//  jira_project = "SEC"
+//  jira_issue_type = "Bug"
+//  github_repo = "acme/shop"
+//  gitlab_url = "https://gitlab.acme.com"
+//  gitlab_repo = "appsec/shop"
+//  watch_ignore = ["evidence/raw", "*.pcap"]
+//  watch_paths = ["../snippets"]
+//  brand = "acme"
+//  fonts = ["Source Sans 3"]
+//  font_paths = ["fonts"]
+//  blob_max_length = 512
+//  toc = true
+//  toc_depth = 2
+//  page_numbering = "1 of 1"
+//  paper = "us-letter"
+//  page_margin = "2.5cm"
+//
+//  [pdf]
+//  title = "Penetration Test Report"
+//  author = "ACME Security"
+//  keywords = ["pentest", "web"]
+//
+//  [renames]
+//  client = "prepared_for"
+//
+//  [severity_labels]
+//  critical = "Urgent"
+//
+//  [severity_colors]
+//  critical = "#b00020"
+//
+//  [severity_scores]
+//  critical = "9.5-10.0"
+//
+//  [severity_descriptions]
+//  critical = "Immediate threat to the business"
+//
+//  [remediation_timelines]
+//  critical = "48 hours"
+//
+//  [csv_columns]
+//  title = "Issue"
+//  asset = "Host"
+//
+//  [jira_priorities]
+//  critical = "Blocker"
+
+pub const KEYS: &[Key] = &[
+    Key {
+        name: "output",
+        kind: "string",
+        description: "Output file of compile",
+    },
+    Key {
+        name: "template",
+        kind: "string",
+        description: "Main report template, relative to the config file",
+    },
+    Key {
+        name: "typst",
+        kind: "string",
+        description: "Path of the typst binary",
+    },
+    Key {
+        name: "locale",
+        kind: "string",
+        description: "Locale of month names in current_date",
+    },
+    Key {
+        name: "date_format",
+        kind: "string",
+        description: "Format of current_date",
+    },
+    Key {
+        name: "severities",
+        kind: "string[]",
+        description: "Severity scheme, most severe first",
+    },
+    Key {
+        name: "unreplaced_placeholders",
+        kind: "string",
+        description: "Whether leftover {{ placeholders }} and boilerplate like CHANGE ME fail compile (error) or only warn (warn)",
+    },
+    Key {
+        name: "secrets",
+        kind: "string",
+        description: "\"warn\" to only warn about likely secrets in the report sources when compiling, \"off\" to skip the scan, \"error\" by default",
+    },
+    Key {
+        name: "clean_template",
+        kind: "string",
+        description: "Main template of reports without findings at or above clean_result_threshold",
+    },
+    Key {
+        name: "partials",
+        kind: "string",
+        description: "Directory of partials (cover, page, tables, finding) replacing the built-in ones, relative to the config file",
+    },
+    Key {
+        name: "snippets",
+        kind: "string",
+        description: "Directory of shared snippets included with {{ include:name }}, searched before the workspace and global ones, relative to the config file",
+    },
+    Key {
+        name: "theme",
+        kind: "string",
+        description: "Built-in theme: classic, modern, minimal or dark-accent (compile)",
+    },
+    Key {
+        name: "clean_result_threshold",
+        kind: "string",
+        description: "Least severe level that still counts as a finding for the clean-result variant, low by default",
+    },
+    Key {
+        name: "qa_checklist",
+        kind: "string[]",
+        description: "Items of the QA checklist that have to pass before delivery (qa, state)",
+    },
+    Key {
+        name: "host_columns",
+        kind: "integer",
+        description: "Columns of the affected hosts tables, 4 by default",
+    },
+    Key {
+        name: "host_appendix_threshold",
+        kind: "integer",
+        description: "Most affected hosts listed in a finding, longer lists move to host_appendix (50 by default)",
+    },
+    Key {
+        name: "screenshot_gallery",
+        kind: "boolean",
+        description: "Collect the images of all findings in the screenshot_gallery appendix",
+    },
+    Key {
+        name: "lint_dictionary",
+        kind: "string",
+        description: "Hunspell dictionary of lint, en_US by default",
+    },
+    Key {
+        name: "lint_ignore",
+        kind: "string[]",
+        description: "Words lint never reports as misspelled",
+    },
+    Key {
+        name: "sla_webhook",
+        kind: "string",
+        description: "Incoming webhook URL digest posts overdue remediations of open retests to",
+    },
+    Key {
+        name: "compile_webhook",
+        kind: "string",
+        description: "Incoming webhook URL compile posts the report name, version, finding counts and outputs to",
+    },
+    Key {
+        name: "jira_url",
+        kind: "string",
+        description: "Base URL of the Jira instance push jira creates issues in",
+    },
+    Key {
+        name: "jira_project",
+        kind: "string",
+        description: "Key of the Jira project of push jira, overridden by --project",
+    },
+    Key {
+        name: "jira_issue_type",
+        kind: "string",
+        description: "Type of the issues push jira creates, Bug by default",
+    },
+    Key {
+        name: "github_repo",
+        kind: "string",
+        description: "Repository push github opens issues in, e.g. acme/shop, overridden by --repo",
+    },
+    Key {
+        name: "gitlab_url",
+        kind: "string",
+        description: "Base URL of the GitLab instance of push gitlab, https://gitlab.com by default",
+    },
+    Key {
+        name: "gitlab_repo",
+        kind: "string",
+        description: "Project push gitlab opens issues in, e.g. acme/shop, overridden by --repo",
+    },
+    Key {
+        name: "redact_headers",
+        kind: "string[]",
+        description: "Headers whose values are replaced in http_evidence, Authorization and cookies by default",
+    },
+    Key {
+        name: "watch_ignore",
+        kind: "string[]",
+        description: "Paths watch doesn't recompile on, * matches any text and patterns without / match names",
+    },
+    Key {
+        name: "watch_paths",
+        kind: "string[]",
+        description: "Files and directories outside the report that watch recompiles on, relative to the config file",
+    },
+    Key {
+        name: "brand",
+        kind: "string",
+        description: "Branding profile from $XDG_CONFIG_HOME/reportgenerator/brands/<name>.toml (compile)",
+    },
+    Key {
+        name: "fonts",
+        kind: "string[]",
+        description: "Font families the report needs, compile fails if typst can't find one",
+    },
+    Key {
+        name: "font_paths",
+        kind: "string[]",
+        description: "Directories with font files passed to typst, relative to the config file",
+    },
+    Key {
+        name: "blob_max_length",
+        kind: "integer",
+        description: "Longest encoded blob in raw evidence kept whole, longer ones are cut short with the full value in attachments/ (512 by default, 0 keeps all)",
+    },
+    Key {
+        name: "manifest",
+        kind: "boolean",
+        description: "Write MANIFEST.txt with the hashes of the compiled report and its sources on every compile",
+    },
+    Key {
+        name: "toc",
+        kind: "boolean",
+        description: "Show the table of contents, true by default",
+    },
+    Key {
+        name: "toc_depth",
+        kind: "integer",
+        description: "Deepest heading level in the table of contents, all by default",
+    },
+    Key {
+        name: "page_numbering",
+        kind: "string",
+        description: "Typst numbering pattern of the page numbers, \"1 of 1\" by default",
+    },
+    Key {
+        name: "paper",
+        kind: "string",
+        description: "Typst paper size, a4 by default (us-letter, a5, ...)",
+    },
+    Key {
+        name: "page_margin",
+        kind: "string",
+        description: "Page margin as a Typst length like 2.5cm, auto by default",
+    },
+    Key {
+        name: "pdf.title",
+        kind: "string",
+        description: "PDF document title",
+    },
+    Key {
+        name: "pdf.author",
+        kind: "string",
+        description: "PDF document author",
+    },
+    Key {
+        name: "pdf.keywords",
+        kind: "string[]",
+        description: "PDF document keywords",
+    },
+    Key {
+        name: "renames.<old>",
+        kind: "string",
+        description: "New name of a renamed template placeholder (templates migrate)",
+    },
+    Key {
+        name: "severity_labels.<level>",
+        kind: "string",
+        description: "Name of a severity level shown in the report, e.g. for client mandated names",
+    },
+    Key {
+        name: "severity_colors.<level>",
+        kind: "string",
+        description: "Badge and chart color (#rrggbb) of a severity level",
+    },
+    Key {
+        name: "severity_scores.<level>",
+        kind: "string",
+        description: "CVSS score range of a severity level in the rating definitions, e.g. 7.0-8.9",
+    },
+    Key {
+        name: "severity_descriptions.<level>",
+        kind: "string",
+        description: "Meaning of a severity level in the rating definitions",
+    },
+    Key {
+        name: "remediation_timelines.<level>",
+        kind: "string",
+        description: "Suggested remediation timeline of a priority in the remediation roadmap, e.g. 7 days",
+    },
+    Key {
+        name: "csv_columns.<field>",
+        kind: "string",
+        description: "Header of the spreadsheet column of a field (title, severity, description, remediation, asset) (import-csv)",
+    },
+    Key {
+        name: "jira_priorities.<level>",
+        kind: "string",
+        description: "Jira priority of the issues of a severity level (push jira)",
+    },
+];
+
+#[derive(Default)]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub keywords: Option<Vec<String>>,
+}
+
+#[derive(Default)]
+pub struct Config {
+    pub output: Option<String>,
+    // Main report template, relative paths are resolved against the config file
+    pub template: Option<PathBuf>,
+    // Main template of the clean-result variant, resolved like `template`
+    pub clean_template: Option<PathBuf>,
+    // Overrides of the built-in partials, resolved like `template`
+    pub partials: Option<PathBuf>,
+    // Shared snippets of `{{ include:name }}`, resolved like `template`
+    pub snippets: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub clean_result_threshold: Option<String>,
+    pub typst: Option<String>,
+    pub locale: Option<String>,
+    pub date_format: Option<String>,
+    pub severities: Option<Vec<String>>,
+    // Only warn about `{{ placeholders }}` left in the compiled report
+    pub warn_unreplaced: Option<bool>,
+    // What compile does about likely secrets: error, warn or off
+    pub secrets: Option<String>,
+    pub qa_checklist: Option<Vec<String>>,
+    pub host_columns: Option<usize>,
+    pub host_appendix_threshold: Option<usize>,
+    pub screenshot_gallery: Option<bool>,
+    pub manifest: Option<bool>,
+    pub lint_dictionary: Option<String>,
+    pub lint_ignore: Option<Vec<String>>,
+    // Headers of HTTP evidence whose values are replaced
+    pub redact_headers: Option<Vec<String>>,
+    // Incoming webhook notified of SLA breaches by digest
+    pub sla_webhook: Option<String>,
+    // Incoming webhook notified of builds by compile
+    pub compile_webhook: Option<String>,
+    pub jira_url: Option<String>,
+    pub jira_project: Option<String>,
+    pub jira_issue_type: Option<String>,
+    pub github_repo: Option<String>,
+    pub gitlab_url: Option<String>,
+    pub gitlab_repo: Option<String>,
+    pub watch_ignore: Option<Vec<String>>,
+    // Resolved like `template`
+    pub watch_paths: Option<Vec<PathBuf>>,
+    pub brand: Option<String>,
+    pub fonts: Option<Vec<String>>,
+    // Resolved like `template`
+    pub font_paths: Option<Vec<PathBuf>>,
+    pub blob_max_length: Option<usize>,
+    // Page layout, overridden by the metadata of the report
+    pub toc: Option<bool>,
+    pub toc_depth: Option<usize>,
+    pub page_numbering: Option<String>,
+    pub paper: Option<String>,
+    pub page_margin: Option<String>,
+    pub pdf: PdfMetadata,
+    // Placeholders renamed by the template, old name first
+    pub renames: Option<Vec<(String, String)>>,
+    // Presentation of severity levels, level first
+    pub severity_labels: Option<Vec<(String, String)>>,
+    pub severity_colors: Option<Vec<(String, String)>>,
+    // CVSS score range per level, level first
+    pub severity_scores: Option<Vec<(String, String)>>,
+    pub severity_descriptions: Option<Vec<(String, String)>>,
+    // Remediation timeline per priority, level first
+    pub remediation_timelines: Option<Vec<(String, String)>>,
+    // Spreadsheet column of every finding field, field first
+    pub csv_columns: Option<Vec<(String, String)>>,
+    // Jira priority per severity, level first
+    pub jira_priorities: Option<Vec<(String, String)>>,
+}
+
+/// `$XDG_CONFIG_HOME`, falling back to `~/.config`
+pub fn config_home() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+fn global_config_path() -> Option<PathBuf> {
+    Some(config_home()?.join(GLOBAL_CONFIG_FILE))
+}
+
+fn warn_unknown_keys(path: &Path, table: &Table, prefix: &str) {
+    for key in table.keys() {
+        let name = format!("{prefix}{key}");
+        // Tables like `[pdf]` are known through their dotted keys
+        let known = match table.get(key) {
+            Some(Value::Table(_)) => KEYS.iter().any(|k| k.name.starts_with(&format!("{name}."))),
+            _ => KEYS.iter().any(|k| k.name == name),
+        };
+        if !known {
+            eprintln!("WARNING: {}: unknown config key \"{name}\"", path.display());
+        }
+    }
+}
+
+fn string_list(table: &Table, key: &str) -> Option<Vec<String>> {
+    table.contains_key(key).then(|| table.get_str_list(key))
+}
+
+/// Integer value of at least `min`
+fn integer(path: &Path, table: &Table, key: &str, min: usize) -> Result<Option<usize>, String> {
+    match table.get(key) {
+        Some(Value::Integer(n)) if *n >= min as i64 => Ok(Some(*n as usize)),
+        Some(_) => Err(format!(
+            "{}: {key} has to be an integer of at least {min}",
+            path.display()
+        )),
+        None => Ok(None),
+    }
+}
+
+fn boolean(path: &Path, table: &Table, key: &str) -> Result<Option<bool>, String> {
+    match table.get(key) {
+        Some(Value::Boolean(value)) => Ok(Some(*value)),
+        Some(_) => Err(format!("{}: {key} has to be true or false", path.display())),
+        None => Ok(None),
+    }
+}
+
+/// String values of a table like `[renames]`
+fn string_table(table: &Table, key: &str) -> Option<Vec<(String, String)>> {
+    match table.get(key) {
+        Some(Value::Table(values)) => Some(
+            values
+                .keys()
+                .filter_map(|k| Some((k.to_string(), values.get_str(k)?.to_string())))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+impl Config {
+    fn parse(path: &Path, content: &str) -> Result<Self, String> {
+        let table = toml::parse(content).map_err(|e| format!("{}: {e}", path.display()))?;
+        warn_unknown_keys(path, &table, "");
+
+        let string = |key: &str| table.get_str(key).map(str::to_string);
+        let base = path.parent().unwrap_or(Path::new("."));
+
+        let mut pdf = PdfMetadata::default();
+        if let Some(Value::Table(pdf_table)) = table.get("pdf") {
+            warn_unknown_keys(path, pdf_table, "pdf.");
+            pdf.title = pdf_table.get_str("title").map(str::to_string);
+            pdf.author = pdf_table.get_str("author").map(str::to_string);
+            pdf.keywords = string_list(pdf_table, "keywords");
+        }
+
+        let renames = string_table(&table, "renames");
+        let severity_labels = string_table(&table, "severity_labels");
+        let severity_colors = string_table(&table, "severity_colors");
+        for (level, color) in severity_colors.iter().flatten() {
+            if !severity::is_color(color) {
+                return Err(format!(
+                    "{}: severity_colors.{level} has to be a #rrggbb color, got \"{color}\"",
+                    path.display()
+                ));
+            }
+        }
+        let severity_scores = string_table(&table, "severity_scores");
+        for (level, range) in severity_scores.iter().flatten() {
+            if severity::parse_range(range).is_none() {
+                return Err(format!(
+                    "{}: severity_scores.{level} has to be a score range like \"7.0-8.9\" within 0.0 to 10.0, got \"{range}\"",
+                    path.display()
+                ));
+            }
+        }
+
+        let secrets = string("secrets");
+        if let Some(mode) = secrets.as_deref().filter(|m| !secrets::MODES.contains(m)) {
+            return Err(format!(
+                "{}: secrets has to be one of {}, got \"{mode}\"",
+                path.display(),
+                secrets::MODES.join(", ")
+            ));
+        }
+
+        let warn_unreplaced = match table.get_str("unreplaced_placeholders") {
+            Some("error") => Some(false),
+            Some("warn") => Some(true),
+            Some(other) => {
+                return Err(format!(
+                    "{}: unreplaced_placeholders has to be \"error\" or \"warn\", got \"{other}\"",
+                    path.display()
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            output: string("output"),
+            template: string("template").map(|t| base.join(t)),
+            clean_template: string("clean_template").map(|t| base.join(t)),
+            partials: string("partials").map(|p| base.join(p)),
+            snippets: string("snippets").map(|p| base.join(p)),
+            theme: string("theme"),
+            clean_result_threshold: string("clean_result_threshold"),
+            typst: string("typst"),
+            locale: string("locale"),
+            date_format: string("date_format"),
+            severities: string_list(&table, "severities"),
+            warn_unreplaced,
+            secrets,
+            qa_checklist: string_list(&table, "qa_checklist"),
+            host_columns: integer(path, &table, "host_columns", 1)?,
+            host_appendix_threshold: integer(path, &table, "host_appendix_threshold", 0)?,
+            screenshot_gallery: boolean(path, &table, "screenshot_gallery")?,
+            manifest: boolean(path, &table, "manifest")?,
+            lint_dictionary: string("lint_dictionary"),
+            lint_ignore: string_list(&table, "lint_ignore"),
+            redact_headers: string_list(&table, "redact_headers"),
+            sla_webhook: string("sla_webhook"),
+            compile_webhook: string("compile_webhook"),
+            jira_url: string("jira_url"),
+            jira_project: string("jira_project"),
+            jira_issue_type: string("jira_issue_type"),
+            github_repo: string("github_repo"),
+            gitlab_url: string("gitlab_url"),
+            gitlab_repo: string("gitlab_repo"),
+            watch_ignore: string_list(&table, "watch_ignore"),
+            watch_paths: string_list(&table, "watch_paths")
+                .map(|paths| paths.iter().map(|p| base.join(p)).collect()),
+            brand: string("brand"),
+            fonts: string_list(&table, "fonts"),
+            font_paths: string_list(&table, "font_paths")
+                .map(|paths| paths.iter().map(|p| base.join(p)).collect()),
+            blob_max_length: integer(path, &table, "blob_max_length", 0)?,
+            toc: boolean(path, &table, "toc")?,
+            toc_depth: integer(path, &table, "toc_depth", 1)?,
+            page_numbering: string("page_numbering"),
+            paper: string("paper"),
+            page_margin: string("page_margin"),
+            pdf,
+            renames,
+            severity_labels,
+            severity_colors,
+            severity_scores,
+            severity_descriptions: string_table(&table, "severity_descriptions"),
+            remediation_timelines: string_table(&table, "remediation_timelines"),
+            csv_columns: string_table(&table, "csv_columns"),
+            jira_priorities: string_table(&table, "jira_priorities"),
+        })
+    }
+
+    /// Values set in `other` replace the ones in `self`
+    fn merge(self, other: Self) -> Self {
+        Self {
+            output: other.output.or(self.output),
+            template: other.template.or(self.template),
+            clean_template: other.clean_template.or(self.clean_template),
+            partials: other.partials.or(self.partials),
+            snippets: other.snippets.or(self.snippets),
+            theme: other.theme.or(self.theme),
+            clean_result_threshold: other.clean_result_threshold.or(self.clean_result_threshold),
+            typst: other.typst.or(self.typst),
+            locale: other.locale.or(self.locale),
+            date_format: other.date_format.or(self.date_format),
+            severities: other.severities.or(self.severities),
+            warn_unreplaced: other.warn_unreplaced.or(self.warn_unreplaced),
+            secrets: other.secrets.or(self.secrets),
+            qa_checklist: other.qa_checklist.or(self.qa_checklist),
+            host_columns: other.host_columns.or(self.host_columns),
+            host_appendix_threshold: other
+                .host_appendix_threshold
+                .or(self.host_appendix_threshold),
+            screenshot_gallery: other.screenshot_gallery.or(self.screenshot_gallery),
+            manifest: other.manifest.or(self.manifest),
+            lint_dictionary: other.lint_dictionary.or(self.lint_dictionary),
+            lint_ignore: other.lint_ignore.or(self.lint_ignore),
+            redact_headers: other.redact_headers.or(self.redact_headers),
+            sla_webhook: other.sla_webhook.or(self.sla_webhook),
+            compile_webhook: other.compile_webhook.or(self.compile_webhook),
+            jira_url: other.jira_url.or(self.jira_url),
+            jira_project: other.jira_project.or(self.jira_project),
+            jira_issue_type: other.jira_issue_type.or(self.jira_issue_type),
+            github_repo: other.github_repo.or(self.github_repo),
+            gitlab_url: other.gitlab_url.or(self.gitlab_url),
+            gitlab_repo: other.gitlab_repo.or(self.gitlab_repo),
+            watch_ignore: other.watch_ignore.or(self.watch_ignore),
+            watch_paths: other.watch_paths.or(self.watch_paths),
+            brand: other.brand.or(self.brand),
+            fonts: other.fonts.or(self.fonts),
+            font_paths: other.font_paths.or(self.font_paths),
+            blob_max_length: other.blob_max_length.or(self.blob_max_length),
+            toc: other.toc.or(self.toc),
+            toc_depth: other.toc_depth.or(self.toc_depth),
+            page_numbering: other.page_numbering.or(self.page_numbering),
+            paper: other.paper.or(self.paper),
+            page_margin: other.page_margin.or(self.page_margin),
+            pdf: PdfMetadata {
+                title: other.pdf.title.or(self.pdf.title),
+                author: other.pdf.author.or(self.pdf.author),
+                keywords: other.pdf.keywords.or(self.pdf.keywords),
+            },
+            renames: other.renames.or(self.renames),
+            severity_labels: other.severity_labels.or(self.severity_labels),
+            severity_colors: other.severity_colors.or(self.severity_colors),
+            severity_scores: other.severity_scores.or(self.severity_scores),
+            severity_descriptions: other.severity_descriptions.or(self.severity_descriptions),
+            remediation_timelines: other.remediation_timelines.or(self.remediation_timelines),
+            csv_columns: other.csv_columns.or(self.csv_columns),
+            jira_priorities: other.jira_priorities.or(self.jira_priorities),
+        }
+    }
+
+    /// Loads the global config merged with the one of the report at `report_path`
+    pub fn load(report_path: &Path) -> Result<Self, String> {
+        let mut config = Self::default();
+        let paths = global_config_path()
+            .into_iter()
+            .chain([report_path.join(CONFIG_FILE)]);
+        for path in paths {
+            if let Ok(content) = read_to_string(&path) {
+                config = config.merge(Self::parse(&path, &content)?);
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load_or_exit(report_path: &Path) -> Self {
+        Self::load(report_path).unwrap_or_else(|e| {
+            eprintln!("ERROR: Invalid config {e}");
+            exit(1);
+        })
+    }
+
+    pub fn typst(&self) -> &str {
+        self.typst.as_deref().unwrap_or("typst")
+    }
+
+    /// Configured severity scheme or the default levels, most severe first
+    pub fn severities(&self) -> Vec<String> {
+        match self.severities {
+            Some(ref severities) => severities.iter().map(|s| s.to_lowercase()).collect(),
+            None => severity::LEVELS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Configured names, colors, score ranges and descriptions of the severity levels
+    pub fn severity_presentation(&self) -> severity::Presentation {
+        let lowercase = |pairs: &Option<Vec<(String, String)>>| {
+            pairs
+                .iter()
+                .flatten()
+                .map(|(level, value)| (level.to_lowercase(), value.clone()))
+                .collect()
+        };
+        severity::Presentation {
+            labels: lowercase(&self.severity_labels),
+            colors: lowercase(&self.severity_colors),
+            scores: lowercase(&self.severity_scores),
+            descriptions: lowercase(&self.severity_descriptions),
+        }
+    }
+
+    /// Remediation timelines of the default levels, replaced by configured ones
+    pub fn remediation_timelines(&self) -> Vec<(String, String)> {
+        let mut timelines: Vec<(String, String)> = roadmap::DEFAULT_TIMELINES
+            .iter()
+            .map(|(level, timeline)| (level.to_string(), timeline.to_string()))
+            .collect();
+        for (level, timeline) in self.remediation_timelines.iter().flatten() {
+            let level = level.to_lowercase();
+            timelines.retain(|(l, _)| *l != level);
+            timelines.push((level, timeline.clone()));
+        }
+        timelines
+    }
+
+    /// Least severe level counting as a finding for the clean-result variant,
+    /// low or the least severe level of a custom scheme without low
+    pub fn clean_result_threshold(&self) -> Result<String, String> {
+        let severities = self.severities();
+        match self.clean_result_threshold {
+            Some(ref level) if severities.contains(&level.to_lowercase()) => {
+                Ok(level.to_lowercase())
+            }
+            Some(ref level) => Err(format!(
+                "clean_result_threshold \"{level}\" is not one of {}",
+                severities.join(", ")
+            )),
+            None if severities.iter().any(|s| s == "low") => Ok("low".to_string()),
+            None => Ok(severities.last().cloned().unwrap_or_default()),
+        }
+    }
+
+    /// Layout of the affected hosts tables
+    pub fn host_layout(&self) -> hosts::Layout {
+        hosts::Layout {
+            columns: self.host_columns.unwrap_or(hosts::DEFAULT_COLUMNS),
+            appendix_threshold: self
+                .host_appendix_threshold
+                .unwrap_or(hosts::DEFAULT_APPENDIX_THRESHOLD),
+        }
+    }
+
+    /// Headers redacted in HTTP evidence, an empty list redacts none
+    pub fn redact_headers(&self) -> Vec<String> {
+        match self.redact_headers {
+            Some(ref headers) => headers.clone(),
+            None => http_evidence::DEFAULT_REDACTED_HEADERS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+
+    /// Longest blob kept whole, 0 if blobs are never cut short
+    pub fn blob_max_length(&self) -> usize {
+        self.blob_max_length.unwrap_or(blobs::DEFAULT_MAX_LENGTH)
+    }
+
+    /// Configured QA checklist or the default items
+    pub fn qa_checklist(&self) -> Vec<String> {
+        match self.qa_checklist {
+            Some(ref checklist) => checklist.clone(),
+            None => qa::DEFAULT_CHECKLIST
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
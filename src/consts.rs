@@ -1,8 +1,68 @@
 pub const DEFAULT_REPORT_FILE: &str = "report.pdf";
-pub const TMP_FILE: &str = "tmp.typ";
+pub const DEFAULT_PREVIEW_FILE: &str = "preview.pdf";
 
 pub const MAIN_TEMPLATE: &str = include_str!("../templates/main_report.typ");
+// Variant for reports without findings at or above the clean-result threshold
+pub const CLEAN_RESULT_TEMPLATE: &str = include_str!("../templates/clean_result.typ");
+// Parts of the main templates, see partials
+pub const PARTIALS: &[(&str, &str)] = &[
+    ("page", include_str!("../templates/partials/page.typ")),
+    ("tables", include_str!("../templates/partials/tables.typ")),
+    ("cover", include_str!("../templates/partials/cover.typ")),
+    ("finding", include_str!("../templates/partials/finding.typ")),
+];
+// Partials of the built-in themes besides classic, replacing the ones above
+pub const THEMES: &[(&str, &[(&str, &str)])] = &[
+    (
+        "modern",
+        &[
+            ("page", include_str!("../templates/themes/modern/page.typ")),
+            (
+                "cover",
+                include_str!("../templates/themes/modern/cover.typ"),
+            ),
+            (
+                "tables",
+                include_str!("../templates/themes/modern/tables.typ"),
+            ),
+        ],
+    ),
+    (
+        "minimal",
+        &[
+            ("page", include_str!("../templates/themes/minimal/page.typ")),
+            (
+                "cover",
+                include_str!("../templates/themes/minimal/cover.typ"),
+            ),
+            (
+                "tables",
+                include_str!("../templates/themes/minimal/tables.typ"),
+            ),
+        ],
+    ),
+    (
+        "dark-accent",
+        &[
+            (
+                "page",
+                include_str!("../templates/themes/dark-accent/page.typ"),
+            ),
+            (
+                "cover",
+                include_str!("../templates/themes/dark-accent/cover.typ"),
+            ),
+            (
+                "tables",
+                include_str!("../templates/themes/dark-accent/tables.typ"),
+            ),
+        ],
+    ),
+];
+// Page of serve showing the live PDF
+pub const SERVE_PAGE: &str = include_str!("../templates/serve.html");
 pub const T_METADATA: &str = include_str!("../templates/metadata.typ");
+pub const T_GITIGNORE: &str = include_str!("../templates/gitignore");
 
 pub const T_SECTION: &str = include_str!("../templates/sections/default.typ");
 pub const T_SCOPE: &str = include_str!("../templates/sections/scope.typ");
@@ -12,3 +72,99 @@ pub const T_METHODOLOGY: &str = include_str!("../templates/sections/methodology.
 pub const T_FINDING: &str = include_str!("../templates/findings/default.typ");
 pub const T_XSS: &str = include_str!("../templates/findings/xss.typ");
 pub const T_SQL_INJECTION: &str = include_str!("../templates/findings/sql-injection.typ");
+
+pub const T_APPENDIX: &str = include_str!("../templates/appendices/default.typ");
+
+// Methodology sections of the engagement types of `new --kind`
+pub const T_METHODOLOGY_WEBAPP: &str = include_str!("../templates/engagements/webapp.typ");
+pub const T_METHODOLOGY_INTERNAL: &str = include_str!("../templates/engagements/internal.typ");
+pub const T_METHODOLOGY_EXTERNAL: &str = include_str!("../templates/engagements/external.typ");
+pub const T_METHODOLOGY_REDTEAM: &str = include_str!("../templates/engagements/redteam.typ");
+pub const T_METHODOLOGY_CLOUD: &str = include_str!("../templates/engagements/cloud.typ");
+pub const T_METHODOLOGY_MOBILE: &str = include_str!("../templates/engagements/mobile.typ");
+
+// Sections of the engagement types replacing the example section
+pub const T_SECTION_APPLICATION_OVERVIEW: &str =
+    include_str!("../templates/engagements/sections/application_overview.typ");
+pub const T_SECTION_AUTHENTICATION: &str =
+    include_str!("../templates/engagements/sections/authentication.typ");
+pub const T_SECTION_NETWORK_OVERVIEW: &str =
+    include_str!("../templates/engagements/sections/network_overview.typ");
+pub const T_SECTION_ACTIVE_DIRECTORY: &str =
+    include_str!("../templates/engagements/sections/active_directory.typ");
+pub const T_SECTION_ATTACK_SURFACE: &str =
+    include_str!("../templates/engagements/sections/attack_surface.typ");
+pub const T_SECTION_CLOUD_ARCHITECTURE: &str =
+    include_str!("../templates/engagements/sections/cloud_architecture.typ");
+pub const T_SECTION_IAM_REVIEW: &str =
+    include_str!("../templates/engagements/sections/iam_review.typ");
+pub const T_SECTION_STATIC_ANALYSIS: &str =
+    include_str!("../templates/engagements/sections/static_analysis.typ");
+pub const T_SECTION_DYNAMIC_ANALYSIS: &str =
+    include_str!("../templates/engagements/sections/dynamic_analysis.typ");
+pub const T_SECTION_ATTACK_NARRATIVE: &str =
+    include_str!("../templates/engagements/sections/attack_narrative.typ");
+pub const T_SECTION_DETECTION_RESPONSE: &str =
+    include_str!("../templates/engagements/sections/detection_response.typ");
+
+// Built-in templates by name
+pub const SECTION_TEMPLATES: &[(&str, &str)] = &[
+    ("default", T_SECTION),
+    ("summary", T_SUMMARY),
+    ("scope", T_SCOPE),
+    ("methodology", T_METHODOLOGY),
+];
+pub const FINDING_TEMPLATES: &[(&str, &str)] = &[
+    ("default", T_FINDING),
+    ("xss", T_XSS),
+    ("sql-injection", T_SQL_INJECTION),
+];
+
+pub const T_TUTORIAL: &str = include_str!("../templates/tutorial/TUTORIAL.md");
+pub const TUTORIAL_NVD_DUMP: &str = include_str!("../data/tutorial-nvd.json");
+
+pub const DEFAULT_GALLERY_DIR: &str = "template-gallery";
+
+pub const DEFAULT_ATTACK_LAYER_FILE: &str = "attack-layer.json";
+pub const ATTACK_TECHNIQUES: &str = include_str!("../data/attack-techniques.tsv");
+
+// Project config in the report directory and global config below $XDG_CONFIG_HOME
+pub const CONFIG_FILE: &str = "reportgen.toml";
+// In-scope hosts and applications referenced by findings
+pub const ASSETS_FILE: &str = "assets.toml";
+// Targets, exclusions and testing window of the engagement
+pub const SCOPE_FILE: &str = "scope.toml";
+// Imported subdomains with their addresses and status
+pub const ATTACK_SURFACE_FILE: &str = "attack-surface.tsv";
+// Full values of blobs cut short in the compiled report
+pub const ATTACHMENTS_DIR: &str = "attachments";
+// Raw evidence of the engagement types, e.g. tool output
+pub const EVIDENCE_DIR: &str = "evidence";
+// Known values the secret detection of compile leaves alone
+pub const SECRETS_ALLOWLIST_FILE: &str = "secrets-allowlist.txt";
+pub const GLOBAL_CONFIG_FILE: &str = "reportgenerator/config.toml";
+// Client profiles (`<name>.toml`) below $XDG_CONFIG_HOME
+pub const CLIENTS_DIR: &str = "reportgenerator/clients";
+// Shared snippets (`<name>.typ`) of reports, in a workspace and below $XDG_CONFIG_HOME
+pub const SNIPPETS_DIR: &str = "snippets";
+pub const GLOBAL_SNIPPETS_DIR: &str = "reportgenerator/snippets";
+// Branding profiles (`<name>.toml`) below $XDG_CONFIG_HOME
+pub const BRANDS_DIR: &str = "reportgenerator/brands";
+// Reports recently opened in the GUI/TUI, one path per line
+pub const RECENT_REPORTS_FILE: &str = "reportgenerator/recent-reports";
+
+// Directory inside a report holding tool state and caches
+pub const STATE_DIR: &str = ".reportgenerator";
+pub const NVD_CACHE_DIR: &str = "nvd";
+pub const HTTP_CACHE_DIR: &str = "http";
+pub const NVD_API_URL: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+pub const UNDO_DIR: &str = "undo";
+// Fingerprints of the last builds, one file per output format
+pub const BUILD_CACHE_DIR: &str = "build";
+// PDF compiled by serve
+pub const SERVE_DIR: &str = "serve";
+// Number of operations kept on the undo stack
+pub const UNDO_LIMIT: usize = 20;
+pub const CWE_ENTRIES: &str = include_str!("../data/cwe.tsv");
+// Leftover template text flagged by validate
+pub const PLACEHOLDER_TEXT: &[&str] = &["CHANGE ME", "TODO", "#lorem("];
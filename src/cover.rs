@@ -0,0 +1,32 @@
+use crate::brand;
+use crate::report::Report;
+use crate::utils::typst_str;
+
+// Logo on the cover page, the company's or the client's, set in the metadata:
+//
+//  logo:/srv/templates/company-logo.svg
+//
+// Relative paths are resolved against the report. The image is copied into
+// the report as cover-logo.<ext> next to the assembled document and scaled
+// to fit the logo box whatever its size, keeping its aspect ratio.
+
+pub const LOGO_KEY: &str = "logo";
+
+const LOGO_WIDTH: &str = "6cm";
+const LOGO_HEIGHT: &str = "3cm";
+
+/// `{{ cover_logo }}`, empty without a logo, copied into the report with `write`
+pub fn logo(report: &Report, write: bool) -> Result<String, String> {
+    let Some(logo) = report
+        .metadata_value(LOGO_KEY)
+        .filter(|logo| !logo.is_empty())
+    else {
+        return Ok(String::new());
+    };
+    let file_name = brand::copy_image(&report.path.join(logo), &report.path, "cover-logo", write)
+        .map_err(|e| format!("Logo {e}"))?;
+    Ok(format!(
+        "#box(width: {LOGO_WIDTH}, height: {LOGO_HEIGHT}, image({}, width: 100%, height: 100%, fit: \"contain\"))",
+        typst_str(&file_name)
+    ))
+}
@@ -0,0 +1,263 @@
+use std::{error::Error, fs::read_to_string, mem, path::PathBuf, process::exit};
+
+use crate::assets;
+use crate::changes::Changes;
+use crate::config::Config;
+use crate::hosts;
+use crate::rename::name_of;
+use crate::report::{self, Report};
+use crate::severity;
+use crate::toml;
+use crate::utils::typst_escape;
+
+// Imports findings triaged in a spreadsheet, exported as CSV with a header
+// row. Every row with a title becomes a finding. Columns are found by their
+// header, the field name by default, `[csv_columns]` of the config maps the
+// fields to the headers of a team's sheet:
+//
+//  [csv_columns]
+//  title = "Issue"
+//  severity = "Risk"
+//  description = "Details"
+//  remediation = "Fix"
+//  asset = "Host"
+//
+// Semicolon and tab separated files are recognized by their header. Assets
+// known from assets.toml are listed as assets, others as affected hosts.
+
+pub const FIELDS: &[&str] = &["title", "severity", "description", "remediation", "asset"];
+
+/// Rows of CSV text, quoted cells may contain separators, quotes and line breaks
+fn parse(text: &str) -> Vec<Vec<String>> {
+    let header = text.lines().next().unwrap_or_default();
+    let separator = [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|s| header.matches(*s).count())
+        .unwrap_or(',');
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if cell.is_empty() => quoted = true,
+            (c, false) if c == separator => row.push(mem::take(&mut cell)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(mem::take(&mut cell));
+                rows.push(mem::take(&mut row));
+            }
+            (c, _) => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    // Blank lines of the sheet
+    rows.retain(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+    rows
+}
+
+/// Lines of a cell as escaped Typst paragraphs
+fn text(cell: &str) -> String {
+    cell.trim()
+        .lines()
+        .map(|line| typst_escape(line.trim()))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+pub fn import_csv(
+    file: String,
+    report_dir: Option<PathBuf>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    let known_assets = assets::load(&report_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let severities = config.severities();
+
+    let content = read_to_string(&file).unwrap_or_else(|e| {
+        eprintln!("ERROR: Failed to read {file}: {e}");
+        exit(1);
+    });
+    let rows = parse(content.trim_start_matches('\u{feff}'));
+    let Some((header, rows)) = rows.split_first() else {
+        eprintln!("ERROR: {file} has no header row");
+        exit(1);
+    };
+
+    // Column of every field, headers are matched case-insensitively
+    let mapping = config.csv_columns.clone().unwrap_or_default();
+    let column = |field: &str| {
+        let name = mapping
+            .iter()
+            .find(|(f, _)| f == field)
+            .map_or(field, |(_, header)| header.as_str());
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name.trim()))
+    };
+    let Some(title_column) = column("title") else {
+        eprintln!("ERROR: {file} has no title column, map it in [csv_columns] of the config");
+        exit(1);
+    };
+    let columns: Vec<(&str, Option<usize>)> = FIELDS.iter().map(|f| (*f, column(f))).collect();
+    let cell = |row: &[String], field: &str| {
+        columns
+            .iter()
+            .find(|(f, _)| *f == field)
+            .and_then(|(_, column)| row.get((*column)?))
+            .map_or("", |cell| cell.trim())
+            .to_string()
+    };
+
+    let mut changes = Changes::for_report(&report_path);
+    let mut id = report.findings.iter().map(|f| f.id).max().unwrap_or(0);
+    let mut names: Vec<String> = report.findings.iter().map(|f| f.name.clone()).collect();
+    let mut skipped = 0;
+    for (line, row) in rows.iter().enumerate() {
+        let title = row.get(title_column).map_or("", |t| t.trim());
+        let name = name_of(title);
+        if name.is_empty() {
+            eprintln!("WARNING: Row {} has no title, skipped", line + 2);
+            skipped += 1;
+            continue;
+        }
+        // Findings of earlier imports are kept
+        if names.contains(&name) {
+            skipped += 1;
+            continue;
+        }
+
+        let mut front_matter = String::new();
+        let severity = cell(row, "severity").to_lowercase();
+        if severity::is_valid(&severity, &severities) {
+            front_matter.push_str(&format!("severity = {}\n", toml::quote(&severity)));
+        } else if !severity.is_empty() {
+            eprintln!(
+                "WARNING: Row {}: unknown severity \"{severity}\", expected one of {severities:?}",
+                line + 2
+            );
+        }
+        let (in_assets, hosts): (Vec<String>, Vec<String>) = cell(row, "asset")
+            .split([',', ';', '\n'])
+            .map(|asset| asset.trim().to_string())
+            .filter(|asset| !asset.is_empty())
+            .partition(|asset| known_assets.iter().any(|known| known.id == *asset));
+        let list = |items: &[String]| {
+            items
+                .iter()
+                .map(|i| toml::quote(i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if !in_assets.is_empty() {
+            front_matter.push_str(&format!(
+                "{} = [{}]\n",
+                assets::FRONT_MATTER_KEY,
+                list(&in_assets)
+            ));
+        }
+        if !hosts.is_empty() {
+            front_matter.push_str(&format!(
+                "{} = [{}]\n",
+                hosts::FRONT_MATTER_KEY,
+                list(&hosts)
+            ));
+        }
+
+        let mut finding = match front_matter.is_empty() {
+            true => String::new(),
+            false => format!("+++\n{front_matter}+++\n"),
+        };
+        finding.push_str(&format!("= Finding: {}\n", typst_escape(title)));
+        let description = text(&cell(row, "description"));
+        if !description.is_empty() {
+            finding.push_str(&format!("{description}\n"));
+        }
+        if !hosts.is_empty() {
+            finding.push_str("\n{{ affected_hosts }}\n");
+        }
+        if !in_assets.is_empty() {
+            finding.push_str("\n{{ affected_assets }}\n");
+        }
+        let remediation = text(&cell(row, "remediation"));
+        if !remediation.is_empty() {
+            finding.push_str(&format!("\n== Remediation\n{remediation}\n"));
+        }
+
+        id += 1;
+        changes.create(
+            report_path
+                .join("findings")
+                .join(format!("{id}.{name}.typ")),
+            &finding,
+        );
+        names.push(name);
+    }
+    let created = rows.len() - skipped;
+    changes.apply(dry_run)?;
+
+    if !dry_run {
+        println!("Created {created} finding(s) from {file}, skipped {skipped} row(s)");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn quoted_cells_keep_separators_quotes_and_line_breaks() {
+        let text = "title,description\r\n\"XSS, stored\",\"Says \"\"hi\"\"\non two lines\"\r\n";
+        assert_eq!(
+            parse(text),
+            rows(&[
+                &["title", "description"],
+                &["XSS, stored", "Says \"hi\"\non two lines"],
+            ])
+        );
+    }
+
+    #[test]
+    fn separator_is_taken_from_the_header() {
+        assert_eq!(
+            parse("title;severity\nSQLi, blind;high"),
+            rows(&[&["title", "severity"], &["SQLi, blind", "high"]])
+        );
+        assert_eq!(
+            parse("title\tseverity\nSQLi; blind\thigh\n"),
+            rows(&[&["title", "severity"], &["SQLi; blind", "high"]])
+        );
+    }
+
+    #[test]
+    fn blank_rows_are_dropped() {
+        assert_eq!(
+            parse("title,severity\n\n,\nXSS,low\n"),
+            rows(&[&["title", "severity"], &["XSS", "low"]])
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use crate::consts::CWE_ENTRIES;
+
+/// Embedded CWE entries as `(id, name)`
+pub fn entries() -> impl Iterator<Item = (&'static str, &'static str)> {
+    CWE_ENTRIES
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+}
+
+/// Checks the `CWE-<number>` format
+pub fn is_well_formed(id: &str) -> bool {
+    id.strip_prefix("CWE-")
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Name of an embedded CWE entry
+pub fn lookup(id: &str) -> Option<&'static str> {
+    entries()
+        .find(|(entry, _)| entry.eq_ignore_ascii_case(id))
+        .map(|(_, name)| name)
+}
+
+/// Short form of a CWE name, e.g. `Path Traversal` for the quoted part of CWE-22
+pub fn short_name(name: &str) -> &str {
+    name.split_once("('")
+        .and_then(|(_, rest)| rest.split_once("')"))
+        .map_or(name, |(short, _)| short)
+}
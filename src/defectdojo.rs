@@ -0,0 +1,153 @@
+use std::{fs::read, path::Path};
+
+use serde::Serialize;
+
+use crate::base64;
+use crate::model::{Finding, Report};
+use crate::sla::CLOSED_STATUSES;
+
+// Findings in the Generic Findings Import format of DefectDojo, which most
+// vulnerability management platforms can read as well. Screenshots are
+// embedded as files, hosts become endpoints and the part of a finding after
+// its remediation heading is the mitigation:
+//
+//  report-generator export defectdojo -o findings.json
+//
+// Import with the "Generic Findings Import" scan type.
+
+// Severities of DefectDojo, other levels are mapped to Info
+const SEVERITIES: &[&str] = &["Critical", "High", "Medium", "Low", "Info"];
+
+fn severity(finding: &Finding) -> &'static str {
+    let severity = finding.severity.as_deref().unwrap_or_default();
+    SEVERITIES
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(severity))
+        .copied()
+        .unwrap_or("Info")
+}
+
+#[derive(Serialize)]
+pub struct Import<'a> {
+    findings: Vec<ImportFinding<'a>>,
+}
+
+#[derive(Serialize)]
+struct ImportFinding<'a> {
+    title: &'a str,
+    severity: &'static str,
+    description: String,
+    mitigation: String,
+    date: &'a str,
+    unique_id_from_tool: &'a str,
+    active: bool,
+    verified: bool,
+    // DefectDojo takes a single CWE number and CVE
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cwe: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cve: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cvssv3_score: Option<f64>,
+    endpoints: Vec<Endpoint<'a>>,
+    files: Vec<File<'a>>,
+}
+
+#[derive(Serialize)]
+struct Endpoint<'a> {
+    host: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct File<'a> {
+    title: &'a str,
+    // Base64 encoded
+    data: String,
+}
+
+/// Endpoint of a host like `10.0.0.5`, `app.example.com:8443` or `https://app.example.com/login`
+fn endpoint(host: &str) -> Endpoint<'_> {
+    let (protocol, rest) = match host.split_once("://") {
+        Some((protocol, rest)) => (Some(protocol), rest),
+        None => (None, host),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+        None => (rest, None),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => (host, port.parse::<u16>().ok()),
+        _ => (authority, None),
+    };
+    Endpoint {
+        host,
+        port,
+        protocol,
+        path: path.filter(|p| !p.is_empty()),
+    }
+}
+
+/// Screenshots of a finding as files, missing ones are left out with a warning
+fn files<'a>(finding: &'a Finding, report_path: &Path) -> Vec<File<'a>> {
+    let mut files = Vec::new();
+    for evidence in &finding.evidence {
+        match read(report_path.join(&evidence.path)) {
+            Ok(data) => files.push(File {
+                title: &evidence.path,
+                data: base64::encode(&data),
+            }),
+            Err(e) => eprintln!(
+                "WARNING: Evidence {} of {} not readable: {e}",
+                evidence.path, finding.id
+            ),
+        }
+    }
+    files
+}
+
+fn import_finding<'a>(
+    finding: &'a Finding,
+    date: &'a str,
+    report_path: &Path,
+) -> ImportFinding<'a> {
+    let (description, mitigation) = finding.description_and_remediation();
+    let active = finding
+        .status
+        .as_deref()
+        .is_none_or(|status| !CLOSED_STATUSES.contains(&status.to_lowercase().as_str()));
+    ImportFinding {
+        title: &finding.title,
+        severity: severity(finding),
+        description,
+        mitigation,
+        date,
+        unique_id_from_tool: &finding.id,
+        active,
+        verified: true,
+        cwe: finding
+            .cwe
+            .iter()
+            .find_map(|cwe| cwe.trim_start_matches("CWE-").parse::<u32>().ok()),
+        cve: finding.cve.first().map(String::as_str),
+        cvssv3_score: finding.cvss.as_deref().and_then(|s| s.parse::<f64>().ok()),
+        endpoints: finding.hosts.iter().map(|host| endpoint(host)).collect(),
+        files: files(finding, report_path),
+    }
+}
+
+/// Findings of `report` as a Generic Findings Import document, found on `date` (YYYY-MM-DD)
+pub fn export<'a>(report: &'a Report, date: &'a str, report_path: &Path) -> Import<'a> {
+    Import {
+        findings: report
+            .findings
+            .iter()
+            .map(|finding| import_finding(finding, date, report_path))
+            .collect(),
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::config;
+use crate::consts::RECENT_REPORTS_FILE;
+use crate::workspace;
+
+// Integration with the desktop of the OS through its own tools, like typst and
+// qpdf nothing is linked in: folder pickers, the trash and the file manager
+
+const MAX_RECENT_REPORTS: usize = 10;
+
+/// Runs the first available command, its trimmed stdout on success
+fn run_first(commands: &[&[&str]]) -> Result<Option<String>, String> {
+    for command in commands {
+        let output = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                return Ok(Some(
+                    String::from_utf8_lossy(&output.stdout).trim().to_string(),
+                ))
+            }
+            // Dialogs exit with an error when cancelled
+            Ok(_) => return Ok(None),
+            Err(_) => continue,
+        }
+    }
+    Err(format!(
+        "None of {} is installed",
+        commands.iter().map(|c| c[0]).collect::<Vec<_>>().join(", ")
+    ))
+}
+
+/// PowerShell ('') or AppleScript ("") string literal
+#[cfg(feature = "gui")]
+fn quoted(s: &str, quote: char) -> String {
+    let escaped = match quote {
+        '\'' => s.replace('\'', "''"),
+        _ => s.replace('\\', "\\\\").replace('"', "\\\""),
+    };
+    format!("{quote}{escaped}{quote}")
+}
+
+/// Native folder picker, `None` if cancelled
+#[cfg(feature = "gui")]
+pub fn pick_folder(title: &str) -> Result<Option<PathBuf>, String> {
+    let folder = if cfg!(target_os = "windows") {
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $d = New-Object System.Windows.Forms.FolderBrowserDialog; \
+             $d.Description = {}; \
+             if ($d.ShowDialog() -eq 'OK') {{ $d.SelectedPath }} else {{ exit 1 }}",
+            quoted(title, '\'')
+        );
+        run_first(&[&["powershell", "-NoProfile", "-Command", &script]])?
+    } else if cfg!(target_os = "macos") {
+        let script = format!(
+            "POSIX path of (choose folder with prompt {})",
+            quoted(title, '"')
+        );
+        run_first(&[&["osascript", "-e", &script]])?
+    } else {
+        run_first(&[
+            &[
+                "zenity",
+                "--file-selection",
+                "--directory",
+                "--title",
+                title,
+            ],
+            &["kdialog", "--getexistingdirectory", ".", "--title", title],
+        ])?
+    };
+    Ok(folder.filter(|f| !f.is_empty()).map(PathBuf::from))
+}
+
+/// Opens a file or directory with its default application
+pub fn open_path(path: &Path) -> Result<(), String> {
+    let path = path.display().to_string();
+    let opened = if cfg!(target_os = "windows") {
+        run_first(&[&["explorer", &path]])
+    } else if cfg!(target_os = "macos") {
+        run_first(&[&["open", &path]])
+    } else {
+        run_first(&[&["xdg-open", &path], &["gio", "open", &path]])
+    };
+    // explorer exits with 1 even when it succeeds
+    opened.map(|_| ())
+}
+
+/// Moves a file or directory to the trash of the OS, it is never deleted permanently
+#[cfg(feature = "gui")]
+pub fn move_to_trash(path: &Path) -> Result<(), String> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    let trashed = if cfg!(target_os = "windows") {
+        let method = if path.is_dir() {
+            "DeleteDirectory"
+        } else {
+            "DeleteFile"
+        };
+        let script = format!(
+            "Add-Type -AssemblyName Microsoft.VisualBasic; \
+             [Microsoft.VisualBasic.FileIO.FileSystem]::{method}({}, 'OnlyErrorDialogs', 'SendToRecycleBin')",
+            quoted(&path.display().to_string(), '\'')
+        );
+        run_first(&[&["powershell", "-NoProfile", "-Command", &script]])?
+    } else if cfg!(target_os = "macos") {
+        let script = format!(
+            "tell application \"Finder\" to delete POSIX file {}",
+            quoted(&path.display().to_string(), '"')
+        );
+        run_first(&[&["osascript", "-e", &script]])?
+    } else {
+        let path = path.display().to_string();
+        run_first(&[&["gio", "trash", &path], &["trash-put", &path]])?
+    };
+    match trashed {
+        Some(_) => Ok(()),
+        None => Err(format!("Failed to move {} to the trash", path.display())),
+    }
+}
+
+fn recent_reports_path() -> Option<PathBuf> {
+    Some(config::config_home()?.join(RECENT_REPORTS_FILE))
+}
+
+/// Recently opened reports, most recent first, without ones that were removed since
+pub fn recent_reports() -> Vec<PathBuf> {
+    let Some(content) = recent_reports_path().and_then(|path| read_to_string(path).ok()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(PathBuf::from)
+        .filter(|path| workspace::is_report(path))
+        .collect()
+}
+
+/// Adds a report to the front of the recent reports, failures are not worth reporting
+pub fn remember(report_path: &Path) {
+    let (Some(path), Ok(report_path)) = (recent_reports_path(), report_path.canonicalize()) else {
+        return;
+    };
+    let mut recent = recent_reports();
+    recent.retain(|p| p != &report_path);
+    recent.insert(0, report_path);
+    recent.truncate(MAX_RECENT_REPORTS);
+
+    let content: String = recent
+        .iter()
+        .map(|p| format!("{}\n", p.display()))
+        .collect();
+    if let Some(dir) = path.parent() {
+        let _ = create_dir_all(dir);
+    }
+    let _ = write(path, content);
+}
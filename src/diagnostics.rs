@@ -0,0 +1,432 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::assets;
+use crate::attack;
+use crate::consts::{ASSETS_FILE, SCOPE_FILE};
+use crate::cwe;
+use crate::front_matter;
+use crate::hosts;
+use crate::nvd;
+use crate::review;
+use crate::risk;
+use crate::roadmap;
+use crate::schema::{METADATA_KEYS, OPTIONAL_METADATA_KEYS, PLACEHOLDERS};
+use crate::scope::Scope;
+use crate::severity::{self, Presentation};
+use crate::snippets;
+use crate::timesheet;
+use crate::toml;
+use crate::xref;
+
+// Problems found in report files, shared by `check` and the language server.
+// Lines and columns are 0-based (as in LSP) and counted in characters.
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl Level {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        }
+    }
+}
+
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub level: Level,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Error concerning a whole line (or the whole file for line 0)
+    pub fn error(path: PathBuf, line: usize, message: String) -> Self {
+        Self {
+            path,
+            line,
+            start: 0,
+            end: 0,
+            level: Level::Error,
+            message,
+        }
+    }
+
+    /// Stable JSON representation with 1-based lines and columns and a path relative to `base`
+    pub fn to_json(&self, base: &Path) -> Value {
+        let file = self.path.strip_prefix(base).unwrap_or(&self.path);
+        json!({
+            "file": file.to_string_lossy(),
+            "line": self.line + 1,
+            "column": self.start + 1,
+            "end_line": self.line + 1,
+            "end_column": self.end + 1,
+            "severity": self.level.as_str(),
+            "message": self.message,
+        })
+    }
+}
+
+/// Key of a `key = value` front-matter line
+pub fn front_matter_key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        Some(key)
+    } else {
+        None
+    }
+}
+
+/// Quoted strings of a front-matter value, with their character offsets
+fn quoted_values(line: &str) -> Vec<(usize, String)> {
+    let mut values = Vec::new();
+    let mut chars = line.char_indices();
+    let value_start = line.find('=').unwrap_or(0);
+    while let Some((i, c)) = chars.next() {
+        if c != '"' || i < value_start {
+            continue;
+        }
+        let value: String = chars
+            .by_ref()
+            .map(|(_, c)| c)
+            .take_while(|c| *c != '"')
+            .collect();
+        values.push((line[..i].chars().count() + 1, value));
+    }
+    values
+}
+
+fn is_known_placeholder(name: &str, severities: &[String]) -> bool {
+    if PLACEHOLDERS
+        .iter()
+        .chain(METADATA_KEYS)
+        .chain(OPTIONAL_METADATA_KEYS)
+        .any(|k| k.name == name)
+    {
+        return true;
+    }
+    // Finding counts per severity, e.g. high_findings
+    if name
+        .strip_suffix("_findings")
+        .is_some_and(|s| s == "unrated" || severities.iter().any(|level| level == s))
+    {
+        return true;
+    }
+    // Finding references, e.g. ref:FND-003, and snippets, e.g. include:disclaimer
+    if xref::is_reference(name) || snippets::is_include(name) {
+        return true;
+    }
+    // Per CVE placeholders, e.g. CVE-2021-44228.description
+    match name.split_once('.') {
+        Some((id, field)) => {
+            nvd::find_cve_ids(id).first().map(String::as_str) == Some(id)
+                && ["description", "cvss", "references"].contains(&field)
+        }
+        None => false,
+    }
+}
+
+/// References of a report file to findings that don't exist, `findings` are their numbers
+pub fn check_references(path: &Path, text: &str, findings: &[usize]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (line_number, line) in text.lines().enumerate() {
+        for (range, label) in xref::references(line) {
+            if xref::parse_label(label).is_some_and(|id| findings.contains(&id)) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                path: path.to_path_buf(),
+                line: line_number,
+                start: line[..range.start].chars().count(),
+                end: line[..range.end].chars().count(),
+                level: Level::Error,
+                message: format!("Reference to unknown finding \"{label}\""),
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Asset references in the front-matter of a report file that aren't in `assets`
+pub fn check_assets(path: &Path, text: &str, assets: &[String]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let (Some(front_matter), _) = front_matter::split(text) else {
+        return diagnostics;
+    };
+    for (i, line) in front_matter.lines().enumerate() {
+        if front_matter_key(line) != Some(assets::FRONT_MATTER_KEY) {
+            continue;
+        }
+        for (start, value) in quoted_values(line) {
+            if assets.contains(&value) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                path: path.to_path_buf(),
+                line: i + 1,
+                start,
+                end: start + value.chars().count(),
+                level: Level::Error,
+                message: format!("Unknown asset \"{value}\", describe it in {ASSETS_FILE}"),
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Hosts in the front-matter of a report file outside of `scope`
+pub fn check_scope(path: &Path, text: &str, scope: &Scope) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let (Some(front_matter), _) = front_matter::split(text) else {
+        return diagnostics;
+    };
+    for (i, line) in front_matter.lines().enumerate() {
+        if front_matter_key(line) != Some(hosts::FRONT_MATTER_KEY) {
+            continue;
+        }
+        for (start, value) in quoted_values(line) {
+            let Some(reason) = scope.violation(&value) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                path: path.to_path_buf(),
+                line: i + 1,
+                start,
+                end: start + value.chars().count(),
+                level: Level::Warning,
+                message: format!("Host \"{value}\" {reason} {SCOPE_FILE}"),
+            });
+        }
+    }
+    diagnostics
+}
+
+/// A `cvss` score in the front-matter outside the score range of the `severity`
+pub fn check_score(path: &Path, text: &str, presentation: &Presentation) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let (Some(front_matter), _) = front_matter::split(text) else {
+        return diagnostics;
+    };
+    let value = |key: &str| {
+        front_matter.lines().enumerate().find_map(|(i, line)| {
+            let (_, value) = line
+                .split_once('=')
+                .filter(|_| front_matter_key(line) == Some(key))?;
+            Some((i, line, value.trim().trim_matches('"')))
+        })
+    };
+    let (Some((_, _, severity)), Some((i, line, cvss))) = (value("severity"), value("cvss")) else {
+        return diagnostics;
+    };
+    let (Ok(score), Some((low, high))) = (cvss.parse::<f64>(), presentation.score_range(severity))
+    else {
+        return diagnostics;
+    };
+    if !(low..=high).contains(&score) {
+        let start = line.rfind(cvss).unwrap_or(0);
+        diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line: i + 1,
+            start,
+            end: start + cvss.chars().count(),
+            level: Level::Warning,
+            message: format!(
+                "CVSS score {cvss} is outside the range {low:.1}-{high:.1} of severity \"{severity}\""
+            ),
+        });
+    }
+    diagnostics
+}
+
+/// Checks the front-matter and placeholders of a single report file
+pub fn check_file(path: &Path, text: &str, severities: &[String]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut push = |line: usize, start: usize, end: usize, level: Level, message: String| {
+        diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line,
+            start,
+            end,
+            level,
+            message,
+        })
+    };
+
+    // Front-matter of sections and findings, its first line follows the delimiter
+    if let (Some(front_matter), _) = front_matter::split(text) {
+        match toml::parse(front_matter) {
+            Ok(_) => {
+                if let Err((line, message)) = front_matter::validate(front_matter) {
+                    push(line, 0, 0, Level::Error, message);
+                }
+            }
+            Err(e) => {
+                // Errors are reported as "line N: message" relative to the block
+                let line = e
+                    .strip_prefix("line ")
+                    .and_then(|r| r.split(':').next())
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .unwrap_or(0);
+                push(line, 0, 0, Level::Error, e);
+            }
+        }
+
+        // Table of the keys that follow, e.g. http for [[http]]
+        let mut table = String::new();
+        let mut in_multiline_string = false;
+        for (i, line) in front_matter.lines().enumerate() {
+            let line_number = i + 1;
+            // Lines of multi-line strings are values, not keys
+            let multiline = line.matches("\"\"\"").count() % 2 == 1;
+            let was_in_string = in_multiline_string;
+            in_multiline_string ^= multiline;
+            if was_in_string {
+                continue;
+            }
+            if let Some(name) = line.trim().strip_prefix('[') {
+                table = name.trim_matches(['[', ']']).trim().to_string();
+                continue;
+            }
+            let Some(key) = front_matter_key(line) else {
+                continue;
+            };
+            let key_start = line.find(key).unwrap_or(0);
+            let qualified = match table.is_empty() {
+                true => key.to_string(),
+                false => format!("{table}.{key}"),
+            };
+            if !front_matter::KEYS.iter().any(|k| k.name == qualified) {
+                push(
+                    line_number,
+                    key_start,
+                    key_start + key.len(),
+                    Level::Warning,
+                    format!("Unknown front-matter key \"{qualified}\""),
+                );
+                continue;
+            }
+            if !table.is_empty() {
+                continue;
+            }
+
+            if key == "cvss" {
+                let (_, value) = line.split_once('=').unwrap_or_default();
+                let value = value.trim();
+                if value
+                    .parse::<f64>()
+                    .is_ok_and(|score| !(0.0..=10.0).contains(&score))
+                {
+                    let start = line.rfind(value).unwrap_or(0);
+                    push(
+                        line_number,
+                        start,
+                        start + value.chars().count(),
+                        Level::Error,
+                        format!("Invalid cvss \"{value}\", expected a score from 0.0 to 10.0"),
+                    );
+                }
+                continue;
+            }
+
+            if key == risk::LIKELIHOOD_KEY || key == risk::IMPACT_KEY {
+                let (_, value) = line.split_once('=').unwrap_or_default();
+                let value = value.trim();
+                if !value.parse().is_ok_and(risk::is_valid) {
+                    let start = line.rfind(value).unwrap_or(0);
+                    push(
+                        line_number,
+                        start,
+                        start + value.chars().count(),
+                        Level::Error,
+                        format!(
+                            "Invalid {key} \"{value}\", expected a number from 1 to {}",
+                            risk::SCALE
+                        ),
+                    );
+                }
+                continue;
+            }
+
+            for (start, value) in quoted_values(line) {
+                let end = start + value.chars().count();
+                let message = match key {
+                    "severity" if !severity::is_valid(&value, severities) => {
+                        format!("Invalid severity \"{value}\", expected one of {severities:?}")
+                    }
+                    roadmap::PRIORITY_KEY if !severity::is_valid(&value, severities) => {
+                        format!("Invalid priority \"{value}\", expected one of {severities:?}")
+                    }
+                    roadmap::EFFORT_KEY
+                        if !roadmap::EFFORTS.contains(&value.to_lowercase().as_str()) =>
+                    {
+                        format!(
+                            "Invalid effort \"{value}\", expected one of {:?}",
+                            roadmap::EFFORTS
+                        )
+                    }
+                    timesheet::TIME_SPENT_KEY if timesheet::parse_hours(&value).is_none() => {
+                        format!(
+                            "Invalid {key} \"{value}\", expected a duration like 2h, 45m or 1h30m"
+                        )
+                    }
+                    attack::FRONT_MATTER_KEY if attack::lookup(&value).is_none() => {
+                        format!("Unknown ATT&CK technique \"{value}\"")
+                    }
+                    "cwe" if !cwe::is_well_formed(&value) => {
+                        format!("Invalid CWE ID \"{value}\", expected CWE-<number>")
+                    }
+                    "review" if !review::STATES.contains(&value.as_str()) => {
+                        format!(
+                            "Invalid review state \"{value}\", expected one of {:?}",
+                            review::STATES
+                        )
+                    }
+                    _ => continue,
+                };
+                push(line_number, start, end, Level::Error, message);
+            }
+        }
+    }
+
+    // Placeholders, the metadata file only holds values
+    let metadata_file = path.file_name().is_some_and(|name| name == "metadata.typ");
+    for (line_number, line) in text.lines().enumerate() {
+        if metadata_file {
+            break;
+        }
+        let mut offset = 0;
+        while let Some(start) = line[offset..].find("{{") {
+            let start = offset + start;
+            let Some(end) = line[start..].find("}}") else {
+                break;
+            };
+            let end = start + end + 2;
+            let name = line[start + 2..end - 2].trim();
+            if !is_known_placeholder(name, severities) {
+                push(
+                    line_number,
+                    line[..start].chars().count(),
+                    line[..end].chars().count(),
+                    Level::Warning,
+                    format!("Unknown placeholder \"{name}\""),
+                );
+            }
+            offset = end;
+        }
+    }
+
+    diagnostics
+}
@@ -0,0 +1,169 @@
+use std::{
+    env,
+    error::Error,
+    fs::remove_dir_all,
+    path::{Path, PathBuf},
+    process::{self, exit},
+};
+
+use crate::git;
+use crate::report::{Entry, Kind, Report};
+use crate::toml::TableExt;
+use crate::workspace;
+
+// Semantic comparison of two versions of a report: which sections and
+// findings were added, removed, moved or changed, instead of a text diff.
+// Entries are matched by their file name without the number.
+
+// Front-matter keys worth naming when they change
+const COMPARED_KEYS: &[&str] = &["severity", "status", "cvss", "review"];
+
+fn flatten<'a>(entries: &'a [Entry], flat: &mut Vec<&'a Entry>) {
+    for entry in entries {
+        if !entry.is_chapter() {
+            flat.push(entry);
+        }
+        flatten(&entry.children, flat);
+    }
+}
+
+fn label(prefix: char, entry: &Entry) -> String {
+    match entry.meta.get_str("severity") {
+        Some(severity) => format!("{prefix}{} {} ({severity})", entry.id, entry.title()),
+        None => format!("{prefix}{} {}", entry.id, entry.title()),
+    }
+}
+
+/// Changes between two versions of the same entry, empty if none
+fn entry_changes(prefix: char, old: &Entry, new: &Entry) -> Vec<String> {
+    let mut changes = Vec::new();
+    if old.id != new.id {
+        changes.push(format!("moved from {prefix}{}", old.id));
+    }
+    if old.title() != new.title() {
+        changes.push(format!("title \"{}\" -> \"{}\"", old.title(), new.title()));
+    }
+    for key in COMPARED_KEYS {
+        let (before, after) = (old.meta.get_str(key), new.meta.get_str(key));
+        if before != after {
+            changes.push(format!(
+                "{key} {} -> {}",
+                before.unwrap_or("-"),
+                after.unwrap_or("-")
+            ));
+        }
+    }
+    if old.body.trim() != new.body.trim() {
+        changes.push("content changed".to_string());
+    }
+    changes
+}
+
+fn diff_entries(kind: Kind, old: &Report, new: &Report) -> Vec<String> {
+    let prefix = kind.prefix();
+    let (mut old_entries, mut new_entries) = (Vec::new(), Vec::new());
+    flatten(old.entries_of(kind), &mut old_entries);
+    flatten(new.entries_of(kind), &mut new_entries);
+
+    let mut lines = Vec::new();
+    for entry in &new_entries {
+        match old_entries.iter().find(|old| old.name == entry.name) {
+            None => lines.push(format!("  + {}", label(prefix, entry))),
+            Some(old) => {
+                let changes = entry_changes(prefix, old, entry);
+                if !changes.is_empty() {
+                    lines.push(format!(
+                        "  ~ {}: {}",
+                        label(prefix, entry),
+                        changes.join(", ")
+                    ));
+                }
+            }
+        }
+    }
+    for entry in &old_entries {
+        if !new_entries.iter().any(|new| new.name == entry.name) {
+            lines.push(format!("  - {}", label(prefix, entry)));
+        }
+    }
+    lines
+}
+
+fn diff_metadata(old: &Report, new: &Report) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (key, value) in &new.metadata {
+        match old.metadata_value(key) {
+            None => lines.push(format!("  + {key}: {}", value.trim())),
+            Some(before) if before != value.trim() => {
+                lines.push(format!("  ~ {key}: {before} -> {}", value.trim()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, value) in &old.metadata {
+        if new.metadata_value(key).is_none() {
+            lines.push(format!("  - {key}: {}", value.trim()));
+        }
+    }
+    lines
+}
+
+/// Loads a report directory, or the report at a git revision of `report_path`
+/// into `scratch`
+fn load(version: &str, report_path: &Path, scratch: &Path) -> Result<Report, Box<dyn Error>> {
+    let dir = Path::new(version);
+    if workspace::is_report(dir) {
+        return Report::load(dir);
+    }
+    git::export(report_path, version, scratch)?;
+    Report::load(scratch)
+        .map_err(|e| format!("{version} doesn't contain a valid report: {e}").into())
+}
+
+/// Shows the sections, findings and metadata that changed between two report
+/// directories or two git revisions of the report
+pub fn diff(report_dir: Option<PathBuf>, old: String, new: String) -> Result<(), Box<dyn Error>> {
+    // Only needed for revisions, directories are compared as they are
+    let report_path = report_dir.unwrap_or_else(|| ".".into());
+
+    let scratch = env::temp_dir().join(format!("reportgen-diff-{}", process::id()));
+    let loaded = load(&old, &report_path, &scratch.join("old"))
+        .and_then(|old| Ok((old, load(&new, &report_path, &scratch.join("new"))?)));
+    let _ = remove_dir_all(&scratch);
+    let (old_report, new_report) = loaded.unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+
+    let mut different = false;
+    for (title, lines) in [
+        ("Metadata", diff_metadata(&old_report, &new_report)),
+        (
+            "Sections",
+            diff_entries(Kind::Section, &old_report, &new_report),
+        ),
+        (
+            "Findings",
+            diff_entries(Kind::Finding, &old_report, &new_report),
+        ),
+        (
+            "Appendices",
+            diff_entries(Kind::Appendix, &old_report, &new_report),
+        ),
+    ] {
+        if lines.is_empty() {
+            continue;
+        }
+        different = true;
+        println!("{title}");
+        for line in lines {
+            println!("{line}");
+        }
+    }
+
+    if !different {
+        println!("No differences between {old} and {new}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use std::{
+    error::Error,
+    fs::{metadata, write},
+    path::{Path, PathBuf},
+    process::exit,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Local;
+
+use crate::config::Config;
+use crate::qa;
+use crate::report::{self, Report};
+use crate::sla::{self, Breach};
+use crate::summary;
+use crate::toml::TableExt;
+use crate::workspace;
+
+// Short internal summary of the active engagements for weekly reviews: per
+// report the findings added in the last week, the findings per severity, the
+// report state and overdue remediations of retests, which are also posted to
+// `sla_webhook` of the config. Delivered reports are left out.
+
+const PERIOD: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+struct Engagement {
+    title: String,
+    path: PathBuf,
+    state: String,
+    // Non-zero finding counts per severity
+    severities: Vec<(String, usize)>,
+    // Titles and severities of the findings added within the period
+    new_findings: Vec<(String, String)>,
+    breaches: Vec<Breach>,
+    sla_webhook: Option<String>,
+}
+
+/// When a file was added, its modification time where creation isn't recorded
+fn added(path: &Path) -> Option<SystemTime> {
+    let metadata = metadata(path).ok()?;
+    metadata.created().or_else(|_| metadata.modified()).ok()
+}
+
+fn engagement(report_path: &Path, since: SystemTime) -> Result<Engagement, Box<dyn Error>> {
+    let report = Report::load(report_path)?;
+    let config = Config::load(report_path)?;
+
+    let severities = summary::severity_counts(&report.findings, &config.severities())
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    let new_findings = report
+        .findings
+        .iter()
+        .filter(|finding| added(&finding.path).is_some_and(|time| time >= since))
+        .map(|finding| {
+            (
+                finding.title(),
+                finding
+                    .meta
+                    .get_str("severity")
+                    .unwrap_or("unrated")
+                    .to_string(),
+            )
+        })
+        .collect();
+
+    Ok(Engagement {
+        title: report
+            .metadata_value("report_title")
+            .unwrap_or("-")
+            .to_string(),
+        path: report_path.to_path_buf(),
+        state: report
+            .metadata_value("state")
+            .unwrap_or(qa::STATES[0])
+            .to_string(),
+        severities,
+        new_findings,
+        breaches: sla::breaches(&report, &config, Local::now().date_naive())?,
+        sla_webhook: config.sla_webhook.clone(),
+    })
+}
+
+fn counts(engagement: &Engagement) -> String {
+    if engagement.severities.is_empty() {
+        return "none".to_string();
+    }
+    engagement
+        .severities
+        .iter()
+        .map(|(severity, count)| format!("{count} {severity}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn markdown(date: &str, engagements: &[Engagement]) -> String {
+    let mut digest = format!("# Weekly digest {date}\n");
+    if engagements.is_empty() {
+        digest.push_str("\nNo active engagements.\n");
+    }
+    for engagement in engagements {
+        digest.push_str(&format!(
+            "\n## {} ({})\n\n- State: {}\n- Findings: {}\n- New this week: {}\n",
+            engagement.title,
+            engagement.path.display(),
+            engagement.state,
+            counts(engagement),
+            engagement.new_findings.len()
+        ));
+        for (title, severity) in &engagement.new_findings {
+            digest.push_str(&format!("  - {title} ({severity})\n"));
+        }
+        if !engagement.breaches.is_empty() {
+            digest.push_str(&format!(
+                "- Overdue remediations: {}\n",
+                engagement.breaches.len()
+            ));
+            for breach in &engagement.breaches {
+                digest.push_str(&format!("  - {}\n", breach.line()));
+            }
+        }
+    }
+    digest
+}
+
+pub fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(date: &str, engagements: &[Engagement]) -> String {
+    let mut digest = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Weekly digest {date}</title></head>\n<body>\n<h1>Weekly digest {date}</h1>\n"
+    );
+    if engagements.is_empty() {
+        digest.push_str("<p>No active engagements.</p>\n");
+    }
+    for engagement in engagements {
+        digest.push_str(&format!(
+            "<h2>{} <small>{}</small></h2>\n<ul>\n<li>State: {}</li>\n<li>Findings: {}</li>\n<li>New this week: {}",
+            html_escape(&engagement.title),
+            html_escape(&engagement.path.display().to_string()),
+            html_escape(&engagement.state),
+            html_escape(&counts(engagement)),
+            engagement.new_findings.len()
+        ));
+        if !engagement.new_findings.is_empty() {
+            digest.push_str("\n<ul>\n");
+            for (title, severity) in &engagement.new_findings {
+                digest.push_str(&format!(
+                    "<li>{} ({})</li>\n",
+                    html_escape(title),
+                    html_escape(severity)
+                ));
+            }
+            digest.push_str("</ul>\n");
+        }
+        digest.push_str("</li>\n");
+        if !engagement.breaches.is_empty() {
+            digest.push_str(&format!(
+                "<li>Overdue remediations: {}\n<ul>\n",
+                engagement.breaches.len()
+            ));
+            for breach in &engagement.breaches {
+                digest.push_str(&format!("<li>{}</li>\n", html_escape(&breach.line())));
+            }
+            digest.push_str("</ul>\n</li>\n");
+        }
+        digest.push_str("</ul>\n");
+    }
+    digest.push_str("</body>\n</html>\n");
+    digest
+}
+
+/// Digest of the report, or of every active report of a workspace with `--workspace`
+pub fn digest(
+    dir: Option<PathBuf>,
+    whole_workspace: bool,
+    format: Option<String>,
+    output: Option<String>,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
+    let format = format.unwrap_or_else(|| "md".to_string());
+    if format != "md" && format != "html" {
+        eprintln!("ERROR: Unknown format \"{format}\", expected md or html");
+        exit(1);
+    }
+
+    let report_paths = match whole_workspace {
+        true => workspace::find_reports(&dir.unwrap_or_else(|| ".".into())),
+        false => vec![report::resolve_path(dir)],
+    };
+    let since = SystemTime::now() - PERIOD;
+    let mut engagements = Vec::new();
+    for report_path in report_paths {
+        match engagement(&report_path, since) {
+            Ok(engagement) if engagement.state == "delivered" => {}
+            Ok(engagement) => engagements.push(engagement),
+            Err(e) => eprintln!("WARNING: Skipping {}: {e}", report_path.display()),
+        }
+    }
+
+    for engagement in &engagements {
+        let (Some(webhook), false) = (&engagement.sla_webhook, engagement.breaches.is_empty())
+        else {
+            continue;
+        };
+        if let Err(e) = sla::notify(webhook, &engagement.title, &engagement.breaches, offline) {
+            eprintln!("WARNING: Failed to notify {webhook}: {e}");
+        }
+    }
+
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let digest = match format.as_str() {
+        "html" => html(&date, &engagements),
+        _ => markdown(&date, &engagements),
+    };
+    match output {
+        Some(output) => {
+            write(&output, digest)?;
+            println!("Digest written to \"{output}\"");
+        }
+        None => print!("{digest}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::front_matter;
+use crate::github;
+use crate::gitlab;
+use crate::jira;
+use crate::report::{self, Kind, Report, ORDER_KEY};
+use crate::review;
+
+/// Copies a finding, section or appendix with its front-matter as the next
+/// number, e.g. for the same issue in another component. The copy is named
+/// `name` or like the original and needs a new review and tickets.
+pub fn duplicate(
+    report_dir: Option<PathBuf>,
+    kind: Kind,
+    id: usize,
+    name: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let entries = report.entries_of(kind);
+    let Some(entry) = entries.iter().find(|entry| entry.id == id) else {
+        eprintln!(
+            "ERROR: No {}{id} in {}",
+            kind.prefix(),
+            report_path.display()
+        );
+        exit(1);
+    };
+    if entry.is_chapter() {
+        eprintln!(
+            "ERROR: {} is a chapter, duplicate its sections instead",
+            entry.path.display()
+        );
+        exit(1);
+    }
+
+    let mut content = read_to_string(&entry.path)?;
+    if entry.meta.get("review").is_some() {
+        content = front_matter::set(&content, "review", review::STATES[0]);
+    }
+    // The copy isn't ticketed or reviewed yet, push would skip it otherwise
+    for key in [
+        jira::FRONT_MATTER_KEY,
+        github::FRONT_MATTER_KEY,
+        gitlab::FRONT_MATTER_KEY,
+        "reviewer",
+    ] {
+        content = front_matter::remove(&content, key);
+    }
+    let number = report.next_id(kind);
+    // Entries ordered by the front-matter stay unnumbered, the copy comes next
+    let file_name = match (entry.is_ordered(), name) {
+        (false, name) => format!("{number}.{}.typ", name.unwrap_or(entry.name.clone())),
+        (true, Some(name)) => format!("{name}.typ"),
+        (true, None) => format!("{}-{number}.typ", entry.name),
+    };
+    if entry.is_ordered() {
+        content = front_matter::set_integer(&content, ORDER_KEY, number as i64);
+    }
+    let path = entry.path.with_file_name(file_name);
+    if path.exists() {
+        eprintln!("ERROR: {} already exists", path.display());
+        exit(1);
+    }
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.create(path.clone(), &content);
+    changes.apply(dry_run)?;
+    if !dry_run {
+        println!(
+            "Duplicated {} as \"{}\"",
+            entry.path.display(),
+            path.display()
+        );
+    }
+
+    Ok(())
+}
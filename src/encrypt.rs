@@ -0,0 +1,63 @@
+use std::{
+    error::Error,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+// Compiled PDFs are encrypted with qpdf, typst can't do that itself.
+// The owner password is random so that nobody can lift the restrictions.
+// Passwords never go on command lines, other users can list those: qpdf
+// reads them from stdin and compile --all hands them to its children in
+// REPORTGEN_PDF_PASSWORD.
+
+pub const PASSWORD_VAR: &str = "REPORTGEN_PDF_PASSWORD";
+
+/// Asks for the password of `what`, e.g. "archive", on the terminal without
+/// echoing it. New passwords are asked for twice, a typo would lock the file.
+pub fn prompt_password(what: &str, confirm: bool) -> Result<String, Box<dyn Error>> {
+    let password = rpassword::prompt_password(format!("Password of the {what}: "))?;
+    if password.is_empty() {
+        return Err(format!("The password of the {what} can't be empty").into());
+    }
+    if confirm
+        && rpassword::prompt_password(format!("Repeat the password of the {what}: "))? != password
+    {
+        return Err(format!("The passwords of the {what} don't match").into());
+    }
+    Ok(password)
+}
+
+fn random_password() -> Result<String, Box<dyn Error>> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| format!("No random numbers for the owner password: {e}"))?;
+    Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Encrypts `pdf` in place with AES-256, printing, copying and editing are not permitted
+pub fn encrypt_pdf(pdf: &str, password: &str) -> Result<(), Box<dyn Error>> {
+    // qpdf reads arguments one per line
+    if password.contains(['\r', '\n']) {
+        return Err("The PDF password can't contain line breaks".into());
+    }
+    let owner_password = random_password()?;
+    let mut child = Command::new("qpdf")
+        .args([pdf, "--replace-input", "@-"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            format!("Failed to execute qpdf ({e})\nEnsure you have 'qpdf' installed on your system")
+        })?;
+    let arguments = format!(
+        "--encrypt\n{password}\n{owner_password}\n256\n--print=none\n--extract=n\n--modify=none\n--\n"
+    );
+    let mut stdin = child.stdin.take().ok_or("Failed to open qpdf stdin")?;
+    let written = stdin.write_all(arguments.as_bytes());
+    drop(stdin);
+    let status = child.wait()?;
+    written?;
+    if !status.success() {
+        return Err(format!("qpdf failed to encrypt {pdf}").into());
+    }
+    Ok(())
+}
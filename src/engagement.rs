@@ -0,0 +1,92 @@
+use crate::consts::*;
+
+// Engagement types of new reports (`new --kind cloud`), choosing the
+// methodology boilerplate, the sections following it and the directories the
+// report starts with
+
+pub struct Engagement {
+    pub name: &'static str,
+    // Kind of test, the default report title
+    pub title: &'static str,
+    pub methodology: &'static str,
+    // Sections after the methodology by file name, replacing the example section
+    pub sections: &'static [(&'static str, &'static str)],
+    // Directories besides sections and findings
+    pub dirs: &'static [&'static str],
+}
+
+pub const ENGAGEMENTS: &[Engagement] = &[
+    Engagement {
+        name: "webapp",
+        title: "Web Application Penetration Test",
+        methodology: T_METHODOLOGY_WEBAPP,
+        sections: &[
+            ("application_overview", T_SECTION_APPLICATION_OVERVIEW),
+            ("authentication", T_SECTION_AUTHENTICATION),
+        ],
+        dirs: &["evidence"],
+    },
+    Engagement {
+        name: "internal",
+        title: "Internal Network Penetration Test",
+        methodology: T_METHODOLOGY_INTERNAL,
+        sections: &[
+            ("network_overview", T_SECTION_NETWORK_OVERVIEW),
+            ("active_directory", T_SECTION_ACTIVE_DIRECTORY),
+        ],
+        dirs: &["evidence", "appendices"],
+    },
+    Engagement {
+        name: "external",
+        title: "External Network Penetration Test",
+        methodology: T_METHODOLOGY_EXTERNAL,
+        sections: &[("attack_surface", T_SECTION_ATTACK_SURFACE)],
+        dirs: &["evidence", "appendices"],
+    },
+    Engagement {
+        name: "cloud",
+        title: "Cloud Security Assessment",
+        methodology: T_METHODOLOGY_CLOUD,
+        sections: &[
+            ("cloud_architecture", T_SECTION_CLOUD_ARCHITECTURE),
+            ("iam_review", T_SECTION_IAM_REVIEW),
+        ],
+        dirs: &["evidence", "appendices"],
+    },
+    Engagement {
+        name: "mobile",
+        title: "Mobile Application Penetration Test",
+        methodology: T_METHODOLOGY_MOBILE,
+        sections: &[
+            ("static_analysis", T_SECTION_STATIC_ANALYSIS),
+            ("dynamic_analysis", T_SECTION_DYNAMIC_ANALYSIS),
+        ],
+        dirs: &["evidence"],
+    },
+    Engagement {
+        name: "redteam",
+        title: "Red Team Engagement",
+        methodology: T_METHODOLOGY_REDTEAM,
+        sections: &[
+            ("attack_narrative", T_SECTION_ATTACK_NARRATIVE),
+            ("detection_response", T_SECTION_DETECTION_RESPONSE),
+        ],
+        dirs: &["evidence", "appendices"],
+    },
+];
+
+/// Engagement type by name, e.g. `webapp`
+pub fn find(name: &str) -> Option<&'static Engagement> {
+    ENGAGEMENTS
+        .iter()
+        .find(|engagement| engagement.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Names of the engagement types for messages, e.g. `webapp, internal`
+pub fn names() -> String {
+    ENGAGEMENTS
+        .iter()
+        .map(|engagement| engagement.name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
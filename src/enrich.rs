@@ -0,0 +1,119 @@
+use std::{env, error::Error, fs::read_to_string, path::PathBuf, process::exit, time::Duration};
+
+use serde_json::Value;
+
+use crate::changes::Changes;
+use crate::consts::*;
+use crate::http::Client;
+use crate::nvd;
+use crate::report::{self, Report};
+
+// NVD allows 5 requests per 30 seconds without an API key and 50 with one
+const DELAY_WITHOUT_KEY: Duration = Duration::from_secs(6);
+const DELAY_WITH_KEY: Duration = Duration::from_millis(600);
+
+fn fetch_cve(
+    client: &mut Client,
+    id: &str,
+    api_key: Option<&str>,
+) -> Result<Option<Value>, Box<dyn Error>> {
+    let headers: Vec<(&str, &str)> = api_key.map(|key| ("apiKey", key)).into_iter().collect();
+    let body = client.get(&format!("{NVD_API_URL}?cveId={id}"), &headers)?;
+
+    let response: Value = serde_json::from_str(&body)?;
+    Ok(response["vulnerabilities"]
+        .get(0)
+        .and_then(|v| v.get("cve"))
+        .cloned())
+}
+
+pub fn enrich(
+    report_dir: Option<PathBuf>,
+    nvd_dump: Option<PathBuf>,
+    dry_run: bool,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    // Collect every CVE referenced by the findings
+    let mut ids: Vec<String> = Vec::new();
+    for finding in &report.findings {
+        for id in nvd::finding_cve_ids(finding) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+
+    if ids.is_empty() {
+        println!("No CVE references found in findings");
+        return Ok(());
+    }
+
+    // Offline NVD dump (API 2.0 format) if provided
+    let dump = match nvd_dump {
+        Some(path) => {
+            let Ok(content) = read_to_string(&path) else {
+                eprintln!("ERROR: Failed to read NVD dump \"{}\"", path.display());
+                exit(1);
+            };
+            Some(serde_json::from_str::<Value>(&content)?)
+        }
+        None => None,
+    };
+
+    let api_key = env::var("NVD_API_KEY").ok();
+    let delay = if api_key.is_some() {
+        DELAY_WITH_KEY
+    } else {
+        DELAY_WITHOUT_KEY
+    };
+
+    let mut client = Client::new(offline)
+        .cache(report_path.join(STATE_DIR).join(HTTP_CACHE_DIR))
+        .rate_limit(delay);
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.create_dir(report_path.join(STATE_DIR).join(NVD_CACHE_DIR));
+
+    let mut enriched = 0;
+    for id in &ids {
+        let cve = match dump {
+            Some(ref dump) => dump["vulnerabilities"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.get("cve"))
+                .find(|cve| cve["id"] == id.as_str())
+                .cloned(),
+            // Offline only previously fetched CVEs are available
+            None => match fetch_cve(&mut client, id, api_key.as_deref()) {
+                Ok(cve) => cve,
+                Err(e) if offline => {
+                    eprintln!("WARNING: {e}");
+                    continue;
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        let Some(cve) = cve else {
+            eprintln!("WARNING: {id} not found in NVD data");
+            continue;
+        };
+
+        changes.write(
+            nvd::cache_path(&report_path, id),
+            &serde_json::to_string_pretty(&cve)?,
+        );
+        println!("Enriched {id}");
+        enriched += 1;
+    }
+
+    changes.apply(dry_run)?;
+
+    println!("Enriched {enriched} of {} CVE(s)", ids.len());
+
+    Ok(())
+}
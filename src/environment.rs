@@ -0,0 +1,57 @@
+use crate::report::Report;
+use crate::toml::TableExt;
+use crate::utils::typst_str;
+
+// Environments a finding was verified in, e.g. `environment = ["prod", "staging"]`
+// in its front-matter, shown as `{{ environment_matrix }}`
+
+pub const FRONT_MATTER_KEY: &str = "environment";
+
+/// Environments of all findings in order of first appearance
+fn environments(report: &Report) -> Vec<String> {
+    let mut environments: Vec<String> = Vec::new();
+    for finding in &report.findings {
+        for environment in finding.meta.get_str_list(FRONT_MATTER_KEY) {
+            if !environments.contains(&environment) {
+                environments.push(environment);
+            }
+        }
+    }
+    environments
+}
+
+/// Table of findings by environment, empty if no finding names an environment
+pub fn render_matrix(report: &Report) -> String {
+    let environments = environments(report);
+    if environments.is_empty() {
+        return String::new();
+    }
+
+    let mut header = vec!["[*Finding*]".to_string()];
+    header.extend(
+        environments
+            .iter()
+            .map(|e| format!("strong({})", typst_str(e))),
+    );
+    let mut matrix = format!(
+        "#pagebreak()\n= Environments\n#table(\n  columns: {},\n  {},\n",
+        environments.len() + 1,
+        header.join(", ")
+    );
+    for finding in &report.findings {
+        let verified = finding.meta.get_str_list(FRONT_MATTER_KEY);
+        let mut row = vec![typst_str(&finding.title())];
+        for environment in &environments {
+            let mark = if verified.contains(environment) {
+                "✓"
+            } else {
+                ""
+            };
+            row.push(typst_str(mark));
+        }
+        matrix.push_str(&format!("  {},\n", row.join(", ")));
+    }
+    matrix.push_str(")\n");
+
+    matrix
+}
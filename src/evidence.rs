@@ -0,0 +1,86 @@
+use crate::report::{Entry, Report};
+use crate::utils::{typst_escape, typst_str};
+use crate::xref::{self, IdScheme};
+
+// Evidence screenshots are the `image("...")` calls in findings, with the
+// caption of their `figure` if there is one. With `screenshot_gallery = true`
+// in the config they are collected in the `{{ screenshot_gallery }}` appendix.
+
+const GALLERY_COLUMNS: usize = 3;
+
+pub struct Screenshot {
+    pub path: String,
+    // Typst markup
+    pub caption: Option<String>,
+}
+
+/// Content of the brackets `s` starts with, nested brackets included
+fn bracketed(s: &str) -> Option<&str> {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&s[1..i]);
+                }
+            }
+            _ if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Screenshots of a finding in the order they appear
+pub fn screenshots(finding: &Entry) -> Vec<Screenshot> {
+    let body = &finding.body;
+    let starts: Vec<usize> = body.match_indices("image(\"").map(|(i, _)| i).collect();
+    starts
+        .iter()
+        .enumerate()
+        .filter_map(|(n, &start)| {
+            let path_start = start + "image(\"".len();
+            let path = &body[path_start..path_start + body[path_start..].find('"')?];
+            // A caption belongs to the image if it comes before the next one
+            let rest = &body[path_start..starts.get(n + 1).copied().unwrap_or(body.len())];
+            let caption = rest
+                .find("caption:")
+                .and_then(|i| bracketed(rest[i + "caption:".len()..].trim_start()))
+                .map(|caption| caption.trim().to_string());
+            Some(Screenshot {
+                path: path.to_string(),
+                caption,
+            })
+        })
+        .collect()
+}
+
+/// Thumbnail grid of the screenshots of every finding, empty without screenshots
+pub fn render_gallery(report: &Report, ids: &IdScheme) -> String {
+    let mut cells = Vec::new();
+    for finding in &report.findings {
+        for screenshot in screenshots(finding) {
+            let caption = screenshot.caption.unwrap_or_else(|| {
+                let name = screenshot.path.rsplit('/').next().unwrap_or_default();
+                typst_escape(name)
+            });
+            cells.push(format!(
+                "  block(breakable: false)[#image({}, width: 100%, height: 4cm, fit: \"contain\")\n    #text(8pt)[{caption} \\ #link(<{}>)[{} {}]]]",
+                typst_str(&screenshot.path),
+                xref::label(finding.id),
+                typst_escape(&ids.id(finding)),
+                xref::finding_title(finding)
+            ));
+        }
+    }
+    if cells.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        "#pagebreak()\n= Appendix: Screenshots\n#grid(\n  columns: {GALLERY_COLUMNS},\n  gutter: 12pt,\n{},\n)\n",
+        cells.join(",\n")
+    )
+}
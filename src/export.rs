@@ -0,0 +1,208 @@
+use std::{
+    error::Error,
+    fs::write,
+    io::Write,
+    path::PathBuf,
+    process::{exit, Command, Stdio},
+};
+
+use chrono::{Local, NaiveDate};
+
+use crate::config::Config;
+use crate::defectdojo;
+use crate::model;
+use crate::report::{self, Report};
+use crate::sarif;
+use crate::severity::Presentation;
+
+// Findings register for pasting into e-mails or spreadsheets during client
+// calls, put on the system clipboard by the platform's clipboard tool, or the
+// whole report as JSON of the versioned `model` for external tools, or the
+// findings for import into DefectDojo or as SARIF for code scanning.
+
+const FORMATS: &[&str] = &["tsv", "csv"];
+const COLUMNS: &[&str] = &[
+    "ID",
+    "Title",
+    "Severity",
+    "CVSS",
+    "Status",
+    "Affected hosts",
+];
+
+// Tried in order, the first one installed is used
+#[cfg(target_os = "macos")]
+const CLIPBOARD_TOOLS: &[&[&str]] = &[&["pbcopy"]];
+#[cfg(windows)]
+const CLIPBOARD_TOOLS: &[&[&str]] = &[&["clip"]];
+#[cfg(not(any(target_os = "macos", windows)))]
+const CLIPBOARD_TOOLS: &[&[&str]] = &[
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+fn rows(report: &model::Report, presentation: &Presentation) -> Vec<Vec<String>> {
+    report
+        .findings
+        .iter()
+        .map(|finding| {
+            vec![
+                finding.id.clone(),
+                finding.title.clone(),
+                finding
+                    .severity
+                    .as_deref()
+                    .map_or(String::new(), |s| presentation.label(s)),
+                finding.cvss.clone().unwrap_or_default(),
+                finding.status.clone().unwrap_or_default(),
+                finding.hosts.join(", "),
+            ]
+        })
+        .collect()
+}
+
+fn tsv(rows: &[Vec<String>]) -> String {
+    // Tabs and line breaks would split cells
+    let cell = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+    let mut table = COLUMNS.join("\t") + "\n";
+    for row in rows {
+        table.push_str(&row.iter().map(|s| cell(s)).collect::<Vec<_>>().join("\t"));
+        table.push('\n');
+    }
+    table
+}
+
+fn csv(rows: &[Vec<String>]) -> String {
+    let cell = |s: &str| match s.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    };
+    let mut table = COLUMNS.join(",") + "\n";
+    for row in rows {
+        table.push_str(&row.iter().map(|s| cell(s)).collect::<Vec<_>>().join(","));
+        table.push('\n');
+    }
+    table
+}
+
+fn copy_to_clipboard(content: &str) -> Result<(), String> {
+    let mut failed = None;
+    for tool in CLIPBOARD_TOOLS {
+        let Ok(mut child) = Command::new(tool[0])
+            .args(&tool[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        let mut stdin = child.stdin.take().expect("Failed to open clipboard stdin");
+        let written = stdin.write_all(content.as_bytes());
+        drop(stdin);
+        let status = child.wait().map_err(|e| e.to_string())?;
+        // wl-copy is installed on X11 desktops too but only works under Wayland
+        if written.is_err() || !status.success() {
+            failed = Some(format!("{} failed to set the clipboard", tool[0]));
+            continue;
+        }
+        return Ok(());
+    }
+    if let Some(e) = failed {
+        return Err(e);
+    }
+    let tools: Vec<&str> = CLIPBOARD_TOOLS.iter().map(|tool| tool[0]).collect();
+    Err(format!(
+        "No clipboard tool found, install one of {}",
+        tools.join(", ")
+    ))
+}
+
+pub fn export(
+    target: String,
+    report_dir: Option<PathBuf>,
+    format: Option<String>,
+    output: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    if target == "json" {
+        let report_path = report::resolve_path(report_dir);
+        let report = model::Report::from(&Report::load_or_exit(&report_path));
+        let json = report.to_json() + "\n";
+        match output {
+            Some(output) => {
+                write(&output, json)?;
+                println!("Report exported to \"{output}\"");
+            }
+            None => print!("{json}"),
+        }
+        return Ok(());
+    }
+    if target == "defectdojo" {
+        let report_path = report::resolve_path(report_dir);
+        let report = Report::load_or_exit(&report_path);
+        // Findings date from the report date, today for drafts without one
+        let date = report
+            .metadata_value("report_date")
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .unwrap_or(Local::now().date_naive())
+            .format("%Y-%m-%d")
+            .to_string();
+        let model = model::Report::from(&report);
+        let json =
+            serde_json::to_string_pretty(&defectdojo::export(&model, &date, &report_path))? + "\n";
+        match output {
+            Some(output) => {
+                write(&output, json)?;
+                println!("Findings exported to \"{output}\"");
+            }
+            None => print!("{json}"),
+        }
+        return Ok(());
+    }
+    if target == "sarif" {
+        let report_path = report::resolve_path(report_dir);
+        let report = model::Report::from(&Report::load_or_exit(&report_path));
+        let json = serde_json::to_string_pretty(&sarif::export(&report))? + "\n";
+        match output {
+            Some(output) => {
+                write(&output, json)?;
+                println!("Findings exported to \"{output}\"");
+            }
+            None => print!("{json}"),
+        }
+        return Ok(());
+    }
+    if target != "clipboard" {
+        eprintln!(
+            "ERROR: Unknown export target \"{target}\", expected clipboard, json, defectdojo or sarif"
+        );
+        exit(1);
+    }
+    let format = format.unwrap_or("tsv".to_string());
+    if !FORMATS.contains(&format.as_str()) {
+        eprintln!(
+            "ERROR: Unknown format \"{format}\", expected {}",
+            FORMATS.join(" or ")
+        );
+        exit(1);
+    }
+
+    let report_path = report::resolve_path(report_dir);
+    let report = model::Report::from(&Report::load_or_exit(&report_path));
+    let config = Config::load_or_exit(&report_path);
+
+    let rows = rows(&report, &config.severity_presentation());
+    let content = match format.as_str() {
+        "csv" => csv(&rows),
+        _ => tsv(&rows),
+    };
+    if let Err(e) = copy_to_clipboard(&content) {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+
+    println!("Copied {} finding(s) to the clipboard", rows.len());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::{path::PathBuf, process::Command};
+
+// Fonts the report needs, declared by `fonts` in the config and by the branding
+// profile. Typst silently falls back to a default font for missing ones, so
+// they are looked up in `typst fonts` before compiling. Font files bundled
+// with the report or firm template are found through `font_paths`.
+
+/// `--font-path` arguments of typst
+pub fn args(font_paths: &[PathBuf]) -> Vec<String> {
+    font_paths
+        .iter()
+        .flat_map(|path| ["--font-path".to_string(), path.display().to_string()])
+        .collect()
+}
+
+/// Required font families typst doesn't know, compared like typst does without case
+pub fn missing(
+    typst: &str,
+    font_paths: &[PathBuf],
+    required: &[String],
+) -> Result<Vec<String>, String> {
+    if required.is_empty() {
+        return Ok(Vec::new());
+    }
+    for path in font_paths {
+        if !path.is_dir() {
+            return Err(format!("Font directory {} doesn't exist", path.display()));
+        }
+    }
+
+    let output = Command::new(typst)
+        .arg("fonts")
+        .args(args(font_paths))
+        .output()
+        .map_err(|e| format!("Failed to list the fonts of typst: {e}"))?;
+    if !output.status.success() {
+        return Err("typst failed to list the available fonts".to_string());
+    }
+    let available: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|family| family.trim().to_lowercase())
+        .collect();
+
+    Ok(required
+        .iter()
+        .filter(|font| !available.contains(&font.to_lowercase()))
+        .cloned()
+        .collect())
+}
@@ -0,0 +1,386 @@
+use ::toml::Table;
+use serde::Deserialize;
+
+use crate::diagnostics::front_matter_key;
+use crate::schema::Key;
+use crate::toml;
+
+// Front-matter is an optional TOML block at the very top of a section or
+// finding file, enclosed between two `+++` lines:
+//
+//  +++
+//  attack = ["T1190"]
+//  +++
+//  = Finding: SQL Injection
+pub const DELIMITER: &str = "+++";
+
+// Known front-matter keys
+pub const KEYS: &[Key] = &[
+    Key {
+        name: "order",
+        kind: "integer",
+        description: "Position of a section, finding or appendix whose file name has no number, e.g. sql_injection.typ",
+    },
+    Key {
+        name: "attack",
+        kind: "string[]",
+        description: "MITRE ATT&CK technique IDs",
+    },
+    Key {
+        name: "cve",
+        kind: "string[]",
+        description: "CVE IDs enriched from NVD",
+    },
+    Key {
+        name: "severity",
+        kind: "string",
+        description: "Finding severity",
+    },
+    Key {
+        name: "cvss",
+        kind: "number",
+        description: "CVSS base score from 0.0 to 10.0",
+    },
+    Key {
+        name: "cwe",
+        kind: "string[]",
+        description: "CWE IDs (e.g. CWE-79)",
+    },
+    Key {
+        name: "owasp",
+        kind: "string[]",
+        description: "OWASP Top 10 categories (e.g. A03:2021-Injection)",
+    },
+    Key {
+        name: "status",
+        kind: "string",
+        description: "Finding status",
+    },
+    Key {
+        name: "environment",
+        kind: "string[]",
+        description: "Environments the finding was verified in (e.g. prod, staging)",
+    },
+    Key {
+        name: "hosts",
+        kind: "string[]",
+        description: "Hosts affected by the finding (e.g. 10.0.0.5, app.example.com)",
+    },
+    Key {
+        name: "locations",
+        kind: "string[]",
+        description: "Source code locations of the finding, e.g. src/login.py:42 (SARIF export)",
+    },
+    Key {
+        name: "assets",
+        kind: "string[]",
+        description: "IDs of the affected assets described in assets.toml",
+    },
+    Key {
+        name: "likelihood",
+        kind: "integer",
+        description:
+            "Likelihood of exploitation from 1 (very low) to 5 (very high) for the risk matrix",
+    },
+    Key {
+        name: "impact",
+        kind: "integer",
+        description: "Impact from 1 (very low) to 5 (very high) for the risk matrix",
+    },
+    Key {
+        name: "priority",
+        kind: "string",
+        description: "Remediation priority as a severity level, the severity by default",
+    },
+    Key {
+        name: "effort",
+        kind: "string",
+        description: "Remediation effort: low, medium or high",
+    },
+    Key {
+        name: "time_spent",
+        kind: "string",
+        description: "Time spent on the entry, e.g. 2h, 45m or 1h30m",
+    },
+    Key {
+        name: "phase",
+        kind: "string",
+        description: "Engagement phase the time spent counts for, e.g. reconnaissance",
+    },
+    Key {
+        name: "jira",
+        kind: "string",
+        description: "Key of the Jira issue push jira created for the finding, e.g. SEC-123",
+    },
+    Key {
+        name: "github_issue",
+        kind: "string",
+        description: "GitHub issue push github opened for the finding, e.g. acme/shop#42",
+    },
+    Key {
+        name: "gitlab_issue",
+        kind: "string",
+        description: "GitLab issue push gitlab opened for the finding, e.g. acme/shop#42",
+    },
+    Key {
+        name: "related",
+        kind: "integer[]",
+        description: "Numbers of related findings",
+    },
+    Key {
+        name: "chained_with",
+        kind: "integer[]",
+        description: "Numbers of findings this one can be chained with",
+    },
+    Key {
+        name: "duplicate_of",
+        kind: "integer[]",
+        description: "Numbers of findings this one duplicates",
+    },
+    Key {
+        name: "due",
+        kind: "string",
+        description: "Remediation due date (YYYY-MM-DD) checked while a retest is open",
+    },
+    Key {
+        name: "review",
+        kind: "string",
+        description: "Review state: unreviewed, reviewed or approved",
+    },
+    Key {
+        name: "reviewer",
+        kind: "string",
+        description: "Who reviewed or approved the section/finding",
+    },
+    Key {
+        name: "http",
+        kind: "table[]",
+        description: "HTTP request/response pairs shown as http_evidence",
+    },
+    Key {
+        name: "http.label",
+        kind: "string",
+        description: "Caption of an HTTP pair",
+    },
+    Key {
+        name: "http.request",
+        kind: "string",
+        description: "Raw HTTP request, headers and body",
+    },
+    Key {
+        name: "http.response",
+        kind: "string",
+        description: "Raw HTTP response, headers and body",
+    },
+    Key {
+        name: "http.redact",
+        kind: "boolean",
+        description:
+            "Whether the redact_headers values of an HTTP pair are replaced, true by default",
+    },
+];
+
+/// One value or a list of them, like `hosts = "a"` and `hosts = ["a", "b"]`
+#[derive(Deserialize)]
+#[allow(dead_code)]
+#[serde(untagged)]
+enum List<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+/// A string or a TOML date like `due = 2024-05-01`
+#[derive(Deserialize)]
+#[allow(dead_code)]
+#[serde(untagged)]
+enum Date {
+    String(String),
+    Date(::toml::value::Datetime),
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct HttpPair {
+    label: Option<String>,
+    request: Option<String>,
+    response: Option<String>,
+    redact: Option<bool>,
+}
+
+/// Types of the known keys of `KEYS`, deserializing fails on a value of the
+/// wrong type. Values are read through `Table`, this only validates.
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Schema {
+    order: Option<i64>,
+    attack: Option<List<String>>,
+    cve: Option<List<String>>,
+    severity: Option<String>,
+    cvss: Option<f64>,
+    cwe: Option<List<String>>,
+    owasp: Option<List<String>>,
+    status: Option<String>,
+    environment: Option<List<String>>,
+    hosts: Option<List<String>>,
+    locations: Option<List<String>>,
+    assets: Option<List<String>>,
+    likelihood: Option<i64>,
+    impact: Option<i64>,
+    priority: Option<String>,
+    effort: Option<String>,
+    time_spent: Option<String>,
+    phase: Option<String>,
+    jira: Option<String>,
+    github_issue: Option<String>,
+    gitlab_issue: Option<String>,
+    related: Option<List<i64>>,
+    chained_with: Option<List<i64>>,
+    duplicate_of: Option<List<i64>>,
+    due: Option<Date>,
+    review: Option<String>,
+    reviewer: Option<String>,
+    http: Option<Vec<HttpPair>>,
+}
+
+fn describe_kind(kind: &str) -> String {
+    match kind {
+        "string" => "a string".to_string(),
+        "integer" => "an integer".to_string(),
+        "number" => "a number".to_string(),
+        "boolean" => "true or false".to_string(),
+        "table[]" => "an array of tables".to_string(),
+        list => format!("a list of {}s", list.trim_end_matches("[]")),
+    }
+}
+
+/// Known key set on the 1-based `line` of a front-matter, `table.key` for
+/// keys of arrays of tables
+fn key_at(front_matter: &str, line: usize) -> Option<&'static Key> {
+    let mut table = None;
+    for text in front_matter.lines().take(line) {
+        if let Some(name) = text
+            .trim()
+            .strip_prefix("[[")
+            .and_then(|t| t.strip_suffix("]]"))
+        {
+            table = Some(name.trim());
+        }
+    }
+    let text = front_matter.lines().nth(line.checked_sub(1)?)?;
+    let name = match (text.trim().starts_with("[["), front_matter_key(text), table) {
+        (true, _, Some(table)) => table.to_string(),
+        (false, Some(key), Some(table)) => format!("{table}.{key}"),
+        (false, Some(key), None) => key.to_string(),
+        _ => return None,
+    };
+    KEYS.iter().find(|key| key.name == name)
+}
+
+/// Checks the types of the known keys of a front-matter block, the first
+/// wrong one is returned with its 1-based line in the block
+pub fn validate(front_matter: &str) -> Result<(), (usize, String)> {
+    let Err(e) = ::toml::from_str::<Schema>(front_matter) else {
+        return Ok(());
+    };
+    let line = e.span().map_or(0, |span| {
+        front_matter[..span.start.min(front_matter.len())]
+            .matches('\n')
+            .count()
+            + 1
+    });
+    let message = match key_at(front_matter, line) {
+        Some(key) => format!("{} has to be {}", key.name, describe_kind(key.kind)),
+        None => toml::located(front_matter, &e),
+    };
+    Err((line, message))
+}
+
+/// Splits file content into the raw front-matter block (if any) and the body
+pub fn split(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix(DELIMITER)
+        .and_then(|r| r.strip_prefix('\n').or_else(|| r.strip_prefix("\r\n")))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == DELIMITER {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    // No closing delimiter, treat everything as body
+    (None, content)
+}
+
+/// Parses and validates the front-matter of a file, returning it together
+/// with the remaining body
+pub fn parse(content: &str) -> Result<(Table, &str), String> {
+    match split(content) {
+        (Some(front_matter), body) => {
+            let table = toml::parse(front_matter)?;
+            validate(front_matter).map_err(|(line, message)| format!("line {line}: {message}"))?;
+            Ok((table, body))
+        }
+        (None, body) => Ok((Table::default(), body)),
+    }
+}
+
+/// Sets a string key in the front-matter of `content`, creating the block if needed.
+/// Other lines are kept as they are.
+pub fn set(content: &str, key: &str, value: &str) -> String {
+    set_line(content, key, format!("{key} = {}\n", toml::quote(value)))
+}
+
+/// Sets an integer key like `set` does for strings
+pub fn set_integer(content: &str, key: &str, value: i64) -> String {
+    set_line(content, key, format!("{key} = {value}\n"))
+}
+
+/// Content without the top-level `key` of its front-matter
+pub fn remove(content: &str, key: &str) -> String {
+    let (Some(front_matter), body) = split(content) else {
+        return content.to_string();
+    };
+    let mut lines = String::new();
+    let mut in_table = false;
+    for existing in front_matter.split_inclusive('\n') {
+        // Keys after a table header belong to the table
+        in_table |= existing.trim_start().starts_with('[');
+        let existing_key = existing.split_once('=').map(|(k, _)| k.trim());
+        if in_table || existing_key != Some(key) {
+            lines.push_str(existing);
+        }
+    }
+    format!("{DELIMITER}\n{lines}{DELIMITER}\n{body}")
+}
+
+fn set_line(content: &str, key: &str, line: String) -> String {
+    let (front_matter, body) = match split(content) {
+        (Some(front_matter), body) => (front_matter, body),
+        (None, body) => ("", body),
+    };
+
+    let mut replaced = false;
+    let mut lines = String::new();
+    for existing in front_matter.split_inclusive('\n') {
+        let existing_key = existing.split_once('=').map(|(k, _)| k.trim());
+        if !replaced && existing_key == Some(key) {
+            lines.push_str(&line);
+            replaced = true;
+        } else {
+            lines.push_str(existing);
+            if !existing.ends_with('\n') {
+                lines.push('\n');
+            }
+        }
+    }
+    if !replaced {
+        lines.push_str(&line);
+    }
+
+    format!("{DELIMITER}\n{lines}{DELIMITER}\n{body}")
+}
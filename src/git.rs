@@ -0,0 +1,108 @@
+use std::{
+    error::Error,
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::report;
+
+// Reports kept in a git repository (`new --git`): snapshots of the current
+// state, the commit a PDF was compiled from (`compile --tag`) and earlier
+// versions to compare against (`diff`)
+
+fn git(report_path: &Path, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(report_path)
+        .output()
+        .map_err(|e| {
+            format!("Failed to execute git ({e})\nEnsure you have 'git' installed on your system")
+        })?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn init(report_path: &Path) -> Result<(), Box<dyn Error>> {
+    git(report_path, &["init", "--quiet"])?;
+    Ok(())
+}
+
+/// Extracts the report directory as of `revision` to `dir`
+pub fn export(report_path: &Path, revision: &str, dir: &Path) -> Result<(), Box<dyn Error>> {
+    // Path of the report inside the repository, empty at its root
+    let prefix = git(report_path, &["rev-parse", "--show-prefix"])?;
+    // Run in a subdirectory archive would only include that part of the tree
+    let root = git(report_path, &["rev-parse", "--show-toplevel"])?;
+    create_dir_all(dir)?;
+
+    let mut archive = Command::new("git")
+        .args(["archive", "--format=tar", &format!("{revision}:{prefix}")])
+        .current_dir(root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let extracted = Command::new("tar")
+        .arg("-x")
+        .arg("-C")
+        .arg(dir)
+        .stdin(archive.stdout.take().ok_or("git archive has no output")?)
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| {
+            format!("Failed to execute tar ({e})\nEnsure you have 'tar' installed on your system")
+        })?;
+    let output = archive.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "{revision} is neither a report directory nor a git revision of the report: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    if !extracted.success() {
+        return Err(format!("Failed to extract {revision} ({extracted})").into());
+    }
+    Ok(())
+}
+
+/// Short hash of the checked out commit, marked `-dirty` with uncommitted changes
+pub fn commit_label(report_path: &Path) -> Result<String, Box<dyn Error>> {
+    let hash = git(report_path, &["rev-parse", "--short", "HEAD"])
+        .map_err(|e| format!("No commit to tag the report with, use snapshot first ({e})"))?;
+    let status = git(report_path, &["status", "--porcelain", "--", "."])?;
+    Ok(if status.is_empty() {
+        hash
+    } else {
+        format!("{hash}-dirty")
+    })
+}
+
+/// Commits the current state of the report
+pub fn snapshot(report_dir: Option<PathBuf>, message: String) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+
+    git(&report_path, &["add", "--all", "--", "."])?;
+    if git(&report_path, &["status", "--porcelain", "--", "."])?.is_empty() {
+        println!("Nothing changed since the last snapshot");
+        return Ok(());
+    }
+    git(
+        &report_path,
+        &["commit", "--quiet", "-m", &message, "--", "."],
+    )?;
+
+    println!(
+        "Snapshot {} created",
+        git(&report_path, &["rev-parse", "--short", "HEAD"])?
+    );
+
+    Ok(())
+}
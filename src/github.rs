@@ -0,0 +1,95 @@
+use std::{env, error::Error, path::PathBuf, process::exit};
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::http::Client;
+use crate::model::{self, Finding};
+use crate::push;
+use crate::report::{self, Report};
+
+// GitHub issues of findings, created through the REST API with a token in
+// GITHUB_TOKEN that may write issues of the repository. Issues are labeled
+// `security` and with the severity, e.g. `severity: high`. The stored key is
+// the repository and issue number, e.g. `acme/shop#42`.
+
+pub const FRONT_MATTER_KEY: &str = "github_issue";
+
+const API_URL: &str = "https://api.github.com";
+
+fn labels(finding: &Finding) -> Vec<String> {
+    let mut labels = vec!["security".to_string()];
+    if let Some(ref severity) = finding.severity {
+        labels.push(format!("severity: {}", severity.to_lowercase()));
+    }
+    labels
+}
+
+/// Creates a GitHub issue per open finding without one in `repo` or `github_repo`
+pub fn push(
+    report_dir: Option<PathBuf>,
+    repo: Option<String>,
+    dry_run: bool,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    let Some(repo) = repo.or(config.github_repo.clone()) else {
+        eprintln!("ERROR: No GitHub repository, pass --repo or set github_repo in the config");
+        exit(1);
+    };
+    if repo.split('/').count() != 2 {
+        eprintln!("ERROR: Invalid GitHub repository \"{repo}\", expected owner/name");
+        exit(1);
+    }
+
+    let findings = model::Report::from(&report);
+    let pending = push::pending(&report, &findings, FRONT_MATTER_KEY);
+    if pending.is_empty() {
+        println!("No findings to push, all open ones have a GitHub issue");
+        return Ok(());
+    }
+    if dry_run {
+        for (_, finding) in &pending {
+            println!(
+                "Would open an issue in {repo} for {} {} (labels {})",
+                finding.id,
+                finding.title,
+                labels(finding).join(", ")
+            );
+        }
+        return Ok(());
+    }
+    let Ok(token) = env::var("GITHUB_TOKEN") else {
+        eprintln!("ERROR: No GitHub token, set GITHUB_TOKEN");
+        exit(1);
+    };
+
+    let endpoint = format!("{API_URL}/repos/{repo}/issues");
+    let authorization = format!("Bearer {token}");
+    let headers = [
+        ("Authorization", authorization.as_str()),
+        ("Accept", "application/vnd.github+json"),
+    ];
+    let mut client = Client::new(offline);
+    push::create_tickets(
+        &report_path,
+        &pending,
+        FRONT_MATTER_KEY,
+        "GitHub",
+        |finding| {
+            let body = json!({
+                "title": finding.title,
+                "body": push::markdown(finding, &findings),
+                "labels": labels(finding),
+            });
+            let response: Value =
+                serde_json::from_str(&client.post_json(&endpoint, &headers, &body.to_string())?)?;
+            let number = response["number"]
+                .as_u64()
+                .ok_or("No issue number in the response")?;
+            Ok(format!("{repo}#{number}"))
+        },
+    )
+}
@@ -0,0 +1,97 @@
+use std::{env, error::Error, path::PathBuf, process::exit};
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::http::Client;
+use crate::model::{self, Finding};
+use crate::push;
+use crate::report::{self, Report};
+
+// GitLab issues of findings, created through the REST API of gitlab.com or
+// the instance at `gitlab_url` of the config with a token in GITLAB_TOKEN.
+// Issues are labeled `security` and with the scoped severity label, e.g.
+// `severity::high`. The stored key is the project and issue number, e.g.
+// `acme/shop#42`.
+
+pub const FRONT_MATTER_KEY: &str = "gitlab_issue";
+
+const DEFAULT_URL: &str = "https://gitlab.com";
+
+fn labels(finding: &Finding) -> Vec<String> {
+    let mut labels = vec!["security".to_string()];
+    if let Some(ref severity) = finding.severity {
+        labels.push(format!("severity::{}", severity.to_lowercase()));
+    }
+    labels
+}
+
+/// Creates a GitLab issue per open finding without one in `repo` or `gitlab_repo`
+pub fn push(
+    report_dir: Option<PathBuf>,
+    repo: Option<String>,
+    dry_run: bool,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    let Some(repo) = repo.or(config.gitlab_repo.clone()) else {
+        eprintln!("ERROR: No GitLab project, pass --repo or set gitlab_repo in the config");
+        exit(1);
+    };
+
+    let findings = model::Report::from(&report);
+    let pending = push::pending(&report, &findings, FRONT_MATTER_KEY);
+    if pending.is_empty() {
+        println!("No findings to push, all open ones have a GitLab issue");
+        return Ok(());
+    }
+    if dry_run {
+        for (_, finding) in &pending {
+            println!(
+                "Would open an issue in {repo} for {} {} (labels {})",
+                finding.id,
+                finding.title,
+                labels(finding).join(", ")
+            );
+        }
+        return Ok(());
+    }
+    let Ok(token) = env::var("GITLAB_TOKEN") else {
+        eprintln!("ERROR: No GitLab token, set GITLAB_TOKEN");
+        exit(1);
+    };
+
+    // Projects are addressed by their URL-encoded path
+    let endpoint = format!(
+        "{}/api/v4/projects/{}/issues",
+        config
+            .gitlab_url
+            .as_deref()
+            .unwrap_or(DEFAULT_URL)
+            .trim_end_matches('/'),
+        repo.replace('/', "%2F")
+    );
+    let headers = [("PRIVATE-TOKEN", token.as_str())];
+    let mut client = Client::new(offline);
+    push::create_tickets(
+        &report_path,
+        &pending,
+        FRONT_MATTER_KEY,
+        "GitLab",
+        |finding| {
+            let body = json!({
+                "title": finding.title,
+                "description": push::markdown(finding, &findings),
+                "labels": labels(finding).join(","),
+            });
+            let response: Value =
+                serde_json::from_str(&client.post_json(&endpoint, &headers, &body.to_string())?)?;
+            let number = response["iid"]
+                .as_u64()
+                .ok_or("No issue number in the response")?;
+            Ok(format!("{repo}#{number}"))
+        },
+    )
+}
@@ -0,0 +1,386 @@
+use std::{
+    env,
+    error::Error,
+    fs::write,
+    io::{BufRead, BufReader, Read},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc::{channel, Receiver, Sender, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+use eframe::egui;
+
+use crate::config::Config;
+use crate::desktop;
+use crate::health;
+use crate::report::Report;
+use crate::toml::TableExt;
+use crate::workspace;
+
+struct FindingRow {
+    id: usize,
+    path: PathBuf,
+    title: String,
+    // Severity as named and colored in the deliverable
+    severity: String,
+    color: egui::Color32,
+}
+
+#[derive(Default)]
+struct App {
+    // Project browser
+    reports: Vec<PathBuf>,
+    path_input: String,
+    report_path: Option<PathBuf>,
+
+    metadata: Vec<(String, String)>,
+    findings: Vec<FindingRow>,
+    // Health dashboard as label/value rows
+    health: Vec<(String, String)>,
+
+    status: String,
+    log: Vec<String>,
+    compile_log: Option<Receiver<String>>,
+}
+
+/// `#rrggbb` color of the severity presentation
+fn hex_color(hex: &str) -> egui::Color32 {
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .unwrap_or(128)
+    };
+    egui::Color32::from_rgb(channel(1), channel(3), channel(5))
+}
+
+// Forwards every line of a child process stream to the log
+fn forward_lines(stream: impl Read + Send + 'static, sender: Sender<String>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+impl App {
+    fn new(report_dir: Option<PathBuf>) -> Self {
+        let cwd = env::current_dir().unwrap_or_else(|_| ".".into());
+        // Recently opened reports first, then the ones in the working directory
+        let mut reports = desktop::recent_reports();
+        for report in workspace::find_reports(&cwd) {
+            let canonical = report.canonicalize().unwrap_or_else(|_| report.clone());
+            if !reports.contains(&canonical) {
+                reports.push(report);
+            }
+        }
+        let mut app = Self {
+            reports,
+            path_input: cwd.display().to_string(),
+            ..Default::default()
+        };
+        if let Some(report_dir) = report_dir {
+            app.open(report_dir);
+        }
+        app
+    }
+
+    fn open(&mut self, report_path: PathBuf) {
+        match Report::load(&report_path) {
+            Ok(report) => {
+                self.metadata = report.metadata.clone();
+                let presentation = Config::load(&report_path)
+                    .unwrap_or_default()
+                    .severity_presentation();
+                self.findings = report
+                    .findings
+                    .iter()
+                    .map(|f| {
+                        let severity = f.meta.get_str("severity").unwrap_or("-");
+                        FindingRow {
+                            id: f.id,
+                            path: f.path.clone(),
+                            title: f.title(),
+                            severity: presentation.label(severity),
+                            color: hex_color(presentation.color(severity)),
+                        }
+                    })
+                    .collect();
+                self.health = match health::health(&report_path, &report) {
+                    Ok(health) => health.lines(),
+                    Err(e) => vec![("ERROR".to_string(), e.to_string())],
+                };
+                self.status = format!("Opened {}", report_path.display());
+                desktop::remember(&report_path);
+                if !self.reports.contains(&report_path) {
+                    self.reports.push(report_path.clone());
+                }
+                self.report_path = Some(report_path);
+            }
+            Err(e) => self.status = format!("ERROR: {e}"),
+        }
+    }
+
+    fn browse(&mut self) {
+        match desktop::pick_folder("Open report") {
+            Ok(Some(report_path)) => {
+                self.path_input = report_path.display().to_string();
+                self.open(report_path);
+            }
+            Ok(None) => {}
+            Err(e) => self.status = format!("ERROR: No folder picker available ({e})"),
+        }
+    }
+
+    // Findings are moved to the trash of the OS so they can be restored from there
+    fn trash_finding(&mut self, path: PathBuf) {
+        let Some(report_path) = self.report_path.clone() else {
+            return;
+        };
+        match desktop::move_to_trash(&path) {
+            Ok(()) => {
+                self.open(report_path);
+                self.status = format!("Moved {} to the trash", path.display());
+            }
+            Err(e) => self.status = format!("ERROR: {e}"),
+        }
+    }
+
+    fn save_metadata(&mut self) {
+        let Some(ref report_path) = self.report_path else {
+            return;
+        };
+        let content: String = self
+            .metadata
+            .iter()
+            .map(|(key, value)| format!("{key}:{value}\n"))
+            .collect();
+        self.status = match write(report_path.join("metadata.typ"), content) {
+            Ok(()) => "Metadata saved".to_string(),
+            Err(e) => format!("ERROR: {e}"),
+        };
+    }
+
+    // Runs the compile subcommand in a child process, streaming its output to the log
+    fn compile(&mut self) {
+        let Some(ref report_path) = self.report_path else {
+            return;
+        };
+
+        let child = env::current_exe().and_then(|exe| {
+            Command::new(exe)
+                .arg("compile")
+                .arg(report_path)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+        });
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                self.status = format!("ERROR: {e}");
+                return;
+            }
+        };
+
+        let (sender, receiver) = channel();
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, sender.clone());
+        }
+        thread::spawn(move || {
+            let result = match child.wait() {
+                Ok(status) if status.success() => "Compilation finished".to_string(),
+                Ok(status) => format!("Compilation failed ({status})"),
+                Err(e) => format!("Compilation failed ({e})"),
+            };
+            let _ = sender.send(result);
+        });
+
+        self.log.clear();
+        self.status = "Compiling...".to_string();
+        self.compile_log = Some(receiver);
+    }
+
+    fn poll_compile_log(&mut self, ctx: &egui::Context) {
+        let Some(ref receiver) = self.compile_log else {
+            return;
+        };
+
+        let mut finished = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(line) => self.log.push(line),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        if finished {
+            self.status = self.log.last().cloned().unwrap_or_default();
+            self.compile_log = None;
+        } else {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_compile_log(ctx);
+
+        egui::SidePanel::left("projects")
+            .resizable(true)
+            .default_width(240.0)
+            .show(ctx, |ui| {
+                ui.heading("Reports");
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.path_input);
+                    if ui.button("Open").clicked() {
+                        self.open(PathBuf::from(&self.path_input));
+                    }
+                    if ui.button("Browse…").clicked() {
+                        self.browse();
+                    }
+                });
+                ui.separator();
+                for report in self.reports.clone() {
+                    let selected = self.report_path.as_ref() == Some(&report);
+                    let name = report
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| report.display().to_string());
+                    if ui.selectable_label(selected, name).clicked() {
+                        self.open(report);
+                    }
+                }
+            });
+
+        egui::TopBottomPanel::bottom("log")
+            .resizable(true)
+            .min_height(140.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let can_compile = self.report_path.is_some() && self.compile_log.is_none();
+                    if ui
+                        .add_enabled(can_compile, egui::Button::new("Compile"))
+                        .clicked()
+                    {
+                        self.compile();
+                    }
+                    if ui
+                        .add_enabled(self.report_path.is_some(), egui::Button::new("Show folder"))
+                        .clicked()
+                    {
+                        if let Some(ref report_path) = self.report_path {
+                            if let Err(e) = desktop::open_path(report_path) {
+                                self.status = format!("ERROR: {e}");
+                            }
+                        }
+                    }
+                    ui.label(&self.status);
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        for line in &self.log {
+                            ui.monospace(line);
+                        }
+                    });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.report_path.is_none() {
+                ui.label("Select or open a report");
+                return;
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.heading("Health");
+                egui::Grid::new("health")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (label, value) in &self.health {
+                            ui.label(label.as_str());
+                            ui.label(value.as_str());
+                            ui.end_row();
+                        }
+                    });
+                if ui.button("Refresh").clicked() {
+                    if let Some(report_path) = self.report_path.clone() {
+                        self.open(report_path);
+                    }
+                }
+
+                ui.separator();
+                ui.heading("Metadata");
+                egui::Grid::new("metadata")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in &mut self.metadata {
+                            ui.label(key.as_str());
+                            ui.text_edit_singleline(value);
+                            ui.end_row();
+                        }
+                    });
+                if ui.button("Save metadata").clicked() {
+                    self.save_metadata();
+                }
+
+                ui.separator();
+                ui.heading("Findings");
+                let mut trashed = None;
+                for finding in &self.findings {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button("🗑")
+                            .on_hover_text("Move to trash")
+                            .clicked()
+                        {
+                            trashed = Some(finding.path.clone());
+                        }
+                        ui.label(format!("{}.", finding.id));
+                        ui.label(
+                            egui::RichText::new(format!(" {} ", finding.severity.to_uppercase()))
+                                .color(egui::Color32::WHITE)
+                                .background_color(finding.color)
+                                .strong(),
+                        );
+                        ui.label(&finding.title);
+                    });
+                }
+                if let Some(path) = trashed {
+                    self.trash_finding(path);
+                }
+            });
+        });
+    }
+}
+
+pub fn gui(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([1000.0, 700.0]),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Report Generator",
+        options,
+        Box::new(move |_cc| Box::new(App::new(report_dir))),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
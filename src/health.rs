@@ -0,0 +1,114 @@
+use std::{error::Error, path::Path};
+
+use crate::check;
+use crate::config::Config;
+use crate::consts::PLACEHOLDER_TEXT;
+use crate::diagnostics::Level;
+use crate::qa;
+use crate::report::Report;
+use crate::review;
+use crate::summary;
+
+// At-a-glance readiness of a report for QA, shown by the TUI and GUI dashboards
+
+pub struct Health {
+    pub errors: usize,
+    pub warnings: usize,
+    // Leftover template text like TODO or CHANGE ME
+    pub placeholders: usize,
+    // Findings per severity in the order of the scheme, unrated ones last
+    pub severities: Vec<(String, usize)>,
+    // Sections and findings per review state
+    pub reviews: Vec<(&'static str, usize)>,
+    // Passed and total items of the QA checklist
+    pub qa: (usize, usize),
+}
+
+impl Health {
+    /// No errors and no leftover placeholders, reviews are up to QA
+    pub fn is_ready(&self) -> bool {
+        self.errors == 0 && self.placeholders == 0
+    }
+
+    /// One `label: value` line per metric
+    pub fn lines(&self) -> Vec<(String, String)> {
+        let join = |counts: Vec<String>| counts.join(", ");
+        vec![
+            (
+                "Ready for QA".to_string(),
+                if self.is_ready() { "yes" } else { "no" }.to_string(),
+            ),
+            (
+                "Check".to_string(),
+                format!("{} error(s), {} warning(s)", self.errors, self.warnings),
+            ),
+            ("TODOs".to_string(), self.placeholders.to_string()),
+            (
+                "Findings".to_string(),
+                join(
+                    self.severities
+                        .iter()
+                        .map(|(severity, count)| format!("{count} {severity}"))
+                        .collect(),
+                ),
+            ),
+            (
+                "Review".to_string(),
+                join(
+                    self.reviews
+                        .iter()
+                        .map(|(state, count)| format!("{count} {state}"))
+                        .collect(),
+                ),
+            ),
+            (
+                "QA checklist".to_string(),
+                format!("{}/{} passed", self.qa.0, self.qa.1),
+            ),
+        ]
+    }
+}
+
+pub fn health(report_path: &Path, report: &Report) -> Result<Health, Box<dyn Error>> {
+    let diagnostics = check::check_report(report_path)?;
+    let count = |level| diagnostics.iter().filter(|d| d.level == level).count();
+
+    let entries: Vec<_> = report.entries().filter(|e| !e.is_chapter()).collect();
+    let placeholders = entries
+        .iter()
+        .map(|entry| {
+            PLACEHOLDER_TEXT
+                .iter()
+                .map(|p| entry.body.matches(p).count())
+                .sum::<usize>()
+        })
+        .sum();
+
+    let config = Config::load(report_path).unwrap_or_default();
+    let severities = summary::severity_counts(&report.findings, &config.severities());
+    let checklist = config.qa_checklist();
+    let qa = (
+        checklist.len() - qa::pending(report, &checklist).len(),
+        checklist.len(),
+    );
+
+    let reviews = review::STATES
+        .iter()
+        .map(|state| {
+            let count = entries
+                .iter()
+                .filter(|e| review::state(e) == *state)
+                .count();
+            (*state, count)
+        })
+        .collect();
+
+    Ok(Health {
+        errors: count(Level::Error),
+        warnings: count(Level::Warning),
+        placeholders,
+        severities,
+        reviews,
+        qa,
+    })
+}
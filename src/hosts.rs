@@ -0,0 +1,86 @@
+use crate::report::{Entry, Report};
+use crate::toml::TableExt;
+use crate::utils::{typst_escape, typst_str};
+use crate::xref::{self, IdScheme};
+
+// Hosts affected by a finding, e.g. `hosts = ["10.0.0.5", "app.example.com"]`
+// in its front-matter, shown as `{{ affected_hosts }}`. Lists longer than the
+// appendix threshold are moved to `{{ host_appendix }}`, the finding only
+// names their count.
+
+pub const FRONT_MATTER_KEY: &str = "hosts";
+pub const DEFAULT_COLUMNS: usize = 4;
+pub const DEFAULT_APPENDIX_THRESHOLD: usize = 50;
+
+pub struct Layout {
+    pub columns: usize,
+    // Most hosts listed in the finding itself
+    pub appendix_threshold: usize,
+}
+
+fn appendix_label(finding: &Entry) -> String {
+    format!("{}-hosts", xref::label(finding.id))
+}
+
+/// Table of hosts filled row by row, its caption is repeated on every page it spans
+fn table(caption: &str, hosts: &[String], columns: usize) -> String {
+    let columns = columns.clamp(1, hosts.len().max(1));
+    let mut table = format!(
+        "#table(\n  columns: {columns},\n  table.header(table.cell(colspan: {columns})[*{caption}*]),\n"
+    );
+    for row in hosts.chunks(columns) {
+        let cells: Vec<String> = row.iter().map(|host| typst_str(host)).collect();
+        table.push_str(&format!("  {},\n", cells.join(", ")));
+    }
+    table.push_str(")\n");
+    table
+}
+
+/// `{{ affected_hosts }}` of a finding, empty if it names no hosts
+pub fn render(finding: &Entry, layout: &Layout) -> String {
+    let hosts = finding.meta.get_str_list(FRONT_MATTER_KEY);
+    if hosts.is_empty() {
+        return String::new();
+    }
+    if hosts.len() > layout.appendix_threshold {
+        return format!(
+            "{} affected hosts, listed in #link(<{}>)[the appendix].\n",
+            hosts.len(),
+            appendix_label(finding)
+        );
+    }
+    table(
+        &format!("Affected hosts ({})", hosts.len()),
+        &hosts,
+        layout.columns,
+    )
+}
+
+/// Host lists too long for their findings, empty if there are none
+pub fn render_appendix(report: &Report, ids: &IdScheme, layout: &Layout) -> String {
+    let long: Vec<(&Entry, Vec<String>)> = report
+        .findings
+        .iter()
+        .map(|finding| (finding, finding.meta.get_str_list(FRONT_MATTER_KEY)))
+        .filter(|(_, hosts)| hosts.len() > layout.appendix_threshold)
+        .collect();
+    if long.is_empty() {
+        return String::new();
+    }
+
+    let mut appendix = String::from("#pagebreak()\n= Appendix: Affected Hosts\n");
+    for (finding, hosts) in long {
+        appendix.push_str(&format!(
+            "== {} <{}>\n",
+            typst_escape(&ids.title(finding)),
+            appendix_label(finding)
+        ));
+        appendix.push_str(&table(
+            &format!("Affected hosts ({})", hosts.len()),
+            &hosts,
+            layout.columns,
+        ));
+    }
+
+    appendix
+}
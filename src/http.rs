@@ -0,0 +1,187 @@
+use std::{
+    error::Error,
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use crate::sha256;
+
+// Every network request of the enrichment commands goes through `Client`.
+// Responses are cached per URL, requests to the same service are spaced out
+// and `--offline` guarantees that no request is sent at all. URLs, headers
+// and bodies reach curl as a config on stdin, they may hold tokens and
+// command lines are visible to other users.
+
+/// `option = "value"` line of a curl config
+fn config_line(option: &str, value: &str) -> String {
+    let value = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("{option} = \"{value}\"\n")
+}
+
+/// Runs curl with `args` and the curl config `config`
+fn curl(args: &[&str], config: &str) -> Result<Output, Box<dyn Error>> {
+    let mut child = Command::new("curl")
+        .args(args)
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            format!("Failed to execute curl ({e})\nEnsure you have 'curl' installed on your system")
+        })?;
+    let mut stdin = child.stdin.take().ok_or("Failed to open curl stdin")?;
+    let written = stdin.write_all(config.as_bytes());
+    drop(stdin);
+    let output = child.wait_with_output()?;
+    written?;
+    Ok(output)
+}
+
+fn headers_config(headers: &[(&str, &str)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| config_line("header", &format!("{name}: {value}")))
+        .collect()
+}
+
+pub struct Client {
+    offline: bool,
+    // Response cache, e.g. `<report>/.reportgenerator/http`
+    cache_dir: Option<PathBuf>,
+    // Minimum time between two requests
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(offline: bool) -> Self {
+        Self {
+            offline,
+            cache_dir: None,
+            interval: Duration::ZERO,
+            last_request: None,
+        }
+    }
+
+    pub fn cache(mut self, dir: PathBuf) -> Self {
+        self.cache_dir = Some(dir);
+        self
+    }
+
+    pub fn rate_limit(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Waits until the interval since the last request has passed
+    fn wait_turn(&mut self) {
+        if let Some(last_request) = self.last_request {
+            sleep(self.interval.saturating_sub(last_request.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+    }
+
+    fn cache_path(&self, url: &str) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        Some(dir.join(sha256::hex_digest(url.as_bytes())))
+    }
+
+    /// GET `url`, answered from the cache if possible. Headers are not part of the cache key.
+    pub fn get(&mut self, url: &str, headers: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+        let cache_path = self.cache_path(url);
+        if let Some(cached) = cache_path.as_ref().and_then(|p| read_to_string(p).ok()) {
+            return Ok(cached);
+        }
+        if self.offline {
+            return Err(format!("Offline mode, not requesting {url}").into());
+        }
+
+        self.wait_turn();
+        let config = config_line("url", url) + &headers_config(headers);
+        let output = curl(&["-s", "-f"], &config)?;
+        if !output.status.success() {
+            return Err(format!("Request to {url} failed").into());
+        }
+        let body = String::from_utf8_lossy(&output.stdout).to_string();
+
+        if let Some(cache_path) = cache_path {
+            if let Some(dir) = cache_path.parent() {
+                create_dir_all(dir)?;
+            }
+            write(cache_path, &body)?;
+        }
+
+        Ok(body)
+    }
+
+    /// POST a JSON `body` to `url`, never cached, returning the response body
+    pub fn post_json(
+        &mut self,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        if self.offline {
+            return Err(format!("Offline mode, not posting to {url}").into());
+        }
+
+        self.wait_turn();
+        let config = config_line("url", url)
+            + &config_line("header", "Content-Type: application/json")
+            + &headers_config(headers)
+            + &config_line("data-binary", body);
+        let output = curl(&["-s", "-f", "-X", "POST"], &config)?;
+        if !output.status.success() {
+            return Err(format!("Request to {url} failed").into());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// POST the file `upload` to `url` and write the response body to
+    /// `download`, a failed request returns the body as the error
+    pub fn post_file(
+        &mut self,
+        url: &str,
+        headers: &[(&str, &str)],
+        upload: &Path,
+        download: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.offline {
+            return Err(format!("Offline mode, not posting to {url}").into());
+        }
+
+        self.wait_turn();
+        let config = config_line("url", url)
+            + &config_line("header", "Content-Type: application/octet-stream")
+            + &headers_config(headers)
+            + &config_line("data-binary", &format!("@{}", upload.display()))
+            + &config_line("output", &download.display().to_string());
+        let output = curl(&["-s", "-S", "-X", "POST", "-w", "%{http_code}"], &config)?;
+        if !output.status.success() {
+            return Err(format!(
+                "Request to {url} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+        let status = String::from_utf8_lossy(&output.stdout).to_string();
+        if status != "200" {
+            let body = read_to_string(download).unwrap_or_default();
+            let _ = remove_file(download);
+            return Err(format!("{url} answered {status}: {}", body.trim()).into());
+        }
+
+        Ok(())
+    }
+}
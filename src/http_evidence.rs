@@ -0,0 +1,122 @@
+use ::toml::{Table, Value};
+
+use crate::report::Entry;
+use crate::toml::TableExt;
+use crate::utils::typst_escape;
+
+// HTTP request/response pairs listed in the front-matter of a finding, shown
+// as `{{ http_evidence }}`:
+//
+//  [[http]]
+//  label = "Login with a forged token"
+//  request = """
+//  POST /login HTTP/1.1
+//  Host: app.example.com
+//  """
+//  response = """
+//  HTTP/1.1 200 OK
+//  """
+//
+// Values of the headers in `redact_headers` of the config (Authorization and
+// cookies by default) are replaced unless the pair sets `redact = false`.
+
+pub const FRONT_MATTER_KEY: &str = "http";
+pub const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "Authorization",
+    "Proxy-Authorization",
+    "Cookie",
+    "Set-Cookie",
+];
+const REDACTED: &str = "[REDACTED]";
+
+/// Message with the values of `redacted` headers replaced, the body is kept
+fn redact(message: &str, redacted: &[String]) -> String {
+    let mut lines = Vec::new();
+    let mut in_headers = true;
+    for (n, line) in message.lines().enumerate() {
+        if line.is_empty() {
+            in_headers = false;
+        }
+        // The first line is the request or status line
+        let header = match (in_headers && n > 0, line.split_once(':')) {
+            (true, Some((name, _))) => name.trim(),
+            _ => "",
+        };
+        match redacted.iter().any(|r| r.eq_ignore_ascii_case(header)) {
+            true => lines.push(format!("{header}: {REDACTED}")),
+            false => lines.push(line.to_string()),
+        }
+    }
+    lines.join("\n")
+}
+
+/// Labeled raw block of a request or response
+fn message_block(caption: &str, message: &str) -> String {
+    // The fence has to be longer than any backtick run of the message
+    let longest = message.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "_{caption}_\n{fence}http\n{}\n{fence}\n",
+        message.trim_matches('\n')
+    )
+}
+
+/// Values of the headers `render` replaces, e.g. the token of an
+/// `Authorization` header
+pub fn redacted_values(meta: &Table, redacted: &[String]) -> Vec<String> {
+    let Some(Value::Array(pairs)) = meta.get(FRONT_MATTER_KEY) else {
+        return Vec::new();
+    };
+    let mut values = Vec::new();
+    for pair in pairs {
+        let Value::Table(pair) = pair else {
+            continue;
+        };
+        if let Some(Value::Boolean(false)) = pair.get("redact") {
+            continue;
+        }
+        for key in ["request", "response"] {
+            let Some(message) = pair.get_str(key) else {
+                continue;
+            };
+            let message = message.replace('\r', "");
+            for (line, shown) in message.lines().zip(redact(&message, redacted).lines()) {
+                if let (true, Some((_, value))) = (line != shown, line.split_once(':')) {
+                    values.push(value.trim().to_string());
+                }
+            }
+        }
+    }
+    values
+}
+
+/// `{{ http_evidence }}` of a finding, empty if it lists no HTTP pairs
+pub fn render(finding: &Entry, redacted: &[String]) -> String {
+    let Some(Value::Array(pairs)) = finding.meta.get(FRONT_MATTER_KEY) else {
+        return String::new();
+    };
+    let mut rendered = String::new();
+    for (n, pair) in pairs.iter().enumerate() {
+        let Value::Table(pair) = pair else {
+            continue;
+        };
+        let redacted = match pair.get("redact") {
+            Some(Value::Boolean(false)) => &[],
+            _ => redacted,
+        };
+        rendered
+            .push_str("#block(width: 100%, inset: 8pt, radius: 2pt, stroke: 0.5pt + luma(200))[\n");
+        let label = pair
+            .get_str("label")
+            .map_or(format!("HTTP exchange {}", n + 1), typst_escape);
+        rendered.push_str(&format!("*{label}*\n\n"));
+        for (key, caption) in [("request", "Request"), ("response", "Response")] {
+            if let Some(message) = pair.get_str(key) {
+                let message = message.replace('\r', "");
+                rendered.push_str(&message_block(caption, &redact(&message, redacted)));
+            }
+        }
+        rendered.push_str("]\n");
+    }
+    rendered
+}
@@ -0,0 +1,174 @@
+use std::{env, error::Error, path::PathBuf, process::exit};
+
+use serde_json::{json, Value};
+
+use crate::base64;
+use crate::config::Config;
+use crate::http::Client;
+use crate::model::{self, Finding};
+use crate::push;
+use crate::report::{self, Report};
+
+// Jira issues of findings, created through the REST API of Jira Cloud and
+// Data Center. The instance is `jira_url` of the config, the credentials come
+// from the environment: JIRA_USER and an API token in JIRA_TOKEN for Jira
+// Cloud, or a personal access token alone in JIRA_TOKEN for Data Center.
+
+pub const FRONT_MATTER_KEY: &str = "jira";
+
+const DEFAULT_ISSUE_TYPE: &str = "Bug";
+// Priorities of the default Jira scheme per severity, `[jira_priorities]`
+// of the config replaces them
+const DEFAULT_PRIORITIES: &[(&str, &str)] = &[
+    ("critical", "Highest"),
+    ("high", "High"),
+    ("medium", "Medium"),
+    ("low", "Low"),
+    ("info", "Lowest"),
+];
+
+/// Authorization header from the environment
+fn authorization() -> Option<String> {
+    let token = env::var("JIRA_TOKEN").ok()?;
+    Some(match env::var("JIRA_USER") {
+        Ok(user) => format!(
+            "Basic {}",
+            base64::encode(format!("{user}:{token}").as_bytes())
+        ),
+        Err(_) => format!("Bearer {token}"),
+    })
+}
+
+fn priority(finding: &Finding, config: &Config) -> Option<String> {
+    let severity = finding.severity.as_deref()?.to_lowercase();
+    let configured = config
+        .jira_priorities
+        .iter()
+        .flatten()
+        .find(|(level, _)| level.to_lowercase() == severity)
+        .map(|(_, priority)| priority.clone());
+    configured.or_else(|| {
+        DEFAULT_PRIORITIES
+            .iter()
+            .find(|(level, _)| *level == severity)
+            .map(|(_, priority)| priority.to_string())
+    })
+}
+
+/// Description in Jira wiki markup
+fn description(finding: &Finding, reference: &str) -> String {
+    let (description, remediation) = finding.description_and_remediation();
+    let mut text = description;
+    if !remediation.is_empty() {
+        text.push_str(&format!("\n\nh2. Remediation\n{remediation}"));
+    }
+    text.push_str("\n\n----\n");
+    if let Some(ref severity) = finding.severity {
+        text.push_str(&format!("*Severity:* {severity}\n"));
+    }
+    if let Some(ref cvss) = finding.cvss {
+        text.push_str(&format!("*CVSS:* {cvss}\n"));
+    }
+    if !finding.cwe.is_empty() {
+        text.push_str(&format!("*CWE:* {}\n", finding.cwe.join(", ")));
+    }
+    if !finding.hosts.is_empty() {
+        text.push_str(&format!("*Affected hosts:* {}\n", finding.hosts.join(", ")));
+    }
+    text.push_str(&format!("*Finding:* {reference}"));
+    text
+}
+
+fn issue(
+    finding: &Finding,
+    project: &str,
+    issue_type: &str,
+    priority: Option<String>,
+    reference: &str,
+) -> Value {
+    let mut issue = json!({
+        "fields": {
+            "project": { "key": project },
+            "summary": finding.title,
+            "description": description(finding, reference),
+            "issuetype": { "name": issue_type },
+            "labels": ["security"],
+        }
+    });
+    if let Some(priority) = priority {
+        issue["fields"]["priority"] = json!({ "name": priority });
+    }
+    issue
+}
+
+/// Creates a Jira issue per open finding without one in `project` or `jira_project`
+pub fn push(
+    report_dir: Option<PathBuf>,
+    project: Option<String>,
+    dry_run: bool,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    let Some(project) = project.or(config.jira_project.clone()) else {
+        eprintln!("ERROR: No Jira project, pass --project or set jira_project in the config");
+        exit(1);
+    };
+    let Some(url) = config.jira_url.as_deref() else {
+        eprintln!("ERROR: No Jira instance, set jira_url in the config");
+        exit(1);
+    };
+    let issue_type = config
+        .jira_issue_type
+        .as_deref()
+        .unwrap_or(DEFAULT_ISSUE_TYPE);
+
+    let findings = model::Report::from(&report);
+    let pending = push::pending(&report, &findings, FRONT_MATTER_KEY);
+    if pending.is_empty() {
+        println!("No findings to push, all open ones have a Jira issue");
+        return Ok(());
+    }
+    if dry_run {
+        for (_, finding) in &pending {
+            println!(
+                "Would create a {project} issue for {} {} (priority {})",
+                finding.id,
+                finding.title,
+                priority(finding, &config).unwrap_or("default".to_string())
+            );
+        }
+        return Ok(());
+    }
+    let Some(authorization) = authorization() else {
+        eprintln!("ERROR: No Jira credentials, set JIRA_TOKEN and for Jira Cloud JIRA_USER");
+        exit(1);
+    };
+
+    let endpoint = format!("{}/rest/api/2/issue", url.trim_end_matches('/'));
+    let mut client = Client::new(offline);
+    push::create_tickets(
+        &report_path,
+        &pending,
+        FRONT_MATTER_KEY,
+        "Jira",
+        |finding| {
+            let body = issue(
+                finding,
+                &project,
+                issue_type,
+                priority(finding, &config),
+                &push::reference(finding, &findings),
+            )
+            .to_string();
+            let response =
+                client.post_json(&endpoint, &[("Authorization", &authorization)], &body)?;
+            let response: Value = serde_json::from_str(&response)?;
+            Ok(response["key"]
+                .as_str()
+                .ok_or("No issue key in the response")?
+                .to_string())
+        },
+    )
+}
@@ -0,0 +1,243 @@
+use std::{
+    error::Error,
+    fs::read_to_string,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{exit, Command, Stdio},
+};
+
+use crate::check;
+use crate::config::Config;
+use crate::consts::PLACEHOLDER_TEXT;
+use crate::diagnostics::{Diagnostic, Level};
+use crate::front_matter;
+use crate::report;
+
+// Spelling and style of the prose in sections and findings. Spelling is
+// checked by hunspell with the `lint_dictionary` of the config (en_US by
+// default), words in `lint_ignore` are never flagged.
+
+const DEFAULT_DICTIONARY: &str = "en_US";
+const AUXILIARIES: &[&str] = &["is", "are", "was", "were", "be", "been", "being"];
+// Participles that aren't passive voice after an auxiliary
+const NOT_PASSIVE: &[&str] = &["based", "used", "required", "needed", "located"];
+
+/// Prose of a line with Typst code, raw text and placeholders blanked out,
+/// so columns stay the same. `None` for lines that are code only.
+fn prose(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with("//") {
+        return None;
+    }
+    let mut prose = String::with_capacity(line.len());
+    let mut skip_until: Option<&str> = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match skip_until {
+            Some(end) if line[i..].starts_with(end) => {
+                for _ in 1..end.len() {
+                    chars.next();
+                    prose.push(' ');
+                }
+                skip_until = None;
+            }
+            Some(_) => {}
+            None if line[i..].starts_with("{{") => skip_until = Some("}}"),
+            None if c == '`' => skip_until = Some("`"),
+            None if c == '<' => skip_until = Some(">"),
+            None if c == '@' || line[i..].starts_with("http") => skip_until = Some(" "),
+            None => {
+                prose.push(c);
+                continue;
+            }
+        }
+        prose.push(' ');
+    }
+    Some(prose)
+}
+
+/// Words of a line with their byte offsets
+fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_alphabetic() || c == '\'') {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                words.push((s, line[s..i].trim_matches('\'')));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Misspelled words of `text` according to hunspell
+fn misspelled(text: &str, dictionary: &str) -> Result<Vec<String>, String> {
+    let mut child = Command::new("hunspell")
+        .args(["-l", "-d", dictionary])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|_| "hunspell is not installed, skipping the spell check".to_string())?;
+    let mut stdin = child.stdin.take().expect("Failed to open hunspell stdin");
+    let written = stdin.write_all(text.as_bytes());
+    drop(stdin);
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if written.is_err() || !output.status.success() {
+        return Err(format!(
+            "hunspell failed, is the {dictionary} dictionary installed?"
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Prose lines of a file by line index, front-matter and raw blocks left out
+fn prose_lines(text: &str) -> Vec<(usize, String)> {
+    let (_, body) = front_matter::split(text);
+    let offset = text[..text.len() - body.len()].lines().count();
+    let mut in_raw = false;
+    body.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            if line.trim_start().starts_with("```") {
+                in_raw = !in_raw;
+                return None;
+            }
+            if in_raw {
+                return None;
+            }
+            Some((offset + i, prose(line)?))
+        })
+        .collect()
+}
+
+fn check_style(path: &Path, text: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut push = |line: usize, start: usize, end: usize, message: String| {
+        diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line,
+            start,
+            end,
+            level: Level::Warning,
+            message,
+        })
+    };
+
+    for (line_number, line) in text.lines().enumerate() {
+        for placeholder in PLACEHOLDER_TEXT {
+            if let Some(start) = line.find(placeholder) {
+                push(
+                    line_number,
+                    start,
+                    start + placeholder.len(),
+                    format!("Leftover \"{placeholder}\""),
+                );
+            }
+        }
+    }
+
+    for (line_number, line) in prose_lines(text) {
+        let content = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        if let Some(start) = content.find("  ") {
+            push(
+                line_number,
+                indent + start,
+                indent + start + 2,
+                "Double space".to_string(),
+            );
+        }
+
+        let words = words(&line);
+        for pair in words.windows(2) {
+            let ((start, auxiliary), (end, participle)) = (pair[0], pair[1]);
+            let participle = participle.to_lowercase();
+            if AUXILIARIES.contains(&auxiliary.to_lowercase().as_str())
+                && participle.ends_with("ed")
+                && participle.len() > 3
+                && !NOT_PASSIVE.contains(&participle.as_str())
+            {
+                push(
+                    line_number,
+                    start,
+                    end + participle.len(),
+                    format!(
+                        "Passive voice \"{auxiliary} {participle}\", consider naming who did it"
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn check_spelling(
+    path: &Path,
+    text: &str,
+    dictionary: &str,
+    ignore: &[String],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<(), String> {
+    let lines = prose_lines(text);
+    let prose: Vec<&str> = lines.iter().map(|(_, line)| line.as_str()).collect();
+    let misspelled = misspelled(&prose.join("\n"), dictionary)?;
+
+    for (line_number, line) in &lines {
+        for (start, word) in words(line) {
+            if misspelled.iter().any(|m| m == word)
+                && !ignore.iter().any(|i| i.eq_ignore_ascii_case(word))
+            {
+                diagnostics.push(Diagnostic {
+                    path: path.to_path_buf(),
+                    line: *line_number,
+                    start,
+                    end: start + word.len(),
+                    level: Level::Warning,
+                    message: format!("Unknown word \"{word}\""),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lints every section and finding, failing if anything was found
+pub fn lint(report_dir: Option<PathBuf>, format: Option<String>) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let config = Config::load_or_exit(&report_path);
+    let dictionary = config
+        .lint_dictionary
+        .as_deref()
+        .unwrap_or(DEFAULT_DICTIONARY);
+    let ignore = config.lint_ignore.clone().unwrap_or_default();
+
+    let mut diagnostics = Vec::new();
+    let mut spell_check = true;
+    for path in check::report_files(&report_path)? {
+        // Unreadable files are reported by check
+        let Ok(text) = read_to_string(&path) else {
+            continue;
+        };
+        check_style(&path, &text, &mut diagnostics);
+        if spell_check {
+            if let Err(e) = check_spelling(&path, &text, dictionary, &ignore, &mut diagnostics) {
+                eprintln!("WARNING: {e}");
+                spell_check = false;
+            }
+        }
+    }
+    diagnostics.sort_by(|a, b| (&a.path, a.line, a.start).cmp(&(&b.path, b.line, b.start)));
+
+    check::print_diagnostics(&report_path, &diagnostics, format.as_deref())?;
+
+    if !diagnostics.is_empty() {
+        exit(1);
+    }
+
+    Ok(())
+}
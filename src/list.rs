@@ -0,0 +1,210 @@
+use std::{
+    error::Error,
+    fs::metadata,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use chrono::{DateTime, Local};
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::health;
+use crate::qa;
+use crate::report::{self, Entry, Report};
+use crate::review;
+use crate::sla;
+use crate::toml::TableExt;
+use crate::workspace;
+
+/// Words of the body, front-matter and markup included
+fn word_count(entry: &Entry) -> usize {
+    entry.body.split_whitespace().count()
+}
+
+/// Modification time of the file, e.g. 2026-10-15 14:03
+fn modified(entry: &Entry) -> Option<String> {
+    let modified = metadata(&entry.path).and_then(|m| m.modified()).ok()?;
+    Some(
+        DateTime::<Local>::from(modified)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+    )
+}
+
+fn print_entries(prefix: char, entries: &[Entry], depth: usize) {
+    for entry in entries {
+        let id = format!("{}{prefix}{}", "  ".repeat(depth), entry.id);
+        if entry.is_chapter() {
+            println!("{id:<6} {}", entry.title());
+        } else {
+            println!(
+                "{id:<6} {:<40} {:<10} {:<12} {:<11} {:<11} {:>6}  {}",
+                entry.title(),
+                entry.meta.get_str("severity").unwrap_or("-"),
+                entry.meta.get_str("status").unwrap_or("-"),
+                review::state(entry),
+                entry.meta.get_str("reviewer").unwrap_or("-"),
+                word_count(entry),
+                modified(entry).unwrap_or("-".to_string()),
+            );
+        }
+        print_entries(prefix, &entry.children, depth + 1);
+    }
+}
+
+fn entries_json(report: &Report, entries: &[Entry]) -> Value {
+    entries
+        .iter()
+        .map(|entry| {
+            let file = entry.path.strip_prefix(&report.path).unwrap_or(&entry.path);
+            match entry.is_chapter() {
+                true => json!({
+                    "number": entry.id,
+                    "file": file.to_string_lossy(),
+                    "title": entry.title(),
+                    "children": entries_json(report, &entry.children),
+                }),
+                false => json!({
+                    "number": entry.id,
+                    "file": file.to_string_lossy(),
+                    "title": entry.title(),
+                    "severity": entry.meta.get_str("severity"),
+                    "status": entry.meta.get_str("status"),
+                    "review": review::state(entry),
+                    "reviewer": entry.meta.get_str("reviewer"),
+                    "words": word_count(entry),
+                    "modified": modified(entry),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Lists every report of a workspace with its findings, approvals and readiness
+fn list_reports(workspace_path: &Path, json: bool) -> Result<(), Box<dyn Error>> {
+    if !json {
+        println!(
+            "{:<40} {:<40} {:<9} {:<9} Ready",
+            "Report", "Title", "Findings", "Approved"
+        );
+    }
+    let mut rows = Vec::new();
+    for report_path in workspace::find_reports(workspace_path) {
+        let report = match Report::load(&report_path) {
+            Ok(report) => report,
+            Err(e) => {
+                match json {
+                    true => rows.push(json!({
+                        "report": report_path.display().to_string(),
+                        "error": e.to_string(),
+                    })),
+                    false => println!("{:<40} ERROR: {e}", report_path.display()),
+                }
+                continue;
+            }
+        };
+        let entries = report.entries().filter(|e| !e.is_chapter()).count();
+        let approved = entries - review::unapproved(&report).len();
+        let ready = health::health(&report_path, &report)?.is_ready();
+        if json {
+            rows.push(json!({
+                "report": report_path.display().to_string(),
+                "title": report.metadata_value("report_title"),
+                "findings": report.findings.len(),
+                "entries": entries,
+                "approved": approved,
+                "ready": ready,
+            }));
+            continue;
+        }
+        println!(
+            "{:<40} {:<40} {:<9} {:<9} {}",
+            report_path.display().to_string(),
+            report.metadata_value("report_title").unwrap_or("-"),
+            report.findings.len(),
+            format!("{approved}/{entries}"),
+            if ready { "yes" } else { "no" }
+        );
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    }
+    Ok(())
+}
+
+/// Lists the sections and findings with their severity, status, review state,
+/// length and last change, or all reports when run on a workspace
+pub fn list(report_dir: Option<PathBuf>, format: Option<String>) -> Result<(), Box<dyn Error>> {
+    let json = match format.as_deref().unwrap_or("text") {
+        "text" => false,
+        "json" => true,
+        format => {
+            eprintln!("ERROR: Unknown format \"{format}\", expected text or json");
+            exit(1);
+        }
+    };
+    let dir = report_dir.clone().unwrap_or_else(|| ".".into());
+    if dir.is_dir() && !workspace::is_report(&dir) {
+        return list_reports(&dir, json);
+    }
+
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    let passed = qa::passed(&report);
+    let state = report.metadata_value("state").unwrap_or(qa::STATES[0]);
+    let breaches = sla::breaches(&report, &config, Local::now().date_naive())?;
+
+    if json {
+        let checklist = config
+            .qa_checklist()
+            .into_iter()
+            .map(|item| json!({ "passed": passed.contains(&item), "item": item }))
+            .collect::<Vec<_>>();
+        let overdue = breaches
+            .iter()
+            .map(|breach| {
+                json!({
+                    "title": breach.title,
+                    "severity": breach.severity,
+                    "due": breach.due.to_string(),
+                    "overdue_days": breach.overdue,
+                })
+            })
+            .collect::<Vec<_>>();
+        let list = json!({
+            "sections": entries_json(&report, &report.sections),
+            "findings": entries_json(&report, &report.findings),
+            "appendices": entries_json(&report, &report.appendices),
+            "state": state,
+            "qa_checklist": checklist,
+            "overdue_remediations": overdue,
+        });
+        println!("{}", serde_json::to_string_pretty(&list)?);
+        return Ok(());
+    }
+
+    println!(
+        "{:<6} {:<40} {:<10} {:<12} {:<11} {:<11} {:>6}  Modified",
+        "#", "Title", "Severity", "Status", "Review", "Reviewer", "Words"
+    );
+    print_entries('s', &report.sections, 0);
+    print_entries('f', &report.findings, 0);
+    print_entries('a', &report.appendices, 0);
+
+    println!("\nQA checklist (state: {state})");
+    for item in config.qa_checklist() {
+        let mark = if passed.contains(&item) { "x" } else { " " };
+        println!("  [{mark}] {item}");
+    }
+
+    if !breaches.is_empty() {
+        println!("\nOverdue remediations");
+        for breach in breaches {
+            println!("  {}", breach.line());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,343 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::{stdin, stdout, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use crate::assets;
+use crate::attack;
+use crate::config::Config;
+use crate::cwe;
+use crate::diagnostics::{self, front_matter_key, Diagnostic, Level};
+use crate::front_matter::{self, DELIMITER};
+use crate::report::Report;
+use crate::review;
+use crate::schema::{Key, METADATA_KEYS, OPTIONAL_METADATA_KEYS, PLACEHOLDERS};
+use crate::scope;
+use crate::xref;
+
+// Language server for report files speaking JSON-RPC over stdio.
+// Offers completion and diagnostics for front-matter keys and values,
+// metadata keys and `{{ placeholder }}` names.
+
+const SEVERITY_ERROR: u32 = 1;
+const SEVERITY_WARNING: u32 = 2;
+
+const KIND_PROPERTY: u32 = 10;
+const KIND_VALUE: u32 = 12;
+const KIND_CONSTANT: u32 = 21;
+const KIND_REFERENCE: u32 = 18;
+
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, Box<dyn Error>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+
+    let Some(length) = length else {
+        return Err("missing Content-Length header".into());
+    };
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn send(message: Value) -> Result<(), Box<dyn Error>> {
+    let body = message.to_string();
+    let mut out = stdout().lock();
+    write!(out, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    out.flush()?;
+    Ok(())
+}
+
+fn respond(id: &Value, result: Value) -> Result<(), Box<dyn Error>> {
+    send(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn notify(method: &str, params: Value) -> Result<(), Box<dyn Error>> {
+    send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+}
+
+fn position(line: usize, character: usize) -> Value {
+    json!({ "line": line, "character": character })
+}
+
+fn diagnostic(d: &Diagnostic) -> Value {
+    let severity = match d.level {
+        Level::Error => SEVERITY_ERROR,
+        Level::Warning => SEVERITY_WARNING,
+    };
+    json!({
+        "range": {
+            "start": position(d.line, d.start),
+            "end": position(d.line, d.end),
+        },
+        "severity": severity,
+        "source": "report-generator",
+        "message": d.message,
+    })
+}
+
+fn completion(label: &str, kind: u32, detail: &str, insert_text: &str) -> Value {
+    json!({
+        "label": label,
+        "kind": kind,
+        "detail": detail,
+        "insertText": insert_text,
+    })
+}
+
+/// Number of lines taken by the front-matter including both delimiters, if present
+fn front_matter_lines(text: &str) -> Option<usize> {
+    let (front_matter, _) = front_matter::split(text);
+    front_matter.map(|f| f.lines().count() + 2)
+}
+
+fn key_completions(keys: &[Key], suffix: &str) -> Vec<Value> {
+    // Patterns like `<severity>_findings` are completed separately
+    keys.iter()
+        .filter(|k| !k.name.contains('<'))
+        .map(|k| {
+            completion(
+                k.name,
+                KIND_PROPERTY,
+                k.description,
+                &format!("{}{suffix}", k.name),
+            )
+        })
+        .collect()
+}
+
+fn completions(uri: &str, text: &str, line: usize, character: usize) -> Vec<Value> {
+    let current = text.lines().nth(line).unwrap_or_default();
+    let prefix: String = current.chars().take(character).collect();
+
+    if uri.ends_with("metadata.typ") {
+        if prefix.contains(':') {
+            return Vec::new();
+        }
+        return key_completions(METADATA_KEYS, ":")
+            .into_iter()
+            .chain(key_completions(OPTIONAL_METADATA_KEYS, ":"))
+            .collect();
+    }
+
+    // Placeholders
+    if let Some(open) = prefix.rfind("{{") {
+        if !prefix[open..].contains("}}") {
+            let severity_counts = severities(uri)
+                .into_iter()
+                .chain(["unrated".to_string()])
+                .map(|severity| {
+                    let name = format!("{severity}_findings");
+                    completion(
+                        &name,
+                        KIND_PROPERTY,
+                        "Number of findings",
+                        &format!("{name} }}}}"),
+                    )
+                })
+                .collect::<Vec<_>>();
+            return key_completions(PLACEHOLDERS, " }}")
+                .into_iter()
+                .chain(severity_counts)
+                .chain(reference_completions(uri))
+                .chain(key_completions(METADATA_KEYS, " }}"))
+                .collect();
+        }
+    }
+
+    // Front-matter, either complete or still being written
+    let in_front_matter = text.starts_with(DELIMITER)
+        && line > 0
+        && front_matter_lines(text).is_none_or(|count| line < count - 1);
+    if !in_front_matter {
+        return Vec::new();
+    }
+
+    match front_matter_key(&prefix) {
+        None => key_completions(front_matter::KEYS, " = "),
+        Some("severity") => severities(uri)
+            .iter()
+            .map(|level| completion(level, KIND_VALUE, "Severity", &format!("\"{level}\"")))
+            .collect(),
+        Some("review") => review::STATES
+            .iter()
+            .map(|state| completion(state, KIND_VALUE, "Review state", &format!("\"{state}\"")))
+            .collect(),
+        Some("cwe") => cwe::entries()
+            .map(|(id, name)| completion(id, KIND_CONSTANT, name, &format!("\"{id}\"")))
+            .collect(),
+        Some(assets::FRONT_MATTER_KEY) => assets::load(&report_path(uri))
+            .unwrap_or_default()
+            .iter()
+            .map(|a| completion(&a.id, KIND_VALUE, &a.name, &format!("\"{}\"", a.id)))
+            .collect(),
+        Some(attack::FRONT_MATTER_KEY) => attack::techniques()
+            .map(|t| completion(t.id, KIND_CONSTANT, t.name, &format!("\"{}\"", t.id)))
+            .collect(),
+        Some(_) => Vec::new(),
+    }
+}
+
+// Only `file://` URIs are used for opened reports
+fn uri_path(uri: &str) -> PathBuf {
+    PathBuf::from(uri.strip_prefix("file://").unwrap_or(uri))
+}
+
+/// Report directory containing the document
+fn report_path(uri: &str) -> PathBuf {
+    let path = uri_path(uri);
+    path.ancestors()
+        .find(|dir| dir.join("metadata.typ").exists())
+        .unwrap_or(Path::new("."))
+        .to_path_buf()
+}
+
+/// Severity scheme of the report containing the document
+fn severities(uri: &str) -> Vec<String> {
+    Config::load(&report_path(uri))
+        .unwrap_or_default()
+        .severities()
+}
+
+/// `ref:FND-003` placeholders of the findings of the report
+fn reference_completions(uri: &str) -> Vec<Value> {
+    let Ok(report) = Report::load(&report_path(uri)) else {
+        return Vec::new();
+    };
+    report
+        .findings
+        .iter()
+        .map(|finding| {
+            let name = format!("ref:{}", xref::label(finding.id));
+            completion(
+                &name,
+                KIND_REFERENCE,
+                &finding.title(),
+                &format!("{name} }}}}"),
+            )
+        })
+        .collect()
+}
+
+/// IDs of the assets of the report containing the document
+fn asset_ids(uri: &str) -> Vec<String> {
+    assets::load(&report_path(uri))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| a.id)
+        .collect()
+}
+
+fn publish_diagnostics(uri: &str, text: &str) -> Result<(), Box<dyn Error>> {
+    let path = uri_path(uri);
+    let config = Config::load(&report_path(uri)).unwrap_or_default();
+    let mut diagnostics = diagnostics::check_file(&path, text, &config.severities());
+    diagnostics.extend(diagnostics::check_score(
+        &path,
+        text,
+        &config.severity_presentation(),
+    ));
+    diagnostics.extend(diagnostics::check_assets(&path, text, &asset_ids(uri)));
+    if let Ok(Some(scope)) = scope::load(&report_path(uri)) {
+        diagnostics.extend(diagnostics::check_scope(&path, text, &scope));
+    }
+    notify(
+        "textDocument/publishDiagnostics",
+        json!({
+            "uri": uri,
+            "diagnostics": diagnostics.iter().map(diagnostic).collect::<Vec<_>>(),
+        }),
+    )
+}
+
+pub fn lsp() -> Result<(), Box<dyn Error>> {
+    let mut reader = stdin().lock();
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    while let Some(message) = read_message(&mut reader)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        match method {
+            "initialize" => {
+                let result = json!({
+                    "capabilities": {
+                        // Full document sync
+                        "textDocumentSync": 1,
+                        "completionProvider": { "triggerCharacters": ["{", "\"", " "] },
+                    },
+                    "serverInfo": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                });
+                respond(&message["id"], result)?;
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                publish_diagnostics(&uri, &text)?;
+                documents.insert(uri, text);
+            }
+            "textDocument/didChange" => {
+                // Full sync, the last change holds the whole document
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                publish_diagnostics(&uri, &text)?;
+                documents.insert(uri, text);
+            }
+            "textDocument/didClose" => {
+                documents.remove(&uri);
+                notify(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )?;
+            }
+            "textDocument/completion" => {
+                let position = &params["position"];
+                let line = position["line"].as_u64().unwrap_or_default() as usize;
+                let character = position["character"].as_u64().unwrap_or_default() as usize;
+                let text = documents.get(&uri).map(String::as_str).unwrap_or_default();
+                respond(
+                    &message["id"],
+                    json!(completions(&uri, text, line, character)),
+                )?;
+            }
+            "shutdown" => respond(&message["id"], Value::Null)?,
+            "exit" => break,
+            // Unsupported requests still need a response
+            _ => {
+                if let Some(id) = message.get("id") {
+                    respond(id, Value::Null)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
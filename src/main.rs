@@ -1,14 +1,113 @@
-use std::{error::Error, process::exit};
+use std::{env, error::Error, path::PathBuf};
+
+use clap::Parser;
+
+use args::{Cli, Command};
 
 mod args;
 mod consts;
+mod config;
 mod utils;
+mod changes;
 mod template;
+mod toml;
+mod front_matter;
+mod report;
+mod attack;
+mod nvd;
+mod renumber;
+mod schema;
+mod severity;
+mod sha256;
+mod http;
+mod cwe;
+mod diagnostics;
+mod quality;
+mod summary;
+mod review;
+mod source_map;
+mod health;
+mod workspace;
+mod relations;
+mod xref;
+mod encrypt;
+mod environment;
+mod hosts;
+mod assets;
+mod digest;
+mod tls;
+mod risk;
+mod surface;
+mod roadmap;
+mod evidence;
+mod client;
+mod pack;
+mod versions;
+mod desktop;
+mod git;
+mod qa;
+mod trend;
+mod watch;
+mod brand;
+mod fonts;
+mod blobs;
+mod page;
+mod export;
+mod attach;
+mod view;
+mod http_evidence;
+mod sla;
+mod secrets;
+mod preview;
+mod build_cache;
+mod completions;
+mod rename;
+mod remove;
+mod stats;
+mod timesheet;
+mod partials;
+mod cover;
+mod sign;
+mod signoff;
+mod csv_import;
+mod base64;
+mod defectdojo;
+mod sarif;
+mod jira;
+mod push;
+mod github;
+mod gitlab;
+mod notify;
+mod serve;
+mod remote;
+mod ci;
+mod engagement;
+mod questionnaire;
+mod scope;
+mod snippets;
 
 mod compile_report;
 mod new_report;
 mod new_section;
 mod new_finding;
+mod attack_layer;
+mod enrich;
+mod tui;
+mod undo;
+mod lsp;
+mod reorder;
+mod check;
+mod validate;
+mod lint;
+mod templates;
+mod tutorial;
+mod list;
+mod diff;
+#[cfg(feature = "gui")]
+mod gui;
+mod model;
+mod duplicate;
+mod manifest;
 
 // TODO: templates for default finding (+evidence), common vulns, default section
 // TODO: better looking template
@@ -21,35 +120,396 @@ mod new_finding;
    - - 2.scope.typ
    - - 3.methodology.typ
    - - 4.section.typ
+   - - 5.chapter
+   - - - 1.subsection.typ
    - findings
    - - 1.finding.typ
+
+   A workspace is a directory holding several reports at any depth,
+   see workspace.rs
+
+   Section and finding files may start with a TOML front-matter block
+   enclosed in `+++` lines, e.g. `attack = ["T1190"]`
 */
 
+/// Desktop GUI, started without a subcommand
+#[cfg(feature = "gui")]
+fn start_gui(dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    gui::gui(dir)
+}
+
+#[cfg(not(feature = "gui"))]
+fn start_gui(_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    eprintln!(
+        "No subcommand provided. Check --help\n(the GUI requires building with `--features gui`)"
+    );
+    std::process::exit(1);
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
-    let args = args::get_args();
+    let cli = Cli::parse();
 
-    if let Some(command) = args.subcommand {
-        match command.as_ref() {
-            "new" => {
-                new_report::new_report(args.dir)?;
+    if cli.schema {
+        println!("{}", serde_json::to_string_pretty(&schema::schema())?);
+        return Ok(());
+    }
+
+    let offline = cli.offline;
+    let Some(command) = cli.command else {
+        return start_gui(cli.dir);
+    };
+
+    match command {
+        Command::New {
+            dir,
+            client,
+            git,
+            engagement,
+            interactive,
+            dry_run,
+        } => {
+            new_report::new_report(dir, client, git, engagement, interactive, dry_run)?;
+        }
+        Command::Compile(args) => {
+            // PDFs are encrypted with the given password or one asked for
+            let password = match (args.encrypt, args.password) {
+                (true, Some(password)) => Some(password),
+                (true, None) => match env::var(encrypt::PASSWORD_VAR) {
+                    Ok(password) if !password.is_empty() => Some(password),
+                    _ => Some(encrypt::prompt_password("PDF", true)?),
+                },
+                (false, _) => None,
+            };
+            let options = compile_report::Options {
+                final_report: args.final_report,
+                password,
+                draft: args.build.draft,
+                tag: args.build.tag,
+                clean_result: args.build.clean_result,
+                allow_placeholders: args.build.allow_placeholders,
+                brand: args.build.brand,
+                theme: args.build.theme,
+                sign: args.sign,
+                manifest: args.manifest,
+                force: args.force,
+                notify: !args.no_notify,
+                offline,
+                emit_typ: args.emit_typ,
+            };
+            if let Some(ref url) = args.remote {
+                remote::compile(args.dir, url, args.output, &options)?;
+            } else if args.all {
+                compile_report::compile_all(args.dir, args.format, options)?;
+            } else {
+                compile_report::compile_report(args.dir, args.output, args.format, options)?;
             }
-            "compile" => {
-                compile_report::compile_report(args.dir, args.output)?;
+        }
+        Command::Watch {
+            dir,
+            output,
+            format,
+            build,
+            manifest,
+        } => {
+            // Compile flags are passed on to every rebuild
+            let mut compile_args = build.to_args();
+            if let Some(output) = output {
+                compile_args.extend(["-o".to_string(), output]);
+            }
+            if let Some(format) = format {
+                compile_args.extend(["--format".to_string(), format]);
             }
-            "new-section" => {
-                new_section::new_section(args.dir, args.name, args.template)?;
+            if manifest {
+                compile_args.push("--manifest".to_string());
             }
-            "new-finding" => {
-                new_finding::new_finding(args.dir, args.name, args.template)?;
+            // Rebuilds on every change would flood the compile webhook
+            compile_args.push("--no-notify".to_string());
+            watch::watch(dir, compile_args)?;
+        }
+        Command::Serve {
+            dir,
+            port,
+            api,
+            bind,
+            build,
+        } => {
+            if api {
+                remote::serve_api(port, bind)?;
+            } else {
+                // Serve compiles a PDF of its own
+                let mut compile_args = build.to_args();
+                compile_args.push("--no-notify".to_string());
+                serve::serve(dir, port, compile_args)?;
+            }
+        }
+        Command::NewSection {
+            dir,
+            name,
+            template,
+            dry_run,
+        } => {
+            new_section::new_section(dir, name, template, dry_run)?;
+        }
+        Command::NewAppendix { dir, name, dry_run } => {
+            new_section::new_appendix(dir, name, dry_run)?;
+        }
+        Command::NewFinding {
+            dir,
+            name,
+            template,
+            dry_run,
+        } => {
+            new_finding::new_finding(dir, name, template, dry_run)?;
+        }
+        Command::AttackLayer { dir, output } => {
+            attack_layer::attack_layer(dir, output)?;
+        }
+        Command::Enrich { dir, nvd, dry_run } => {
+            enrich::enrich(dir, nvd, dry_run, offline)?;
+        }
+        Command::Tui { dir } => {
+            tui::tui(dir)?;
+        }
+        Command::Undo { dir } => {
+            undo::undo(dir)?;
+        }
+        Command::Lsp => {
+            lsp::lsp()?;
+        }
+        Command::Check {
+            dir,
+            format,
+            quality,
+        } => {
+            check::check(dir, format, quality)?;
+        }
+        Command::Validate { dir, format } => {
+            validate::validate(dir, format)?;
+        }
+        Command::Lint { dir, format } => {
+            lint::lint(dir, format)?;
+        }
+        Command::Templates {
+            action,
+            dir,
+            output,
+            dry_run,
+        } => {
+            templates::templates(action, dir, output, dry_run)?;
+        }
+        Command::Tutorial { dir } => {
+            tutorial::tutorial(dir)?;
+        }
+        Command::Qa {
+            action,
+            item,
+            dir,
+            dry_run,
+        } => {
+            qa::qa(dir, action, item, dry_run)?;
+        }
+        Command::State {
+            action,
+            state,
+            dir,
+            dry_run,
+        } => {
+            qa::state(dir, action, state, dry_run)?;
+        }
+        Command::Diff { old, new, dir } => {
+            diff::diff(dir, old, new)?;
+        }
+        Command::Snapshot { message, dir } => {
+            git::snapshot(dir, message)?;
+        }
+        Command::Bump {
+            dir,
+            major,
+            minor: _,
+            note,
+            author,
+            dry_run,
+        } => {
+            versions::bump(dir, major, note, author, dry_run)?;
+        }
+        Command::Summary { dir } => {
+            summary::summary(dir)?;
+        }
+        Command::List { dir, format } => {
+            list::list(dir, format)?;
+        }
+        Command::Verify { dir } => {
+            manifest::verify(dir)?;
+        }
+        Command::Ci {
+            dir,
+            format,
+            final_report,
+            build,
+            force,
+            manifest,
+            no_notify,
+        } => {
+            // Compile flags are passed on to every report
+            let mut compile_args = build.to_args();
+            if let Some(ref format) = format {
+                compile_args.extend(["--format".to_string(), format.clone()]);
             }
-            _ => {
-                eprintln!("Incorrect subcommand. Check --help");
-                exit(1);
+            for (set, flag) in [
+                (final_report, "--final"),
+                (force, "--force"),
+                (manifest, "--manifest"),
+                (no_notify, "--no-notify"),
+            ] {
+                if set {
+                    compile_args.push(flag.to_string());
+                }
             }
+            ci::ci(dir, format, compile_args)?;
+        }
+        Command::Stats {
+            dir,
+            format,
+            effort,
+        } => {
+            stats::stats(dir, format, effort)?;
+        }
+        Command::ImportTls {
+            file,
+            dir,
+            findings,
+            dry_run,
+        } => {
+            tls::import_tls(file, dir, findings, dry_run)?;
+        }
+        Command::ImportCsv { file, dir, dry_run } => {
+            csv_import::import_csv(file, dir, dry_run)?;
+        }
+        Command::ImportSubdomains { file, dir, dry_run } => {
+            surface::import_subdomains(file, dir, dry_run)?;
+        }
+        Command::Export {
+            target,
+            dir,
+            format,
+            output,
+        } => {
+            export::export(target, dir, format, output)?;
+        }
+        Command::Push {
+            target,
+            dir,
+            project,
+            repo,
+            dry_run,
+        } => {
+            push::push(target, dir, project, repo, dry_run, offline)?;
+        }
+        Command::View { target, password } => {
+            view::view(target, password)?;
+        }
+        Command::Preview {
+            kind,
+            id,
+            dir,
+            output,
+        } => {
+            preview::preview(kind, id, dir, output)?;
+        }
+        Command::Completions { shell } => {
+            completions::completions(shell)?;
+        }
+        Command::Manpage { output } => {
+            completions::man(output)?;
+        }
+        Command::Attach {
+            file,
+            dir,
+            kind,
+            syntax,
+            max_lines,
+            name,
+            dry_run,
+        } => {
+            attach::attach(file, dir, kind, syntax, max_lines, name, dry_run)?;
+        }
+        Command::Digest {
+            dir,
+            workspace,
+            format,
+            output,
+        } => {
+            digest::digest(dir, workspace, format, output, offline)?;
+        }
+        Command::Review {
+            target,
+            state,
+            dir,
+            reviewer,
+            dry_run,
+        } => {
+            review::review(dir, target, state, reviewer, dry_run)?;
+        }
+        Command::Pack {
+            dir,
+            output,
+            password,
+        } => {
+            let password = match password {
+                Some(password) => password,
+                None => encrypt::prompt_password("archive", true)?,
+            };
+            pack::pack(dir, output, password)?;
+        }
+        Command::Unpack {
+            archive,
+            dir,
+            password,
+        } => {
+            let password = match password {
+                Some(password) => password,
+                None => encrypt::prompt_password("archive", false)?,
+            };
+            pack::unpack(archive, dir, password)?;
+        }
+        Command::Reorder { kind, dir, dry_run } => {
+            reorder::reorder(dir, kind, dry_run)?;
+        }
+        Command::Rename {
+            kind,
+            id,
+            title,
+            dir,
+            dry_run,
+        } => {
+            rename::rename(dir, kind, id, title, dry_run)?;
+        }
+        Command::Rm {
+            kind,
+            id,
+            dir,
+            keep_evidence,
+            dry_run,
+        } => {
+            remove::remove(dir, kind, id, keep_evidence, dry_run)?;
+        }
+        Command::Dup {
+            kind,
+            id,
+            dir,
+            name,
+            dry_run,
+        } => {
+            duplicate::duplicate(dir, kind, id, name, dry_run)?;
+        }
+        Command::Move {
+            target,
+            position,
+            dir,
+            dry_run,
+        } => {
+            reorder::move_entry(dir, target, position, dry_run)?;
         }
-    } else {
-        // GUI
-        todo!("GUI");
     }
 
     Ok(())
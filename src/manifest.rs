@@ -0,0 +1,135 @@
+use std::{
+    error::Error,
+    fs::{read, read_dir, read_to_string, write},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use crate::consts::STATE_DIR;
+use crate::report;
+use crate::sha256;
+
+// Integrity manifest of a delivered report, `compile --manifest` (or
+// `manifest = true` in the config) writes `MANIFEST.txt` into the report
+// with sha256sum style hashes of the compiled report and every source file:
+//
+//  3a7bd3e2...  metadata.typ
+//  9f86d081...  report.pdf
+//
+// `verify <dir>` checks a delivered bundle against it, `sha256sum -c
+// MANIFEST.txt` works as well. Packed archives carry one of the same format.
+
+pub const MANIFEST_FILE: &str = "MANIFEST.txt";
+
+/// Manifest line of `path`, relative to `base`
+pub fn line(path: &Path, base: &Path) -> Result<String, String> {
+    let content = read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let relative = path.strip_prefix(base).unwrap_or(path);
+    Ok(format!(
+        "{}  {}\n",
+        sha256::hex_digest(&content),
+        relative.display()
+    ))
+}
+
+/// Files of the report below `dir`, without undo history, caches and the manifest itself
+fn files(dir: &Path, base: &Path, found: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.file_name().is_some_and(|name| name == STATE_DIR)
+            || path
+                .strip_prefix(base)
+                .is_ok_and(|p| p == Path::new(MANIFEST_FILE))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            files(&path, base, found)?;
+        } else {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Writes the manifest of the report, compiled outputs outside of it can't be listed
+pub fn write_manifest(report_path: &Path, outputs: &[String]) -> Result<(), String> {
+    let mut paths = Vec::new();
+    files(report_path, report_path, &mut paths)?;
+    paths.sort();
+    for output in outputs {
+        let inside = Path::new(output)
+            .canonicalize()
+            .ok()
+            .zip(report_path.canonicalize().ok())
+            .is_some_and(|(output, report)| output.starts_with(report));
+        if !inside {
+            eprintln!("WARNING: {output} is outside the report and not in {MANIFEST_FILE}");
+        }
+    }
+    let mut manifest = String::new();
+    for path in paths {
+        manifest.push_str(&line(&path, report_path)?);
+    }
+    let path = report_path.join(MANIFEST_FILE);
+    write(&path, manifest).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Number of files listed in `manifest` and the problems of their copies in `dir`
+pub fn check(dir: &Path, manifest: &str) -> (usize, Vec<String>) {
+    let mut problems = Vec::new();
+    let mut count = 0;
+    for line in manifest.lines() {
+        let Some((hash, path)) = line.split_once("  ") else {
+            continue;
+        };
+        count += 1;
+        match read(dir.join(path)) {
+            Ok(content) if sha256::hex_digest(&content) == hash => {}
+            Ok(_) => problems.push(format!("{path} doesn't match its hash")),
+            Err(e) => problems.push(format!("{path}: {e}")),
+        }
+    }
+    (count, problems)
+}
+
+/// Verifies a delivered report against its manifest, files added since count as altered
+pub fn verify(dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let dir = report::resolve_path(dir);
+    let manifest = read_to_string(dir.join(MANIFEST_FILE)).unwrap_or_else(|e| {
+        eprintln!(
+            "ERROR: {MANIFEST_FILE} not readable in {}: {e}",
+            dir.display()
+        );
+        exit(1);
+    });
+
+    let (count, mut problems) = check(&dir, &manifest);
+    let mut paths = Vec::new();
+    files(&dir, &dir, &mut paths)?;
+    for path in paths {
+        let relative = path
+            .strip_prefix(&dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        if !manifest
+            .lines()
+            .any(|line| line.split_once("  ").is_some_and(|(_, p)| p == relative))
+        {
+            problems.push(format!("{relative} isn't in {MANIFEST_FILE}"));
+        }
+    }
+
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("ERROR: {problem}");
+        }
+        eprintln!("{} doesn't match its manifest", dir.display());
+        exit(1);
+    }
+    println!("Verified {count} files, all hashes match");
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use ::toml::Value;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::evidence;
+use crate::hosts;
+use crate::report::{self, Entry};
+use crate::toml::TableExt;
+use crate::xref::{self, IdScheme};
+
+// Versioned data model of a report for exporters and external tools, kept
+// independent of the parsing internals in `report`. Fields are only added
+// within a version; renaming or removing one bumps `VERSION`.
+
+pub const VERSION: u32 = 1;
+
+// Headings starting the remediation part of a finding
+const REMEDIATION_HEADINGS: &[&str] = &["remediation", "recommendation", "mitigation"];
+
+#[derive(Serialize)]
+pub struct Report {
+    pub title: Option<String>,
+    // Serialized as an object in the order of the metadata file
+    #[serde(serialize_with = "ordered_map")]
+    pub metadata: Vec<(String, String)>,
+    pub sections: Vec<Section>,
+    pub findings: Vec<Finding>,
+    pub appendices: Vec<Section>,
+}
+
+#[derive(Serialize)]
+pub struct Section {
+    pub number: usize,
+    pub name: String,
+    pub title: String,
+    // Typst markup
+    pub body: String,
+    pub children: Vec<Section>,
+}
+
+#[derive(Serialize)]
+pub struct Finding {
+    // Displayed ID, e.g. FND-001
+    pub id: String,
+    pub number: usize,
+    pub name: String,
+    // Plain text without Typst escapes
+    pub title: String,
+    pub severity: Option<String>,
+    pub cvss: Option<String>,
+    pub status: Option<String>,
+    pub hosts: Vec<String>,
+    pub cwe: Vec<String>,
+    pub cve: Vec<String>,
+    // Source code locations, e.g. src/login.py:42
+    pub locations: Vec<String>,
+    pub evidence: Vec<Evidence>,
+    // Typst markup
+    pub body: String,
+}
+
+#[derive(Serialize)]
+pub struct Evidence {
+    // Relative to the report
+    pub path: String,
+    // Typst markup
+    pub caption: Option<String>,
+}
+
+/// Front-matter value as plain text, numbers included
+fn text(entry: &Entry, key: &str) -> Option<String> {
+    match entry.meta.get(key) {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Integer(i)) => Some(i.to_string()),
+        Some(Value::Float(f)) => Some(f.to_string()),
+        _ => None,
+    }
+}
+
+/// Title without the escapes of Typst markup
+fn plain_title(finding: &Entry) -> String {
+    let markup = xref::finding_title(finding);
+    let mut title = String::new();
+    let mut chars = markup.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => title.extend(chars.next()),
+            _ => title.push(c),
+        }
+    }
+    title
+}
+
+impl Section {
+    fn from(entry: &Entry) -> Self {
+        Section {
+            number: entry.id,
+            name: entry.name.clone(),
+            title: entry.title(),
+            body: entry.body.clone(),
+            children: entry.children.iter().map(Section::from).collect(),
+        }
+    }
+}
+
+impl Finding {
+    fn from(finding: &Entry, ids: &IdScheme) -> Self {
+        Finding {
+            id: ids.id(finding),
+            number: finding.id,
+            name: finding.name.clone(),
+            title: plain_title(finding),
+            severity: text(finding, "severity"),
+            cvss: text(finding, "cvss"),
+            status: text(finding, "status"),
+            hosts: finding.meta.get_str_list(hosts::FRONT_MATTER_KEY),
+            cwe: finding.meta.get_str_list("cwe"),
+            cve: finding.meta.get_str_list("cve"),
+            locations: finding.meta.get_str_list("locations"),
+            evidence: evidence::screenshots(finding)
+                .into_iter()
+                .map(|screenshot| Evidence {
+                    path: screenshot.path,
+                    caption: screenshot.caption,
+                })
+                .collect(),
+            body: finding.body.clone(),
+        }
+    }
+
+    /// Description and remediation as plain parts of the body, split at the
+    /// remediation heading. The title heading and placeholders like
+    /// `{{ affected_hosts }}` are left out.
+    pub fn description_and_remediation(&self) -> (String, String) {
+        let lines: Vec<&str> = self
+            .body
+            .lines()
+            .enumerate()
+            .filter(|(i, line)| {
+                let line = line.trim();
+                !(*i == 0 && line.starts_with('=')
+                    || line.starts_with("{{") && line.ends_with("}}"))
+            })
+            .map(|(_, line)| line)
+            .collect();
+        let heading = lines.iter().position(|line| {
+            let line = line.trim_start();
+            line.starts_with('=')
+                && REMEDIATION_HEADINGS.iter().any(|h| {
+                    line.trim_start_matches('=')
+                        .trim()
+                        .to_lowercase()
+                        .starts_with(h)
+                })
+        });
+        match heading {
+            Some(i) => (
+                lines[..i].join("\n").trim().to_string(),
+                lines[i + 1..].join("\n").trim().to_string(),
+            ),
+            None => (lines.join("\n").trim().to_string(), String::new()),
+        }
+    }
+}
+
+impl Report {
+    pub fn from(report: &report::Report) -> Self {
+        let ids = IdScheme::of(report);
+        Report {
+            title: report.metadata_value("report_title").map(str::to_string),
+            // The first occurrence of a key is the one in effect
+            metadata: report
+                .metadata
+                .iter()
+                .enumerate()
+                .filter(|(i, (key, _))| !report.metadata[..*i].iter().any(|(k, _)| k == key))
+                .map(|(_, pair)| pair.clone())
+                .collect(),
+            sections: report.sections.iter().map(Section::from).collect(),
+            findings: report
+                .findings
+                .iter()
+                .map(|finding| Finding::from(finding, &ids))
+                .collect(),
+            appendices: report.appendices.iter().map(Section::from).collect(),
+        }
+    }
+
+    /// The model as pretty-printed JSON, tagged with its `VERSION`
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Versioned<'a> {
+            version: u32,
+            #[serde(flatten)]
+            report: &'a Report,
+        }
+        serde_json::to_string_pretty(&Versioned {
+            version: VERSION,
+            report: self,
+        })
+        .expect("the report model serializes to JSON")
+    }
+}
+
+fn ordered_map<S: Serializer>(
+    pairs: &[(String, String)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(pairs.len()))?;
+    for (key, value) in pairs {
+        map.serialize_entry(key, value)?;
+    }
+    map.end()
+}
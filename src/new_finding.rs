@@ -1,17 +1,14 @@
-use std::{
-    error::Error,
-    fs::{read_dir, File},
-    io::Write,
-    path::PathBuf,
-    process::exit,
-};
+use std::{error::Error, fs::File, path::PathBuf, process::exit};
 
+use crate::changes::Changes;
 use crate::consts::*;
+use crate::report::{Kind, Report};
 
 pub fn new_finding(
     report_dir: Option<PathBuf>,
     name: Option<String>,
     template: Option<String>,
+    dry_run: bool,
 ) -> Result<(), Box<dyn Error>> {
     // Ensure user provided the report path
     let report_path = report_dir.unwrap_or_else(|| {
@@ -31,8 +28,8 @@ pub fn new_finding(
         exit(1);
     });
 
-    let findings_count = read_dir(report_path.join("findings"))?.count();
-    let new_finding_fname = format!("{}.{name}.typ", findings_count + 1);
+    let number = Report::load_or_exit(&report_path).next_id(Kind::Finding);
+    let new_finding_fname = format!("{number}.{name}.typ");
 
     // FIXME: this should not be necessary
     let existing_templates = ["xss"];
@@ -44,21 +41,12 @@ pub fn new_finding(
         }
     }
 
-    let mut f = File::options()
-        .create_new(true)
-        .write(true)
-        .open(report_path.join("findings").join(&new_finding_fname))?;
-
     // FIXME: make so it is not necessary to add code here on every template added
-    if let Some(template) = template {
+    let content = if let Some(template) = template {
         // Handle templates
         match template.as_str() {
-            "xss" => {
-                f.write_all(T_XSS.as_bytes())?;
-            }
-            "sql-injection" => {
-                f.write_all(T_SQL_INJECTION.as_bytes())?;
-            }
+            "xss" => T_XSS,
+            "sql-injection" => T_SQL_INJECTION,
             _ => {
                 eprintln!("ERROR: Invalid template: {template}");
                 exit(1);
@@ -66,7 +54,18 @@ pub fn new_finding(
         }
     } else {
         // Handle new default finding
-        f.write_all(T_FINDING.as_bytes())?;
+        T_FINDING
+    };
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.create(
+        report_path.join("findings").join(&new_finding_fname),
+        content,
+    );
+    changes.apply(dry_run)?;
+
+    if dry_run {
+        return Ok(());
     }
 
     println!("Added new finding \"{new_finding_fname}\"");
@@ -1,14 +1,21 @@
-use std::{
-    error::Error,
-    fs::{create_dir, File},
-    io::Write,
-    path::PathBuf,
-    process::exit,
-};
+use std::{error::Error, path::PathBuf, process::exit};
 
+use crate::changes::Changes;
+use crate::client;
 use crate::consts::*;
+use crate::engagement;
+use crate::git;
+use crate::questionnaire;
+use crate::report::set_metadata;
 
-pub fn new_report(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+pub fn new_report(
+    report_dir: Option<PathBuf>,
+    client: Option<String>,
+    git: bool,
+    kind: Option<String>,
+    interactive: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
     // Ensure user provided the report path
     let report_path = report_dir.unwrap_or_else(|| {
         eprintln!("ERROR: Report path not provided");
@@ -21,28 +28,122 @@ pub fn new_report(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
         exit(1);
     }
 
+    // Client profile prefilling the metadata and scope
+    let profile = client.map(|name| {
+        client::load(&name).unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        })
+    });
+
+    let kind = kind.map(|name| {
+        engagement::find(&name).unwrap_or_else(|| {
+            eprintln!(
+                "ERROR: Unknown engagement type \"{name}\", expected {}",
+                engagement::names()
+            );
+            exit(1);
+        })
+    });
+
+    // Client, engagement type, dates and scope tailoring the report
+    let answers = match interactive {
+        true => Some(questionnaire::ask_all(
+            profile.as_ref().map(|profile| profile.legal_name.as_str()),
+            kind,
+        )?),
+        false => None,
+    };
+    let engagement = answers.as_ref().map(|answers| answers.engagement).or(kind);
+
     // Create the file structure
-    create_dir(&report_path)?;
+    let mut changes = Changes::default();
 
-    File::create_new(report_path.join("metadata.typ"))?.write_all(T_METADATA.as_bytes())?;
+    changes.create_dir(report_path.clone());
 
-    create_dir(report_path.join("sections"))?;
+    let mut metadata = T_METADATA.to_string();
+    let mut scope = T_SCOPE.to_string();
+    if let Some(ref profile) = profile {
+        // The logo is copied so typst can access it
+        let logo = profile.logo.as_ref().map(|logo| {
+            let extension = logo.extension().unwrap_or_default().to_string_lossy();
+            let file_name = format!("client-logo.{extension}");
+            changes.copy(logo.clone(), report_path.join(&file_name));
+            file_name
+        });
+        metadata = profile.metadata(&metadata, logo.as_deref());
+    }
+    if let Some(ref answers) = answers {
+        metadata = answers.metadata(&metadata);
+        scope = answers.scope_section();
+    }
+    if let Some(engagement) = engagement {
+        metadata = set_metadata(&metadata, "report_title", engagement.title);
+        metadata = set_metadata(&metadata, "engagement_type", engagement.name);
+    }
+    if let Some(notes) = profile.and_then(|profile| profile.scope_notes) {
+        scope.push_str(&format!("\n{notes}\n"));
+    }
+    changes.create(report_path.join("metadata.typ"), &metadata);
 
-    File::create_new(report_path.join("sections").join("1.summary.typ"))?
-        .write_all(T_SUMMARY.as_bytes())?;
-    File::create_new(report_path.join("sections").join("2.scope.typ"))?
-        .write_all(T_SCOPE.as_bytes())?;
-    File::create_new(report_path.join("sections").join("3.methodology.typ"))?
-        .write_all(T_METHODOLOGY.as_bytes())?;
-    File::create_new(report_path.join("sections").join("4.example_section.typ"))?
-        .write_all(T_SECTION.as_bytes())?;
+    changes.create_dir(report_path.join("sections"));
 
-    create_dir(report_path.join("findings"))?;
+    changes.create(
+        report_path.join("sections").join("1.summary.typ"),
+        T_SUMMARY,
+    );
+    changes.create(report_path.join("sections").join("2.scope.typ"), &scope);
+    changes.create(
+        report_path.join("sections").join("3.methodology.typ"),
+        engagement.map_or(T_METHODOLOGY, |engagement| engagement.methodology),
+    );
+    match engagement {
+        Some(engagement) => {
+            for (i, (name, section)) in engagement.sections.iter().enumerate() {
+                changes.create(
+                    report_path
+                        .join("sections")
+                        .join(format!("{}.{name}.typ", i + 4)),
+                    section,
+                );
+            }
+        }
+        None => changes.create(
+            report_path.join("sections").join("4.example_section.typ"),
+            T_SECTION,
+        ),
+    }
 
-    File::create_new(report_path.join("findings").join("1.example_finding.typ"))?
-        .write_all(T_FINDING.as_bytes())?;
+    changes.create_dir(report_path.join("findings"));
 
-    println!("New report created successfully");
+    changes.create(
+        report_path.join("findings").join("1.example_finding.typ"),
+        T_FINDING,
+    );
+
+    if let Some(engagement) = engagement {
+        for dir in engagement.dirs {
+            changes.create_dir(report_path.join(dir));
+        }
+    }
+
+    if git {
+        changes.create(report_path.join(".gitignore"), T_GITIGNORE);
+    }
+
+    changes.apply(dry_run)?;
+
+    if git {
+        if dry_run {
+            println!("[dry-run] git init {}", report_path.display());
+        } else {
+            git::init(&report_path)?;
+        }
+    }
+
+    if !dry_run {
+        println!("New report created successfully");
+    }
 
     Ok(())
 }
@@ -1,17 +1,14 @@
-use std::{
-    error::Error,
-    fs::{read_dir, File},
-    io::Write,
-    path::PathBuf,
-    process::exit,
-};
+use std::{error::Error, fs::File, path::PathBuf, process::exit};
 
+use crate::changes::Changes;
 use crate::consts::*;
+use crate::report::{Kind, Report};
 
 pub fn new_section(
     report_dir: Option<PathBuf>,
     name: Option<String>,
     template: Option<String>,
+    dry_run: bool,
 ) -> Result<(), Box<dyn Error>> {
     // Ensure user provided the report path
     let report_path = report_dir.unwrap_or_else(|| {
@@ -31,8 +28,8 @@ pub fn new_section(
         exit(1);
     });
 
-    let sections_count = read_dir(report_path.join("sections"))?.count();
-    let new_section_fname = format!("{}.{name}.typ", sections_count + 1);
+    let number = Report::load_or_exit(&report_path).next_id(Kind::Section);
+    let new_section_fname = format!("{number}.{name}.typ");
 
     // FIXME: this should not be necessary
     let existing_templates = ["summary"];
@@ -44,27 +41,14 @@ pub fn new_section(
         }
     }
 
-    let mut f = File::options()
-        .create_new(true)
-        .write(true)
-        .open(report_path.join("sections").join(&new_section_fname))?;
-
     // FIXME: make so it is not necessary to add code here on every template added
-    if let Some(template) = template {
+    let content = if let Some(template) = template {
         // Handle templates
         match template.as_str() {
-            "default" => {
-                f.write_all(T_SECTION.as_bytes())?;
-            }
-            "summary" => {
-                f.write_all(T_SUMMARY.as_bytes())?;
-            }
-            "scope" => {
-                f.write_all(T_SCOPE.as_bytes())?;
-            }
-            "methodology" => {
-                f.write_all(T_METHODOLOGY.as_bytes())?;
-            }
+            "default" => T_SECTION,
+            "summary" => T_SUMMARY,
+            "scope" => T_SCOPE,
+            "methodology" => T_METHODOLOGY,
             _ => {
                 eprintln!("ERROR: Invalid template: {template}");
                 exit(1);
@@ -72,10 +56,62 @@ pub fn new_section(
         }
     } else {
         // Handle new default section
-        f.write_all(T_SECTION.as_bytes())?;
+        T_SECTION
+    };
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.create(
+        report_path.join("sections").join(&new_section_fname),
+        content,
+    );
+    changes.apply(dry_run)?;
+
+    if dry_run {
+        return Ok(());
     }
 
     println!("Added new section \"{new_section_fname}\"");
 
     Ok(())
 }
+
+/// Adds an appendix, creating the appendices directory on first use
+pub fn new_appendix(
+    report_dir: Option<PathBuf>,
+    name: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report_dir.unwrap_or_else(|| {
+        eprintln!("ERROR: Report path not provided");
+        exit(1);
+    });
+
+    if File::open(report_path.join("metadata.typ")).is_err() {
+        eprintln!("ERROR: Directory not a valid report");
+        exit(1);
+    }
+
+    let name = name.unwrap_or_else(|| {
+        eprintln!("ERROR: name not provided (--name)");
+        exit(1);
+    });
+
+    let number = Report::load_or_exit(&report_path).next_id(Kind::Appendix);
+    let appendices = report_path.join("appendices");
+    let mut changes = Changes::for_report(&report_path);
+    if !appendices.is_dir() {
+        changes.create_dir(appendices.clone());
+    }
+    let new_appendix_fname = format!("{number}.{name}.typ");
+
+    changes.create(appendices.join(&new_appendix_fname), T_APPENDIX);
+    changes.apply(dry_run)?;
+
+    if dry_run {
+        return Ok(());
+    }
+
+    println!("Added new appendix \"{new_appendix_fname}\"");
+
+    Ok(())
+}
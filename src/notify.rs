@@ -0,0 +1,100 @@
+use std::{fs::canonicalize, path::Path};
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::http::Client;
+use crate::report::Report;
+use crate::summary;
+
+// Team visibility of builds, e.g. of reports compiled in CI: whenever compile
+// built a report, `compile_webhook` of the config is posted its name,
+// version, finding counts and outputs. `text` is the message of Slack,
+// Mattermost and Teams incoming webhooks, other receivers read the fields.
+// Rebuilds of watch and the TUI don't notify, neither do
+// `compile --no-notify` and `--offline`.
+
+pub struct Notification {
+    webhook: String,
+    name: String,
+    version: Option<String>,
+    // Findings per severity level
+    counts: Vec<(String, usize)>,
+}
+
+impl Notification {
+    /// Notification of builds of `report`, none without `compile_webhook`
+    pub fn of(report_path: &Path, report: &Report, config: &Config) -> Option<Self> {
+        let webhook = config.compile_webhook.clone()?;
+        let name = report
+            .metadata_value("report_title")
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                canonicalize(report_path)
+                    .ok()
+                    .and_then(|path| {
+                        path.file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                    })
+                    .unwrap_or(report_path.display().to_string())
+            });
+        Some(Self {
+            webhook,
+            name,
+            version: report.metadata_value("version").map(str::to_string),
+            counts: summary::severity_counts(&report.findings, &config.severities()),
+        })
+    }
+
+    fn payload(&self, outputs: &[String]) -> Value {
+        // Absolute paths say where CI put the outputs
+        let outputs: Vec<String> = outputs
+            .iter()
+            .map(|output| {
+                canonicalize(output).map_or(output.clone(), |path| path.display().to_string())
+            })
+            .collect();
+
+        let mut text = format!("Compiled {}", self.name);
+        if let Some(ref version) = self.version {
+            text.push_str(&format!(" version {version}"));
+        }
+        let findings: Vec<String> = self
+            .counts
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(level, count)| format!("{count} {level}"))
+            .collect();
+        match findings.is_empty() {
+            true => text.push_str(", no findings"),
+            false => text.push_str(&format!(", findings: {}", findings.join(", "))),
+        }
+        text.push_str(&format!(" to {}", outputs.join(", ")));
+
+        let findings: Value = self
+            .counts
+            .iter()
+            .map(|(level, count)| (level.as_str(), json!(count)))
+            .collect();
+        json!({
+            "text": text,
+            "report": self.name,
+            "version": self.version,
+            "findings": findings,
+            "outputs": outputs,
+        })
+    }
+
+    /// Posts a build to `outputs`, a failure doesn't fail the build
+    pub fn send(&self, outputs: &[String], offline: bool) {
+        if offline {
+            println!("Compile webhook not notified in offline mode");
+            return;
+        }
+        let body = self.payload(outputs).to_string();
+        match Client::new(offline).post_json(&self.webhook, &[], &body) {
+            Ok(_) => println!("Compile webhook notified"),
+            Err(e) => eprintln!("WARNING: Failed to notify the compile webhook: {e}"),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::consts::*;
+use crate::report::Entry;
+use crate::toml::TableExt;
+use crate::utils::{typst_escape, typst_str};
+
+// Front-matter key listing the CVE IDs of a finding
+pub const FRONT_MATTER_KEY: &str = "cve";
+
+pub struct CveInfo {
+    pub id: String,
+    pub description: String,
+    pub cvss_score: Option<f64>,
+    pub cvss_severity: String,
+    pub cvss_vector: String,
+    pub references: Vec<String>,
+}
+
+impl CveInfo {
+    /// Reads the relevant fields from an NVD 2.0 `cve` object
+    pub fn from_json(cve: &Value) -> Option<Self> {
+        let id = cve["id"].as_str()?.to_string();
+
+        let description = cve["descriptions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|d| d["lang"] == "en")
+            .and_then(|d| d["value"].as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        // Prefer the newest CVSS version available
+        let metric = ["cvssMetricV31", "cvssMetricV30", "cvssMetricV2"]
+            .iter()
+            .find_map(|key| cve["metrics"][key].get(0));
+        let cvss_data = metric.map(|m| &m["cvssData"]);
+        let cvss_score = cvss_data.and_then(|d| d["baseScore"].as_f64());
+        let cvss_severity = cvss_data
+            .and_then(|d| d["baseSeverity"].as_str())
+            .or_else(|| metric.and_then(|m| m["baseSeverity"].as_str()))
+            .unwrap_or_default()
+            .to_string();
+        let cvss_vector = cvss_data
+            .and_then(|d| d["vectorString"].as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let references = cve["references"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|r| r["url"].as_str().map(str::to_string))
+            .collect();
+
+        Some(Self {
+            id,
+            description,
+            cvss_score,
+            cvss_severity,
+            cvss_vector,
+            references,
+        })
+    }
+
+    fn cvss(&self) -> String {
+        match self.cvss_score {
+            Some(score) => format!("{score:.1} {} {}", self.cvss_severity, self.cvss_vector)
+                .trim_end()
+                .to_string(),
+            None => "N/A".to_string(),
+        }
+    }
+}
+
+/// Finds all CVE IDs (e.g. CVE-2021-44228) in the given text, without duplicates
+pub fn find_cve_ids(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while let Some(pos) = text[i..].find("CVE-") {
+        let start = i + pos;
+        let mut end = start + 4;
+        let year_len = bytes[end..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if year_len == 4 && bytes.get(end + 4) == Some(&b'-') {
+            end += 5;
+            let seq_len = bytes[end..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            if seq_len >= 4 {
+                let id = text[start..end + seq_len].to_string();
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        i = start + 4;
+    }
+    ids
+}
+
+/// CVE IDs referenced by a finding, both in the front-matter and the content
+pub fn finding_cve_ids(entry: &Entry) -> Vec<String> {
+    let mut ids: Vec<String> = entry
+        .meta
+        .get_str_list(FRONT_MATTER_KEY)
+        .iter()
+        .map(|id| id.trim().to_uppercase())
+        .collect();
+    for id in find_cve_ids(&entry.body) {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+pub fn cache_path(report_path: &Path, id: &str) -> PathBuf {
+    report_path
+        .join(STATE_DIR)
+        .join(NVD_CACHE_DIR)
+        .join(format!("{id}.json"))
+}
+
+/// Reads a previously enriched CVE from the report cache
+pub fn load_cached(report_path: &Path, id: &str) -> Option<CveInfo> {
+    let content = read_to_string(cache_path(report_path, id)).ok()?;
+    CveInfo::from_json(&serde_json::from_str(&content).ok()?)
+}
+
+/// Template context for a finding with details of every enriched CVE it references
+///
+/// Provides `{{ cve_details }}` plus `{{ <CVE-ID>.description }}`, `{{ <CVE-ID>.cvss }}`
+/// and `{{ <CVE-ID>.references }}` for each CVE
+pub fn finding_context(report_path: &Path, entry: &Entry) -> Vec<(String, String)> {
+    let mut context = Vec::new();
+    let mut details = String::new();
+
+    for id in finding_cve_ids(entry) {
+        let Some(info) = load_cached(report_path, &id) else {
+            continue;
+        };
+
+        details.push_str(&format!(
+            "#table(\n  columns: 2,\n  [*CVE*], {},\n  [*CVSS*], {},\n  [*Description*], {},\n  [*References*], {},\n)\n",
+            typst_str(&info.id),
+            typst_str(&info.cvss()),
+            typst_str(&info.description),
+            typst_str(&info.references.join("\n")),
+        ));
+
+        let references: Vec<String> = info.references.iter().map(|r| typst_escape(r)).collect();
+        context.push((format!("{id}.description"), typst_escape(&info.description)));
+        context.push((format!("{id}.cvss"), typst_escape(&info.cvss())));
+        context.push((format!("{id}.references"), references.join(" \\\n")));
+    }
+
+    context.push(("cve_details".to_string(), details));
+    context
+}
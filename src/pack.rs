@@ -0,0 +1,249 @@
+use std::{
+    env,
+    error::Error,
+    fs::{copy, create_dir_all, read_dir, read_to_string, remove_dir_all, write, DirBuilder},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::{exit, Command, Stdio},
+};
+
+use crate::config::Config;
+use crate::consts::*;
+use crate::manifest;
+use crate::report;
+
+// Encrypted 7z bundle of a report for delivery and archival:
+//
+//  MANIFEST.sha256      sha256sum style hashes of every other file
+//  report.pdf           compiled report, if present
+//  <report>/...         report sources without the undo stack and caches
+//
+// The password goes to 7z on stdin, command lines are visible to other users.
+
+const MANIFEST_FILE: &str = "MANIFEST.sha256";
+// Local state of the working copy, left out of archives
+const LOCAL_DIRS: &[&str] = &[
+    UNDO_DIR,
+    SERVE_DIR,
+    HTTP_CACHE_DIR,
+    NVD_CACHE_DIR,
+    BUILD_CACHE_DIR,
+];
+
+/// Copies the report tree to `to`, adding every file to the manifest
+fn copy_tree(
+    from: &Path,
+    to: &Path,
+    base: &Path,
+    manifest: &mut String,
+) -> Result<(), Box<dyn Error>> {
+    create_dir_all(to)?;
+    for entry in read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Undo history, the PDF of serve and caches are local to the working copy
+        if LOCAL_DIRS
+            .iter()
+            .any(|dir| path.ends_with(Path::new(STATE_DIR).join(dir)))
+        {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_tree(&path, &target, base, manifest)?;
+        } else {
+            copy(&path, &target)?;
+            manifest.push_str(&manifest::line(&target, base)?);
+        }
+    }
+    Ok(())
+}
+
+/// Runs 7z with `-p`, answering its password prompts on stdin
+fn seven_zip(args: &[&str], dir: &Path, password: &str) -> Result<(), Box<dyn Error>> {
+    let mut child = Command::new("7z")
+        .args(args)
+        .arg("-p")
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            format!("Failed to execute 7z ({e})\nEnsure you have '7z' installed on your system")
+        })?;
+    // Creating an archive asks to verify the password
+    let mut stdin = child.stdin.take().ok_or("Failed to open 7z stdin")?;
+    let written = stdin.write_all(format!("{password}\n{password}\n").as_bytes());
+    drop(stdin);
+    let status = child.wait()?;
+    // Archives without a password are extracted without asking for it
+    match written {
+        Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(e.into()),
+        _ => {}
+    }
+    if !status.success() {
+        return Err(format!("7z failed ({status})").into());
+    }
+    Ok(())
+}
+
+fn stage(
+    report_path: &Path,
+    name: &str,
+    pdf: Option<&Path>,
+    staging: &Path,
+    archive: &Path,
+    password: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut manifest = String::new();
+    copy_tree(report_path, &staging.join(name), staging, &mut manifest)?;
+    match pdf {
+        Some(pdf) if pdf.exists() => {
+            let target = staging.join(pdf.file_name().unwrap_or_default());
+            copy(pdf, &target)?;
+            manifest.push_str(&manifest::line(&target, staging)?);
+        }
+        Some(pdf) => eprintln!(
+            "WARNING: Compiled report {} not found, packing the sources only",
+            pdf.display()
+        ),
+        None => {}
+    }
+    write(staging.join(MANIFEST_FILE), manifest)?;
+
+    // AES-256 with encrypted file names
+    let archive = archive.display().to_string();
+    seven_zip(&["a", "-t7z", "-mhe=on", &archive, "."], staging, password)
+}
+
+/// New temporary directory only the current user can access, failing
+/// rather than reusing one that exists
+fn staging_dir() -> Result<PathBuf, Box<dyn Error>> {
+    let mut bytes = [0u8; 8];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| format!("No random numbers for a directory name: {e}"))?;
+    let suffix: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    let staging = env::temp_dir().join(format!("reportgen-pack-{suffix}"));
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder
+        .create(&staging)
+        .map_err(|e| format!("Failed to create {}: {e}", staging.display()))?;
+    Ok(staging)
+}
+
+/// Directory name of a report
+fn name_of(report_path: &Path) -> Result<String, Box<dyn Error>> {
+    Ok(report_path
+        .canonicalize()?
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or("report".to_string()))
+}
+
+/// Packs the report sources and a manifest into an encrypted 7z archive at
+/// `archive`, e.g. to send them to a compile server
+pub fn pack_sources(
+    report_path: &Path,
+    archive: &Path,
+    password: &str,
+) -> Result<(), Box<dyn Error>> {
+    let staging = staging_dir()?;
+    let result = stage(
+        report_path,
+        &name_of(report_path)?,
+        None,
+        &staging,
+        archive,
+        password,
+    );
+    let _ = remove_dir_all(&staging);
+    result
+}
+
+/// Packs the report sources, compiled PDF and a manifest into an encrypted 7z archive
+pub fn pack(
+    report_dir: Option<PathBuf>,
+    output: Option<String>,
+    password: String,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let config = Config::load_or_exit(&report_path);
+
+    let name = name_of(&report_path)?;
+    let archive = env::current_dir()?.join(output.unwrap_or(format!("{name}.7z")));
+    if archive.exists() {
+        eprintln!("ERROR: {} already exists", archive.display());
+        exit(1);
+    }
+    // Compiled report at the configured output, relative to the working directory like compile
+    let pdf = PathBuf::from(config.output.as_deref().unwrap_or(DEFAULT_REPORT_FILE));
+
+    // Staged in a private temporary directory, removed on failure as well
+    let staging = staging_dir()?;
+    let result = stage(
+        &report_path,
+        &name,
+        Some(&pdf),
+        &staging,
+        &archive,
+        &password,
+    );
+    let _ = remove_dir_all(&staging);
+    result?;
+
+    println!("Report packed to {}", archive.display());
+
+    Ok(())
+}
+
+/// Extracts a packed archive to `dir` and verifies it against its manifest,
+/// returning the number of files
+pub fn extract(archive: &Path, dir: &Path, password: &str) -> Result<usize, Box<dyn Error>> {
+    create_dir_all(dir)?;
+
+    let archive = archive.display().to_string();
+    seven_zip(&["x", "-aos", &archive], dir, password)?;
+
+    let manifest = read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|e| format!("{MANIFEST_FILE} missing from the archive: {e}"))?;
+    let (count, problems) = manifest::check(dir, &manifest);
+    for problem in &problems {
+        eprintln!("ERROR: {problem}");
+    }
+    if !problems.is_empty() {
+        return Err(format!("{archive} doesn't match its manifest").into());
+    }
+
+    Ok(count)
+}
+
+/// Extracts a packed report to `dir` and verifies it against its manifest
+pub fn unpack(
+    archive: String,
+    dir: Option<PathBuf>,
+    password: String,
+) -> Result<(), Box<dyn Error>> {
+    let archive = env::current_dir()?.join(archive);
+    let dir = dir.unwrap_or_else(|| ".".into());
+    if dir.join(MANIFEST_FILE).exists() {
+        eprintln!(
+            "ERROR: {} already contains an unpacked report",
+            dir.display()
+        );
+        exit(1);
+    }
+
+    let count = extract(&archive, &dir, &password).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+
+    println!(
+        "Unpacked {count} files to {}, all hashes match",
+        dir.display()
+    );
+
+    Ok(())
+}
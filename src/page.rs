@@ -0,0 +1,92 @@
+use crate::config::Config;
+use crate::report::Report;
+use crate::utils::typst_str;
+
+// Table of contents and page layout of the built-in templates, set by the
+// metadata of the report or the config, the former taking precedence:
+//
+//  toc:false
+//  toc_depth:2
+//  page_numbering:i
+//  paper:us-letter
+//  page_margin:2.5cm
+
+const DEFAULT_NUMBERING: &str = "1 of 1";
+const DEFAULT_PAPER: &str = "a4";
+const UNITS: &[&str] = &["pt", "mm", "cm", "in", "em"];
+
+/// Whether `margin` is `auto` or a Typst length like `2.5cm`
+fn is_length(margin: &str) -> bool {
+    margin == "auto"
+        || UNITS.iter().any(|unit| {
+            margin
+                .strip_suffix(unit)
+                .is_some_and(|number| number.parse::<f64>().is_ok_and(|n| n >= 0.0))
+        })
+}
+
+/// Typst paper name, accepting `letter` and `legal` for the US sizes
+fn paper(name: &str) -> Result<String, String> {
+    let name = name.to_lowercase();
+    let name = match name.as_str() {
+        "letter" | "legal" => format!("us-{name}"),
+        _ => name,
+    };
+    match !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        true => Ok(name),
+        false => Err(format!(
+            "Invalid paper size \"{name}\", e.g. a4 or us-letter"
+        )),
+    }
+}
+
+/// `toc`, `toc_depth`, `page_numbering`, `paper` and `page_margin`
+/// placeholders, `report` is `None` for previews without a report
+pub fn context(report: Option<&Report>, config: &Config) -> Result<Vec<(String, String)>, String> {
+    let metadata = |key: &str| report.and_then(|r| r.metadata_value(key));
+
+    let toc = match metadata("toc") {
+        Some("true") => true,
+        Some("false") => false,
+        Some(other) => return Err(format!("toc has to be true or false, got \"{other}\"")),
+        None => config.toc.unwrap_or(true),
+    };
+    let toc_depth = match metadata("toc_depth") {
+        Some(depth) => match depth.parse::<usize>() {
+            Ok(depth) if depth > 0 => Some(depth),
+            _ => {
+                return Err(format!(
+                    "toc_depth has to be a positive number, got \"{depth}\""
+                ))
+            }
+        },
+        None => config.toc_depth,
+    };
+    let numbering = metadata("page_numbering")
+        .or(config.page_numbering.as_deref())
+        .unwrap_or(DEFAULT_NUMBERING);
+    let paper = paper(
+        metadata("paper")
+            .or(config.paper.as_deref())
+            .unwrap_or(DEFAULT_PAPER),
+    )?;
+    let margin = metadata("page_margin")
+        .or(config.page_margin.as_deref())
+        .unwrap_or("auto");
+    if !is_length(margin) {
+        return Err(format!(
+            "page_margin has to be a length like 2.5cm or auto, got \"{margin}\""
+        ));
+    }
+
+    Ok(vec![
+        ("toc".to_string(), toc.to_string()),
+        (
+            "toc_depth".to_string(),
+            toc_depth.map_or("none".to_string(), |depth| depth.to_string()),
+        ),
+        ("page_numbering".to_string(), typst_str(numbering)),
+        ("paper".to_string(), typst_str(&paper)),
+        ("page_margin".to_string(), margin.to_string()),
+    ])
+}
@@ -0,0 +1,111 @@
+use std::{fs::read_to_string, path::Path};
+
+use crate::config::Config;
+use crate::consts::{PARTIALS, THEMES};
+
+// Templates are composed of partials, included with `{{> name }}`: the page
+// setup with header and footer, table styles, the cover page and the layout
+// of every finding. A company overrides just the parts it needs by putting
+// files named after them into the directory of `partials` in the config, the
+// others come from the theme:
+//
+//  partials = "templates/partials"
+//  theme = "modern"
+//
+//  templates/partials/cover.typ
+//
+// Themes replace some of the built-in partials, "classic" being the built-in
+// ones. Partials can include other partials. The finding layout gets the
+// rendered finding as `{{ finding }}`.
+
+const OPEN: &str = "{{>";
+const CLOSE: &str = "}}";
+// Deepest nesting of partials, deeper ones most likely include themselves
+const MAX_DEPTH: usize = 8;
+
+pub const DEFAULT_THEME: &str = "classic";
+
+/// Names of the built-in themes
+pub fn themes() -> Vec<&'static str> {
+    std::iter::once(DEFAULT_THEME)
+        .chain(THEMES.iter().map(|(name, _)| *name))
+        .collect()
+}
+
+/// Content of the partial `name`: the override, the theme's or the built-in one
+fn load(name: &str, dir: Option<&Path>, theme: &str) -> Result<String, String> {
+    if let Some(dir) = dir {
+        let path = dir.join(format!("{name}.typ"));
+        if path.is_file() {
+            return read_to_string(&path)
+                .map_err(|e| format!("Failed to read partial {}: {e}", path.display()));
+        }
+    }
+    let themed = match theme {
+        DEFAULT_THEME => &[][..],
+        _ => THEMES
+            .iter()
+            .find(|(name, _)| *name == theme)
+            .map(|(_, partials)| *partials)
+            .ok_or(format!(
+                "Unknown theme \"{theme}\", expected one of {:?}",
+                themes()
+            ))?,
+    };
+    themed
+        .iter()
+        .chain(PARTIALS)
+        .find(|(partial, _)| *partial == name)
+        .map(|(_, content)| content.to_string())
+        .ok_or(format!(
+            "Unknown partial \"{name}\", expected one of {:?} or a file in the partials directory",
+            PARTIALS.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+        ))
+}
+
+fn include(
+    template: &str,
+    dir: Option<&Path>,
+    theme: &str,
+    depth: usize,
+) -> Result<String, String> {
+    let mut resolved = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(OPEN) {
+        let Some(end) = rest[start..].find(CLOSE) else {
+            break;
+        };
+        let name = rest[start + OPEN.len()..start + end].trim();
+        if depth == MAX_DEPTH {
+            return Err(format!(
+                "Partial \"{name}\" is nested more than {MAX_DEPTH} levels deep, does it include itself?"
+            ));
+        }
+        let partial = include(&load(name, dir, theme)?, dir, theme, depth + 1)?;
+        resolved.push_str(&rest[..start]);
+        // Partials end with a newline, the include line has its own
+        resolved.push_str(partial.strip_suffix('\n').unwrap_or(&partial));
+        rest = &rest[start + end + CLOSE.len()..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// `template` with the partials of `theme` included
+pub fn resolve_themed(template: &str, config: &Config, theme: &str) -> Result<String, String> {
+    include(template, config.partials.as_deref(), theme, 0)
+}
+
+/// `template` with its partials included
+pub fn resolve(template: &str, config: &Config) -> Result<String, String> {
+    resolve_themed(
+        template,
+        config,
+        config.theme.as_deref().unwrap_or(DEFAULT_THEME),
+    )
+}
+
+/// The partial `name` with its partials included
+pub fn partial(name: &str, config: &Config) -> Result<String, String> {
+    resolve(&format!("{OPEN} {name} {CLOSE}\n"), config)
+}
@@ -0,0 +1,201 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use crate::assets;
+use crate::brand::{self, Brand};
+use crate::compile_report::{compile_to_file, render_section, FindingRenderer};
+use crate::config::Config;
+use crate::consts::{DEFAULT_PREVIEW_FILE, MAIN_TEMPLATE};
+use crate::page;
+use crate::partials;
+use crate::report::{self, Kind, Report};
+use crate::risk;
+use crate::scope;
+use crate::snippets;
+use crate::summary;
+use crate::template::Template;
+use crate::trend;
+use crate::utils::get_report_date;
+use crate::xref;
+
+// Compiles a single section, finding or appendix to a small PDF for fast
+// iteration on it, e.g. `preview finding 3`. The document gets the page and
+// text rules at the top of the main template, cover, outline and the other
+// entries are left out. Links to anything outside the entry point nowhere.
+
+/// Invisible targets for links to labels the document doesn't define, e.g.
+/// other findings or the host appendix, typst fails on them otherwise
+fn missing_targets(document: &str) -> String {
+    let mut labels: Vec<&str> = Vec::new();
+    for (start, _) in document.match_indices("link(<") {
+        let rest = &document[start + "link(<".len()..];
+        let Some(end) = rest.find('>') else {
+            continue;
+        };
+        let label = &rest[..end];
+        let target = format!("<{label}>");
+        let links = document.matches(&format!("link({target}")).count();
+        if document.matches(&target).count() == links && !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    labels
+        .iter()
+        .map(|label| format!("#metadata(none) <{label}>\n"))
+        .collect()
+}
+
+/// Leading `#set` and `#show` rules of a template, the styling of the document
+fn preamble(template: &str) -> String {
+    let mut preamble = String::new();
+    // Open brackets of the current rule, rules may span several lines
+    let mut depth = 0i32;
+    for line in template.lines() {
+        let trimmed = line.trim();
+        if depth == 0
+            && !trimmed.is_empty()
+            && !trimmed.starts_with("#set")
+            && !trimmed.starts_with("#show")
+        {
+            break;
+        }
+        depth += line.matches(['(', '[', '{']).count() as i32;
+        depth -= line.matches([')', ']', '}']).count() as i32;
+        preamble.push_str(line);
+        preamble.push('\n');
+    }
+    preamble
+}
+
+pub fn preview(
+    kind: Kind,
+    id: usize,
+    report_dir: Option<PathBuf>,
+    output: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let mut report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    if let Err(e) = snippets::resolve(&mut report, &config) {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+    let Some(entry) = report.entries_of(kind).iter().find(|entry| entry.id == id) else {
+        eprintln!(
+            "ERROR: No {}{id} in {}",
+            kind.prefix(),
+            report_path.display()
+        );
+        exit(1);
+    };
+
+    let content = match kind {
+        Kind::Finding => {
+            let assets = assets::load(&report_path).unwrap_or_else(|e| {
+                eprintln!("ERROR: {e}");
+                exit(1);
+            });
+            FindingRenderer::new(&report, &config, &assets)
+                .unwrap_or_else(|e| {
+                    eprintln!("ERROR: {e}");
+                    exit(1);
+                })
+                .render(&report, entry)
+        }
+        Kind::Section | Kind::Appendix => render_section(entry, 0),
+    };
+    let template = match config.template {
+        Some(ref path) => read_to_string(path)
+            .map_err(|e| format!("Failed to read template {}: {e}", path.display()))?,
+        None => MAIN_TEMPLATE.to_string(),
+    };
+    let template = partials::resolve(&template, &config).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let brand = match config.brand {
+        Some(ref name) => brand::load(name),
+        None => Ok(Brand::default()),
+    }
+    .and_then(|brand| brand.context(&report.path, true))
+    .unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    let page_layout = page::context(Some(&report), &config).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+
+    // Placeholders sections may use, see compile_report
+    let severities = config.severities();
+    let presentation = config.severity_presentation();
+    let ids = xref::IdScheme::of(&report);
+    let current_date = get_report_date(
+        report.metadata_value("report_date"),
+        report
+            .metadata_value("date_format")
+            .or(config.date_format.as_deref()),
+        report.metadata_value("locale").or(config.locale.as_deref()),
+    );
+    let executive_summary = summary::executive_summary(&report, &severities, &presentation);
+    let statistics = summary::statistics(&report, &severities, &presentation);
+    let findings_overview = summary::findings_overview(&report, &ids, &presentation);
+    let risk_matrix = risk::render_matrix(&report, &ids);
+    let severity_legend = presentation.legend(&severities);
+    let severity_trend =
+        trend::render_chart(&report, &severities, &presentation).unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        });
+    let scope_definition = match scope::load(&report_path) {
+        Ok(scope) => scope.as_ref().map(scope::Scope::render).unwrap_or_default(),
+        Err(e) => {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        }
+    };
+    let mut context: Vec<(&str, &str)> = vec![
+        ("current_date", &current_date),
+        ("git_commit", ""),
+        ("executive_summary", &executive_summary),
+        ("findings_overview", &findings_overview),
+        ("risk_matrix", &risk_matrix),
+        ("severity_trend", &severity_trend),
+        ("severity_legend", &severity_legend),
+        ("scope_definition", &scope_definition),
+    ];
+    for (key, value) in statistics.iter().chain(&brand).chain(&page_layout) {
+        context.push((key, value));
+    }
+    for (key, value) in &report.metadata {
+        context.push((key, value));
+    }
+
+    let document =
+        Template::from_str(&format!("{}\n{content}", preamble(&template))).render(&context);
+    let mut document = xref::resolve(&document, &report).unwrap_or_else(|dangling| {
+        for (_, label) in dangling {
+            eprintln!("ERROR: Reference to unknown finding {label}");
+        }
+        exit(1);
+    });
+    document.push_str(&missing_targets(&document));
+
+    let output = output.unwrap_or(DEFAULT_PREVIEW_FILE.to_string());
+    let font_paths = config.font_paths.clone().unwrap_or_default();
+    if let Err(e) = compile_to_file(
+        &report_path,
+        &document,
+        &output,
+        "pdf",
+        config.typst(),
+        &font_paths,
+        None,
+    ) {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+    println!("Preview compiled to {output}");
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::{
+    error::Error,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use crate::changes::Changes;
+use crate::front_matter;
+use crate::github;
+use crate::gitlab;
+use crate::jira;
+use crate::model::{self, Finding};
+use crate::report::{Entry, Report};
+use crate::sla::CLOSED_STATUSES;
+
+// Findings pushed to an issue tracker, one ticket each, so remediation is
+// tracked where the developers of the client work:
+//
+//  report-generator push jira --project SEC
+//  report-generator push github --repo acme/shop
+//  report-generator push gitlab --repo acme/shop
+//
+// The key of a created ticket is stored in the front-matter of its finding,
+// pushing again only creates tickets for new findings. Closed findings are
+// left out.
+
+const TARGETS: &[&str] = &["jira", "github", "gitlab"];
+
+/// Findings without a ticket stored under `key` that aren't closed, with their entries
+pub fn pending<'a>(
+    report: &'a Report,
+    model: &'a model::Report,
+    key: &str,
+) -> Vec<(&'a Entry, &'a Finding)> {
+    report
+        .findings
+        .iter()
+        .zip(&model.findings)
+        .filter(|(entry, finding)| {
+            !entry.meta.contains_key(key)
+                && finding
+                    .status
+                    .as_deref()
+                    .is_none_or(|status| !CLOSED_STATUSES.contains(&status.to_lowercase().as_str()))
+        })
+        .collect()
+}
+
+/// Where a ticket comes from, e.g. `FND-001 of Web Application Test, version 1.0`
+pub fn reference(finding: &Finding, report: &model::Report) -> String {
+    let mut reference = finding.id.clone();
+    if let Some(ref title) = report.title {
+        reference.push_str(&format!(" of {title}"));
+    }
+    if let Some((_, version)) = report.metadata.iter().find(|(key, _)| key == "version") {
+        reference.push_str(&format!(", version {version}"));
+    }
+    reference
+}
+
+/// Ticket body in Markdown, the markup of GitHub and GitLab issues
+pub fn markdown(finding: &Finding, report: &model::Report) -> String {
+    let (description, remediation) = finding.description_and_remediation();
+    let mut text = description;
+    if !remediation.is_empty() {
+        text.push_str(&format!("\n\n## Remediation\n\n{remediation}"));
+    }
+    text.push_str("\n\n---\n\n");
+    if let Some(ref severity) = finding.severity {
+        text.push_str(&format!("- **Severity:** {severity}\n"));
+    }
+    if let Some(ref cvss) = finding.cvss {
+        text.push_str(&format!("- **CVSS:** {cvss}\n"));
+    }
+    if !finding.cwe.is_empty() {
+        text.push_str(&format!("- **CWE:** {}\n", finding.cwe.join(", ")));
+    }
+    if !finding.hosts.is_empty() {
+        text.push_str(&format!(
+            "- **Affected hosts:** {}\n",
+            finding.hosts.join(", ")
+        ));
+    }
+    text.push_str(&format!("- **Finding:** {}\n", reference(finding, report)));
+    text
+}
+
+/// Creates a ticket per pending finding with `create`, which returns the key
+/// of the new ticket, and stores the keys under `key` in the front-matter.
+/// Keys of the tickets created before a failure are stored all the same.
+pub fn create_tickets(
+    report_path: &Path,
+    pending: &[(&Entry, &Finding)],
+    key: &str,
+    tracker: &str,
+    mut create: impl FnMut(&Finding) -> Result<String, Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut changes = Changes::for_report(report_path);
+    let mut failed = None;
+    for (entry, finding) in pending {
+        match create(finding) {
+            Ok(ticket) => {
+                let content = front_matter::set(&read_to_string(&entry.path)?, key, &ticket);
+                changes.write(entry.path.clone(), &content);
+                println!("Created {ticket} for {} {}", finding.id, finding.title);
+            }
+            Err(e) => {
+                failed = Some(format!(
+                    "Failed to create a {tracker} issue for {}: {e}",
+                    finding.id
+                ));
+                break;
+            }
+        }
+    }
+    changes.apply(false)?;
+    if let Some(e) = failed {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+    Ok(())
+}
+
+pub fn push(
+    target: String,
+    report_dir: Option<PathBuf>,
+    project: Option<String>,
+    repo: Option<String>,
+    dry_run: bool,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
+    match target.as_str() {
+        "jira" => jira::push(report_dir, project, dry_run, offline),
+        "github" => github::push(report_dir, repo, dry_run, offline),
+        "gitlab" => gitlab::push(report_dir, repo, dry_run, offline),
+        _ => {
+            eprintln!(
+                "ERROR: Unknown push target \"{target}\", expected {}",
+                TARGETS.join(", ")
+            );
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::config::Config;
+use crate::report::{self, Report};
+
+// Internal QA checklist and delivery state of a report, kept in `metadata.typ`:
+//
+//  qa_passed:spelling, severity_review
+//  state:qa
+//
+// The items come from `qa_checklist` in the config, a report can only be
+// set to delivered once every item passed.
+
+pub const DEFAULT_CHECKLIST: &[&str] = &["spelling", "severity_review", "redaction"];
+
+// Report states in workflow order, reports without `state` are drafts
+pub const STATES: &[&str] = &["draft", "qa", "delivered"];
+
+/// Checklist items marked as passed
+pub fn passed(report: &Report) -> Vec<String> {
+    report
+        .metadata_value("qa_passed")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Checklist items that haven't passed yet
+pub fn pending(report: &Report, checklist: &[String]) -> Vec<String> {
+    let passed = passed(report);
+    checklist
+        .iter()
+        .filter(|item| !passed.contains(item))
+        .cloned()
+        .collect()
+}
+
+fn write_metadata(
+    report: &Report,
+    key: &str,
+    value: &str,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let path = report.path.join("metadata.typ");
+    let content = report::set_metadata(&read_to_string(&path)?, key, value);
+
+    let mut changes = Changes::for_report(&report.path);
+    changes.write(path, &content);
+    changes.apply(dry_run)?;
+    Ok(())
+}
+
+/// Marks a checklist item as passed (`set`) or not passed (`unset`)
+pub fn qa(
+    report_dir: Option<PathBuf>,
+    action: String,
+    item: String,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let checklist = Config::load_or_exit(&report_path).qa_checklist();
+
+    if !checklist.contains(&item) {
+        eprintln!(
+            "ERROR: Unknown QA item \"{item}\", expected one of {}",
+            checklist.join(", ")
+        );
+        exit(1);
+    }
+
+    let mut passed = passed(&report);
+    match action.as_str() {
+        "set" if !passed.contains(&item) => passed.push(item.clone()),
+        "set" => {}
+        "unset" => passed.retain(|p| p != &item),
+        _ => {
+            eprintln!("ERROR: Unknown action \"{action}\", expected set or unset");
+            exit(1);
+        }
+    }
+    // Checklist order, items removed from the config are dropped
+    let passed: Vec<&str> = checklist
+        .iter()
+        .filter(|c| passed.contains(c))
+        .map(String::as_str)
+        .collect();
+    write_metadata(&report, "qa_passed", &passed.join(", "), dry_run)?;
+
+    if !dry_run {
+        println!("QA: {}/{} passed", passed.len(), checklist.len());
+    }
+
+    Ok(())
+}
+
+/// Sets the report state, delivered requires the whole QA checklist to pass
+pub fn state(
+    report_dir: Option<PathBuf>,
+    action: String,
+    state: String,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    if action != "set" {
+        eprintln!("ERROR: Unknown action \"{action}\", expected set");
+        exit(1);
+    }
+    if !STATES.contains(&state.as_str()) {
+        eprintln!(
+            "ERROR: Invalid state \"{state}\", expected one of {}",
+            STATES.join(", ")
+        );
+        exit(1);
+    }
+
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    if state == "delivered" {
+        let checklist = Config::load_or_exit(&report_path).qa_checklist();
+        let pending = pending(&report, &checklist);
+        if !pending.is_empty() {
+            eprintln!("ERROR: QA checklist incomplete, pending:");
+            for item in pending {
+                eprintln!("  {item}");
+            }
+            exit(1);
+        }
+    }
+
+    write_metadata(&report, "state", &state, dry_run)?;
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::report::Entry;
+
+// Completeness heuristic for finding write-ups. Every criterion met adds an
+// equal share to a score out of 100.
+
+// Minimum number of words of a reasonably detailed finding
+const MIN_WORDS: usize = 150;
+
+// Criterion name with the keywords of a heading covering it
+const CRITERIA: &[(&str, &[&str])] = &[
+    ("impact", &["impact", "risk"]),
+    (
+        "remediation",
+        &["remediation", "recommendation", "mitigation", "fix"],
+    ),
+    (
+        "evidence",
+        &["evidence", "proof of concept", "poc", "reproduce", "steps"],
+    ),
+    ("references", &["reference", "further reading"]),
+];
+
+pub struct Quality {
+    pub score: usize,
+    pub missing: Vec<&'static str>,
+}
+
+fn headings(body: &str) -> Vec<String> {
+    body.lines()
+        .filter(|line| line.trim_start().starts_with('='))
+        .map(|line| line.trim_start_matches(['=', ' ']).to_lowercase())
+        .collect()
+}
+
+pub fn score(finding: &Entry) -> Quality {
+    let headings = headings(&finding.body);
+    let mut missing = Vec::new();
+
+    for (name, keywords) in CRITERIA {
+        let covered = headings
+            .iter()
+            .any(|heading| keywords.iter().any(|k| heading.contains(k)));
+        // Images and links count as evidence and references even without a heading
+        let implied = match *name {
+            "evidence" => finding.body.contains("image("),
+            "references" => finding.body.contains("http://") || finding.body.contains("https://"),
+            _ => false,
+        };
+        if !covered && !implied {
+            missing.push(*name);
+        }
+    }
+
+    if finding.body.split_whitespace().count() < MIN_WORDS {
+        missing.push("length");
+    }
+
+    let criteria = CRITERIA.len() + 1;
+    Quality {
+        score: (criteria - missing.len()) * 100 / criteria,
+        missing,
+    }
+}
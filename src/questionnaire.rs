@@ -0,0 +1,143 @@
+use std::{
+    error::Error,
+    io::{stdin, stdout, Write},
+};
+
+use chrono::NaiveDate;
+
+use crate::engagement::{self, Engagement, ENGAGEMENTS};
+use crate::report;
+use crate::utils::typst_escape;
+
+// Questions of `new --interactive`, answered on the terminal. Invalid answers
+// are asked again, an empty answer takes the default in brackets.
+
+pub struct Answers {
+    pub client: String,
+    pub engagement: &'static Engagement,
+    // YYYY-MM-DD
+    pub start: String,
+    pub end: String,
+    pub scope: Vec<String>,
+}
+
+/// Line read from the terminal, failing at the end of input
+fn read_line() -> Result<String, Box<dyn Error>> {
+    let mut line = String::new();
+    if stdin().read_line(&mut line)? == 0 {
+        return Err("Questionnaire aborted".into());
+    }
+    Ok(line.trim().to_string())
+}
+
+fn ask(question: &str, default: Option<&str>) -> Result<String, Box<dyn Error>> {
+    loop {
+        match default {
+            Some(default) => print!("{question} [{default}]: "),
+            None => print!("{question}: "),
+        }
+        stdout().flush()?;
+        let answer = read_line()?;
+        match (answer.is_empty(), default) {
+            (false, _) => return Ok(answer),
+            (true, Some(default)) => return Ok(default.to_string()),
+            (true, None) => println!("An answer is required"),
+        }
+    }
+}
+
+fn ask_date(question: &str, default: Option<NaiveDate>) -> Result<NaiveDate, Box<dyn Error>> {
+    let default = default.map(|date| date.format("%Y-%m-%d").to_string());
+    loop {
+        let answer = ask(question, default.as_deref())?;
+        match NaiveDate::parse_from_str(&answer, "%Y-%m-%d") {
+            Ok(date) => return Ok(date),
+            Err(_) => println!("Expected a date like 2024-05-31"),
+        }
+    }
+}
+
+/// Asks the questions, `client` is the default client name. The engagement
+/// type is only asked for without `kind`.
+pub fn ask_all(
+    client: Option<&str>,
+    kind: Option<&'static Engagement>,
+) -> Result<Answers, Box<dyn Error>> {
+    let client = ask("Client name", client)?;
+
+    let engagement = match kind {
+        Some(engagement) => engagement,
+        None => loop {
+            let answer = ask(
+                &format!("Engagement type ({})", engagement::names()),
+                Some(ENGAGEMENTS[0].name),
+            )?;
+            match engagement::find(&answer) {
+                Some(engagement) => break engagement,
+                None => println!("Expected one of {}", engagement::names()),
+            }
+        },
+    };
+
+    let start = ask_date("Start date (YYYY-MM-DD)", None)?;
+    let end = loop {
+        let end = ask_date("End date (YYYY-MM-DD)", Some(start))?;
+        match end < start {
+            true => println!("The end date can't be before the start date"),
+            false => break end,
+        }
+    };
+
+    println!("Targets in scope, one per line, an empty line to finish:");
+    let mut scope = Vec::new();
+    loop {
+        print!("- ");
+        stdout().flush()?;
+        let target = read_line()?;
+        if target.is_empty() {
+            break;
+        }
+        scope.push(target);
+    }
+
+    Ok(Answers {
+        client,
+        engagement,
+        start: start.format("%Y-%m-%d").to_string(),
+        end: end.format("%Y-%m-%d").to_string(),
+        scope,
+    })
+}
+
+impl Answers {
+    /// `metadata` with the client and dates filled in
+    pub fn metadata(&self, metadata: &str) -> String {
+        let mut metadata = metadata.to_string();
+        for (key, value) in [
+            ("prepared_for", typst_escape(&self.client)),
+            ("engagement_start", self.start.clone()),
+            ("engagement_end", self.end.clone()),
+        ] {
+            metadata = report::set_metadata(&metadata, key, &value);
+        }
+        metadata
+    }
+
+    pub fn scope_section(&self) -> String {
+        let mut section = format!(
+            "= Scope\nThe {} for {} took place from {} to {}.",
+            typst_escape(self.engagement.title),
+            typst_escape(&self.client),
+            self.start,
+            self.end
+        );
+        if !self.scope.is_empty() {
+            section.push_str(" The following targets were in scope:\n");
+        }
+        section.push('\n');
+        for target in &self.scope {
+            section.push_str(&format!("- {}\n", typst_escape(target)));
+        }
+        section
+    }
+}
@@ -0,0 +1,110 @@
+use crate::diagnostics::front_matter_key;
+use crate::front_matter;
+use crate::report::{Entry, Report};
+use crate::toml::TableExt;
+use crate::utils::typst_escape;
+
+// Relationships between findings, declared by number in the front-matter:
+//
+//  +++
+//  related = [3]
+//  chained_with = [5, 6]
+//  +++
+
+// Front-matter key with the label of its block line
+pub const KEYS: &[(&str, &str)] = &[
+    ("related", "Related findings"),
+    ("chained_with", "Chained with"),
+    ("duplicate_of", "Duplicate of"),
+];
+
+/// Findings `finding` is related to under `key`, numbers without a finding are returned separately
+pub fn targets<'a>(report: &'a Report, finding: &Entry, key: &str) -> (Vec<&'a Entry>, Vec<i64>) {
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    for id in finding.meta.get_int_list(key) {
+        match report.findings.iter().find(|f| f.id as i64 == id) {
+            Some(target) => found.push(target),
+            None => missing.push(id),
+        }
+    }
+    (found, missing)
+}
+
+/// "Related findings" block listing the relationships of `finding`, empty without any
+pub fn render(report: &Report, finding: &Entry) -> String {
+    let mut lines = Vec::new();
+    for (key, label) in KEYS {
+        let (targets, _) = targets(report, finding, key);
+        if targets.is_empty() {
+            continue;
+        }
+        let titles: Vec<String> = targets
+            .iter()
+            .map(|f| {
+                let title = f.title();
+                let title = title.trim_start_matches("Finding:").trim();
+                format!("{} ({})", typst_escape(title), f.id)
+            })
+            .collect();
+        lines.push(format!("  *{label}:* {}", titles.join(", ")));
+    }
+
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n#block(inset: 8pt, stroke: 0.5pt + gray)[\n{}\n]\n",
+        lines.join(" \\\n")
+    )
+}
+
+/// Rewrites the finding numbers of relationship keys in the front-matter of `content`
+/// after findings were renumbered from the first to the second number of `ids`
+pub fn renumber(content: &str, ids: &[(usize, usize)]) -> String {
+    let (Some(block), body) = front_matter::split(content) else {
+        return content.to_string();
+    };
+
+    let mut lines = String::new();
+    for line in block.split_inclusive('\n') {
+        let is_relation =
+            front_matter_key(line).is_some_and(|k| KEYS.iter().any(|(key, _)| *key == k));
+        if !is_relation {
+            lines.push_str(line);
+            continue;
+        }
+        let (key, value) = line.split_once('=').unwrap_or((line, ""));
+        lines.push_str(key);
+        lines.push('=');
+        // Replace every number in the value
+        let mut number = String::new();
+        for c in value.chars().chain([' ']) {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            if !number.is_empty() {
+                let id = number.parse::<usize>().unwrap_or_default();
+                let id = ids
+                    .iter()
+                    .find(|(from, _)| *from == id)
+                    .map_or(id, |(_, to)| *to);
+                lines.push_str(&id.to_string());
+                number.clear();
+            }
+            lines.push(c);
+        }
+        // Drop the padding char again
+        lines.pop();
+    }
+    if lines == block {
+        return content.to_string();
+    }
+
+    format!(
+        "{}\n{lines}{}\n{body}",
+        front_matter::DELIMITER,
+        front_matter::DELIMITER
+    )
+}
@@ -0,0 +1,353 @@
+use std::{
+    env,
+    error::Error,
+    fs::{create_dir_all, read, read_dir, read_to_string, remove_dir_all, remove_file, write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{self, exit, Command},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use crate::compile_report::Options;
+use crate::config::Config;
+use crate::consts::{CONFIG_FILE, DEFAULT_REPORT_FILE};
+use crate::cover;
+use crate::http::Client;
+use crate::pack;
+use crate::report::{self, Report};
+use crate::serve::{self, Request};
+use crate::toml;
+use crate::toml::TableExt;
+use crate::trend;
+
+// Compiling on a server for those who can't install typst or the fonts of a
+// report: `serve --api` accepts reports packed like `pack` does and answers
+// with the compiled PDF, `compile --remote <url>` sends the report there and
+// saves the PDF like compile does.
+//
+//  report-generator serve --api --port 8000
+//  report-generator compile --remote http://build.acme.internal:8000
+//
+// Both sides share the token in REPORTGEN_TOKEN, which authorizes requests
+// and encrypts the archive. The server listens on localhost unless given
+// `--bind 0.0.0.0`, put it behind TLS on networks that aren't trusted.
+//
+// Uploaded reports only get to read their own files: their config and
+// metadata can't set the typst binary or paths outside the upload, links are
+// refused and typst runs in the report directory, its root for file access.
+
+pub const TOKEN_VAR: &str = "REPORTGEN_TOKEN";
+
+const ENDPOINT: &str = "/compile";
+// Largest packed report the server accepts
+const MAX_ARCHIVE: usize = 256 * 1024 * 1024;
+// Requests compiled at once, more are turned away
+const MAX_CONNECTIONS: usize = 4;
+// Longest wait for the next bytes of a request
+const TIMEOUT: Duration = Duration::from_secs(30);
+// Config keys that would run programs of the upload
+const EXECUTABLE_KEYS: &[&str] = &["typst"];
+// Config keys with paths, relative to the config file
+const PATH_KEYS: &[&str] = &["template", "clean_template", "partials", "snippets"];
+const PATH_LIST_KEYS: &[&str] = &["font_paths", "watch_paths"];
+// Metadata keys with paths, relative to the report
+const METADATA_PATH_KEYS: &[&str] = &[cover::LOGO_KEY, trend::PREVIOUS_REPORT_KEY];
+// Compile flags sent as query parameters
+const FLAGS: &[(&str, &str)] = &[
+    ("draft", "--draft"),
+    ("final", "--final"),
+    ("clean_result", "--clean-result"),
+    ("allow_placeholders", "--allow-placeholders"),
+];
+const VALUE_FLAGS: &[(&str, &str)] = &[("theme", "--theme"), ("brand", "--brand")];
+
+// Requests served so far, naming their scratch directories
+static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+// Requests being compiled
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+fn token() -> String {
+    env::var(TOKEN_VAR)
+        .ok()
+        .filter(|token| !token.is_empty())
+        .unwrap_or_else(|| {
+            eprintln!("ERROR: Set {TOKEN_VAR} to the token of the compile server");
+            exit(1);
+        })
+}
+
+/// Fails on links in the upload, they could point anywhere on the server
+fn check_links(root: &Path, dir: &Path) -> Result<(), String> {
+    for entry in read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        if file_type.is_symlink() {
+            let name = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            return Err(format!("Links aren't allowed in the archive, found {name}"));
+        } else if file_type.is_dir() {
+            check_links(root, &path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fails on uploaded config keys that would run a program or read files
+/// outside of `root`
+fn check_config(report_dir: &Path, root: &Path) -> Result<(), String> {
+    let path = report_dir.join(CONFIG_FILE);
+    let Ok(content) = read_to_string(&path) else {
+        return Ok(());
+    };
+    let table = toml::parse(&content).map_err(|e| format!("{CONFIG_FILE}: {e}"))?;
+    if let Some(key) = EXECUTABLE_KEYS.iter().find(|key| table.contains_key(**key)) {
+        return Err(format!(
+            "{CONFIG_FILE}: {key} can't be set in reports compiled remotely"
+        ));
+    }
+    let mut paths: Vec<(&str, String)> = PATH_KEYS
+        .iter()
+        .filter_map(|key| Some((*key, table.get_str(key)?.to_string())))
+        .collect();
+    for key in PATH_LIST_KEYS {
+        paths.extend(table.get_str_list(key).into_iter().map(|path| (*key, path)));
+    }
+    for (key, path) in paths {
+        let inside = report_dir
+            .join(&path)
+            .canonicalize()
+            .is_ok_and(|path| path.starts_with(root));
+        if !inside {
+            return Err(format!(
+                "{CONFIG_FILE}: {key} \"{path}\" has to be a file of the report"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fails on uploaded metadata with paths outside of `root`, like a logo
+/// copied into the report
+fn check_metadata(report_dir: &Path, root: &Path) -> Result<(), String> {
+    let report = Report::load(report_dir).map_err(|e| e.to_string())?;
+    for key in METADATA_PATH_KEYS {
+        let Some(path) = report.metadata_value(key).filter(|path| !path.is_empty()) else {
+            continue;
+        };
+        let inside = report_dir
+            .join(path)
+            .canonicalize()
+            .is_ok_and(|path| path.starts_with(root));
+        if !inside {
+            return Err(format!(
+                "metadata.typ: {key} \"{path}\" has to be a file of the report"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Compiles the packed report of a request in `dir`, returning the PDF or
+/// the status and message of the failure
+fn compile_upload(
+    request: &Request,
+    dir: &Path,
+    token: &str,
+) -> Result<Vec<u8>, (&'static str, String)> {
+    let internal = |e: String| ("500 Internal Server Error", e);
+    let archive = dir.join("report.7z");
+    create_dir_all(dir).map_err(|e| internal(e.to_string()))?;
+    write(&archive, &request.body).map_err(|e| internal(e.to_string()))?;
+    let sources = dir.join("sources");
+    pack::extract(&archive, &sources, token).map_err(|e| ("400 Bad Request", e.to_string()))?;
+    check_links(&sources, &sources).map_err(|e| ("400 Bad Request", e))?;
+    let report_dir = read_dir(&sources)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir())
+        .ok_or(("400 Bad Request", "No report in the archive".to_string()))?;
+    let root = sources
+        .canonicalize()
+        .map_err(|e| internal(e.to_string()))?;
+    check_config(&report_dir, &root).map_err(|e| ("400 Bad Request", e))?;
+    check_metadata(&report_dir, &root).map_err(|e| ("400 Bad Request", e))?;
+
+    let pdf = dir.join("report.pdf");
+    let exe = env::current_exe().map_err(|e| internal(e.to_string()))?;
+    let mut command = Command::new(exe);
+    // Typst reads files relative to and below its working directory
+    command
+        .current_dir(&report_dir)
+        .args(["compile", "."])
+        .arg("-o")
+        .arg(&pdf)
+        .arg("--no-notify");
+    for (param, flag) in FLAGS {
+        if request.param(param).is_some() {
+            command.arg(flag);
+        }
+    }
+    for (param, flag) in VALUE_FLAGS {
+        if let Some(value) = request.param(param).filter(|value| !value.is_empty()) {
+            command.args([flag, value]);
+        }
+    }
+    let output = command.output().map_err(|e| internal(e.to_string()))?;
+    if !output.status.success() {
+        return Err((
+            "422 Unprocessable Entity",
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+    read(&pdf).map_err(|e| internal(e.to_string()))
+}
+
+fn handle(mut stream: TcpStream, token: &str) {
+    if stream.set_read_timeout(Some(TIMEOUT)).is_err()
+        || stream.set_write_timeout(Some(TIMEOUT)).is_err()
+    {
+        return;
+    }
+    // Authorized before reading the archive
+    let Some(mut request) = serve::read_head(&mut stream) else {
+        return;
+    };
+    if request.method != "POST" || request.path != ENDPOINT {
+        let message = format!("POST a packed report to {ENDPOINT}");
+        serve::respond(
+            &mut stream,
+            "404 Not Found",
+            "text/plain",
+            message.as_bytes(),
+        );
+        return;
+    }
+    if request.header("authorization") != Some(&format!("Bearer {token}")) {
+        serve::respond(
+            &mut stream,
+            "401 Unauthorized",
+            "text/plain",
+            b"Wrong token",
+        );
+        return;
+    }
+    if serve::read_body(&mut stream, &mut request, MAX_ARCHIVE).is_none() {
+        return;
+    }
+
+    let dir = env::temp_dir().join(format!(
+        "reportgen-remote-{}-{}",
+        process::id(),
+        REQUESTS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = compile_upload(&request, &dir, token);
+    let _ = remove_dir_all(&dir);
+    match result {
+        Ok(pdf) => {
+            println!("Compiled a report of {} bytes", request.body.len());
+            serve::respond(&mut stream, "200 OK", "application/pdf", &pdf);
+        }
+        Err((status, message)) => {
+            eprintln!("ERROR: {status}: {}", message.trim());
+            serve::respond(&mut stream, status, "text/plain", message.as_bytes());
+        }
+    }
+}
+
+/// Compiles the reports posted to `/compile` on `port` of `bind`, localhost by default
+pub fn serve_api(port: Option<u16>, bind: Option<String>) -> Result<(), Box<dyn Error>> {
+    let token = token();
+    let port = port.unwrap_or(serve::DEFAULT_PORT);
+    let bind = bind.unwrap_or("127.0.0.1".to_string());
+    let listener = TcpListener::bind((bind.as_str(), port)).unwrap_or_else(|e| {
+        eprintln!("ERROR: Failed to listen on {bind} port {port}: {e}");
+        exit(1);
+    });
+    println!("Compiling reports posted to {bind} port {port} at {ENDPOINT}, Ctrl+C to stop");
+
+    for mut stream in listener.incoming().flatten() {
+        if ACTIVE.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            ACTIVE.fetch_sub(1, Ordering::SeqCst);
+            let _ = stream.set_write_timeout(Some(TIMEOUT));
+            serve::respond(
+                &mut stream,
+                "503 Service Unavailable",
+                "text/plain",
+                b"Too many reports compiling, try again later",
+            );
+            continue;
+        }
+        let token = token.clone();
+        thread::spawn(move || {
+            handle(stream, &token);
+            ACTIVE.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    Ok(())
+}
+
+/// Compiles the report on the server at `url`, saving the PDF like compile
+pub fn compile(
+    report_dir: Option<PathBuf>,
+    url: &str,
+    output: Option<String>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+    let output = output
+        .or(config.output.clone())
+        .unwrap_or(DEFAULT_REPORT_FILE.to_string());
+    let token = token();
+
+    let mut params: Vec<String> = [
+        ("draft", options.draft),
+        ("final", options.final_report),
+        ("clean_result", options.clean_result),
+        ("allow_placeholders", options.allow_placeholders),
+    ]
+    .iter()
+    .filter(|(_, set)| *set)
+    .map(|(param, _)| param.to_string())
+    .collect();
+    for (param, value) in [("theme", &options.theme), ("brand", &options.brand)] {
+        if let Some(value) = value {
+            params.push(format!("{param}={value}"));
+        }
+    }
+    let mut url = format!("{}{ENDPOINT}", url.trim_end_matches('/'));
+    if !params.is_empty() {
+        url.push_str(&format!("?{}", params.join("&")));
+    }
+
+    // 7z adds to an existing archive
+    let archive = env::temp_dir().join(format!("reportgen-remote-{}.7z", process::id()));
+    let _ = remove_file(&archive);
+    let authorization = format!("Bearer {token}");
+    let result = pack::pack_sources(&report_path, &archive, &token).and_then(|_| {
+        Client::new(options.offline).post_file(
+            &url,
+            &[("Authorization", &authorization)],
+            &archive,
+            Path::new(&output),
+        )
+    });
+    let _ = remove_file(&archive);
+    if let Err(e) = result {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+
+    println!("Report compiled remotely to {output}");
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::evidence;
+use crate::relations;
+use crate::renumber;
+use crate::report::{self, Entry, Kind, Report};
+use crate::toml::TableExt;
+use crate::xref;
+
+/// Evidence files of the entry no other entry refers to, relative to the report
+fn orphaned_evidence(report: &Report, kind: Kind, index: usize) -> Vec<String> {
+    let entry = &report.entries_of(kind)[index];
+    let others: Vec<String> = report
+        .entries()
+        .filter(|other| other.path != entry.path && !other.is_chapter())
+        .filter_map(|other| read_to_string(&other.path).ok())
+        .collect();
+    let mut orphaned: Vec<String> = Vec::new();
+    for screenshot in evidence::screenshots(entry) {
+        let path = screenshot.path.trim_start_matches('/').to_string();
+        if !others.iter().any(|content| content.contains(&path)) && !orphaned.contains(&path) {
+            orphaned.push(path);
+        }
+    }
+    orphaned
+}
+
+/// Warns about references to the removed finding, they are left dangling
+fn warn_references(report: &Report, removed: &Entry) {
+    let (id, label) = (removed.id, xref::label(removed.id));
+    for entry in report
+        .entries()
+        .filter(|e| !e.is_chapter() && e.path != removed.path)
+    {
+        for (n, line) in entry.body.lines().enumerate() {
+            if xref::references(line).iter().any(|(_, l)| *l == label) {
+                eprintln!(
+                    "WARNING: {}:{} refers to the removed finding {label}, left dangling as {}",
+                    entry.path.display(),
+                    entry.line + n,
+                    xref::label(0)
+                );
+            }
+        }
+    }
+    for finding in report.findings.iter().filter(|f| f.path != removed.path) {
+        for (key, _) in relations::KEYS {
+            if finding.meta.get_int_list(key).contains(&(id as i64)) {
+                eprintln!(
+                    "WARNING: {} lists the removed finding {id} in {key}",
+                    finding.path.display()
+                );
+            }
+        }
+    }
+}
+
+/// Deletes a section, finding or appendix, numbers the following ones down
+/// and deletes evidence only it referred to unless `keep_evidence` is set
+pub fn remove(
+    report_dir: Option<PathBuf>,
+    kind: Kind,
+    id: usize,
+    keep_evidence: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let Some(index) = report
+        .entries_of(kind)
+        .iter()
+        .position(|entry| entry.id == id)
+    else {
+        eprintln!(
+            "ERROR: No {}{id} in {}",
+            kind.prefix(),
+            report_path.display()
+        );
+        exit(1);
+    };
+    let entry = &report.entries_of(kind)[index];
+    if entry.is_chapter() {
+        eprintln!(
+            "ERROR: {} is a chapter, remove its sections first",
+            entry.path.display()
+        );
+        exit(1);
+    }
+
+    let mut changes = Changes::for_report(&report_path);
+    renumber::plan_remove(&report, kind, index, &mut changes)?;
+    if !keep_evidence {
+        for path in orphaned_evidence(&report, kind, index) {
+            let file = report_path.join(&path);
+            if file.is_file() {
+                changes.remove(file);
+            }
+        }
+    }
+    if kind == Kind::Finding {
+        warn_references(&report, entry);
+    }
+    changes.apply(dry_run)?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::{error::Error, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::renumber;
+use crate::report::{self, Kind, Report};
+
+/// File name part of a title, e.g. `stored_xss_in_comments`
+pub fn name_of(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect();
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Gives a section, finding or appendix a new title: the file is renamed
+/// keeping its number, its heading is replaced and references to the file
+/// name in other entries are updated
+pub fn rename(
+    report_dir: Option<PathBuf>,
+    kind: Kind,
+    id: usize,
+    title: String,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let name = name_of(&title);
+    if name.is_empty() {
+        eprintln!("ERROR: The title needs at least one letter or digit");
+        exit(1);
+    }
+
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let Some(entry) = report.entries_of(kind).iter().find(|entry| entry.id == id) else {
+        eprintln!(
+            "ERROR: No {}{id} in {}",
+            kind.prefix(),
+            report_path.display()
+        );
+        exit(1);
+    };
+
+    let mut changes = Changes::for_report(&report_path);
+    renumber::plan_rename(&report, entry, &name, Some(&title), &mut changes)?;
+    changes.apply(dry_run)?;
+
+    Ok(())
+}
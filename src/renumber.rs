@@ -0,0 +1,370 @@
+use std::{
+    error::Error,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::changes::Changes;
+use crate::front_matter;
+use crate::relations;
+use crate::report::{Entry, Kind, Report, ORDER_KEY};
+use crate::utils::typst_escape;
+use crate::xref;
+
+/// Path of `entry` with a new number and name, chapters are directories without
+/// extension. Entries ordered by their front-matter keep the number out of it.
+fn entry_path(entry: &Entry, id: usize, name: &str) -> PathBuf {
+    if entry.is_ordered() {
+        entry.path.with_file_name(format!("{name}.typ"))
+    } else if entry.is_chapter() {
+        entry.path.with_file_name(format!("{id}.{name}"))
+    } else {
+        entry.path.with_file_name(format!("{id}.{name}.typ"))
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// Replaces whole file names (`7.foo.typ` but not `17.foo.typ`) in a single pass
+fn replace_file_names(content: &str, renames: &[(String, String)]) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    let mut previous: Option<char> = None;
+
+    'outer: while let Some(c) = rest.chars().next() {
+        if !previous.is_some_and(is_name_char) {
+            for (from, to) in renames {
+                let Some(after) = rest.strip_prefix(from.as_str()) else {
+                    continue;
+                };
+                if !after.chars().next().is_some_and(is_name_char) {
+                    result.push_str(to);
+                    rest = after;
+                    previous = to.chars().last();
+                    continue 'outer;
+                }
+            }
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+        previous = Some(c);
+    }
+
+    result
+}
+
+/// Content with its first heading replaced by `title`, keeping the level, a
+/// `Finding:` prefix and a trailing label
+fn retitle(content: &str, title: &str) -> String {
+    let mut retitled = String::with_capacity(content.len());
+    let mut done = false;
+    for line in content.split_inclusive('\n') {
+        if done || !line.starts_with('=') {
+            retitled.push_str(line);
+            continue;
+        }
+        let text = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[text.len()..];
+        let heading = text.trim_start_matches('=');
+        let level = &text[..text.len() - heading.len()];
+        let heading = heading.trim();
+        let prefix = match heading.starts_with("Finding:") {
+            true => "Finding: ",
+            false => "",
+        };
+        let label = match heading.rfind(" <") {
+            Some(start) if heading.ends_with('>') => &heading[start..],
+            _ => "",
+        };
+        retitled.push_str(&format!(
+            "{level} {prefix}{}{label}{ending}",
+            typst_escape(title)
+        ));
+        done = true;
+    }
+    retitled
+}
+
+/// Plans rewriting references to renamed files in all sections and findings,
+/// the heading of the file given with a new title and the `order` keys of
+/// entries moved in `orders`.
+/// Files are written before being renamed, so undo can restore them in place.
+fn plan_references(
+    report: &Report,
+    renames: &[(PathBuf, PathBuf)],
+    ids: &[(usize, usize)],
+    orders: &[(PathBuf, usize)],
+    title: Option<(&Path, &str)>,
+    changes: &mut Changes,
+) -> Result<(), Box<dyn Error>> {
+    let names: Vec<(String, String)> = renames
+        .iter()
+        .map(|(from, to)| (file_name(from), file_name(to)))
+        .collect();
+
+    for entry in report.entries().filter(|e| !e.is_chapter()) {
+        let content = read_to_string(&entry.path)?;
+        let mut updated = replace_file_names(&content, &names);
+        if !ids.is_empty() {
+            updated = relations::renumber(&updated, ids);
+            updated = xref::renumber(&updated, ids);
+        }
+        if let Some((_, title)) = title.filter(|(path, _)| *path == entry.path) {
+            updated = retitle(&updated, title);
+        }
+        if let Some((_, order)) = orders.iter().find(|(path, _)| *path == entry.path) {
+            updated = front_matter::set_integer(&updated, ORDER_KEY, *order as i64);
+        }
+        if updated != content {
+            changes.write(entry.path.clone(), &updated);
+        }
+    }
+
+    Ok(())
+}
+
+/// Plans the renames numbering `entries` (in their new order) from 1 without gaps
+pub fn plan(
+    report: &Report,
+    entries: &[&Entry],
+    changes: &mut Changes,
+) -> Result<(), Box<dyn Error>> {
+    plan_renumbering(report, entries, None, changes)
+}
+
+/// Plans the renumbering, the file of `removed` is deleted before the renames
+fn plan_renumbering(
+    report: &Report,
+    entries: &[&Entry],
+    removed: Option<&Entry>,
+    changes: &mut Changes,
+) -> Result<(), Box<dyn Error>> {
+    let renames: Vec<(PathBuf, PathBuf)> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| (entry.path.clone(), entry_path(entry, i + 1, &entry.name)))
+        .filter(|(from, to)| from != to)
+        .collect();
+    let orders: Vec<(PathBuf, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter(|(i, entry)| entry.is_ordered() && entry.id != i + 1)
+        .map(|(i, entry)| (entry.path.clone(), i + 1))
+        .collect();
+
+    // Findings are referred to by number in relationships
+    let mut ids: Vec<(usize, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| report.findings.iter().any(|f| f.path == entry.path))
+        .map(|(i, entry)| (entry.id, i + 1))
+        .filter(|(from, to)| from != to)
+        .collect();
+    // References to a removed finding become FND-000, so they stay dangling
+    // instead of pointing at the finding taking over its number
+    if let Some(removed) = removed.filter(|r| report.findings.iter().any(|f| f.path == r.path)) {
+        ids.push((removed.id, 0));
+    }
+
+    plan_references(report, &renames, &ids, &orders, None, changes)?;
+    if let Some(removed) = removed {
+        changes.remove(removed.path.clone());
+    }
+
+    // Entries sharing a name could overwrite each other, go through temporary names then
+    let collides = renames
+        .iter()
+        .any(|(_, to)| renames.iter().any(|(from, _)| from == to));
+
+    if collides {
+        for (from, _) in &renames {
+            changes.rename(from.clone(), temporary_path(from));
+        }
+        for (from, to) in renames {
+            changes.rename(temporary_path(&from), to);
+        }
+    } else {
+        for (from, to) in renames {
+            changes.rename(from, to);
+        }
+    }
+
+    Ok(())
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".renumber");
+    tmp.into()
+}
+
+/// Moves the entry at `from` (index) to `position` (1-based) and plans the renumbering
+pub fn plan_move(
+    report: &Report,
+    kind: Kind,
+    from: usize,
+    position: usize,
+    changes: &mut Changes,
+) -> Result<(), Box<dyn Error>> {
+    let mut order: Vec<&Entry> = report.entries_of(kind).iter().collect();
+    let entry = order.remove(from);
+    let position = position.clamp(1, order.len() + 1);
+    order.insert(position - 1, entry);
+    plan(report, &order, changes)
+}
+
+/// Plans deleting the entry at `index` and numbering the following ones down
+pub fn plan_remove(
+    report: &Report,
+    kind: Kind,
+    index: usize,
+    changes: &mut Changes,
+) -> Result<(), Box<dyn Error>> {
+    let mut order: Vec<&Entry> = report.entries_of(kind).iter().collect();
+    let removed = order.remove(index);
+    plan_renumbering(report, &order, Some(removed), changes)
+}
+
+/// Plans renaming an entry while keeping its number, `title` replaces the
+/// heading of the file
+pub fn plan_rename(
+    report: &Report,
+    entry: &Entry,
+    new_name: &str,
+    title: Option<&str>,
+    changes: &mut Changes,
+) -> Result<(), Box<dyn Error>> {
+    let to = entry_path(entry, entry.id, new_name);
+    let renames = match to != entry.path {
+        true => vec![(entry.path.clone(), to.clone())],
+        false => Vec::new(),
+    };
+    let title = title.map(|title| (entry.path.as_path(), title));
+    plan_references(report, &renames, &[], &[], title, changes)?;
+    if to != entry.path {
+        changes.rename(entry.path.clone(), to);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        env,
+        fs::{create_dir_all, read_dir, remove_dir_all, write},
+        process,
+    };
+
+    /// Report in a temporary directory with the given findings as (file name, content)
+    fn report(test: &str, findings: &[(&str, &str)]) -> Report {
+        let dir = env::temp_dir().join(format!("reportgen-renumber-{test}-{}", process::id()));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(dir.join("sections")).unwrap();
+        create_dir_all(dir.join("findings")).unwrap();
+        write(dir.join("metadata.typ"), "title:Test\n").unwrap();
+        for (name, content) in findings {
+            write(dir.join("findings").join(name), content).unwrap();
+        }
+        Report::load(&dir).unwrap()
+    }
+
+    fn findings(report: &Report) -> Vec<(String, String)> {
+        let mut files: Vec<(String, String)> = read_dir(report.path.join("findings"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .map(|path| (file_name(&path), read_to_string(&path).unwrap()))
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn only_whole_file_names_are_replaced() {
+        let renames = vec![("7.x.typ".to_string(), "6.x.typ".to_string())];
+        assert_eq!(
+            replace_file_names(
+                "#include \"7.x.typ\" and 17.x.typ, 7.x.typ.bak, /7.x.typ",
+                &renames
+            ),
+            "#include \"6.x.typ\" and 17.x.typ, 7.x.typ.bak, /6.x.typ"
+        );
+    }
+
+    #[test]
+    fn renames_are_applied_in_a_single_pass() {
+        // Swapped names must not be renamed back
+        let renames = vec![
+            ("1.x.typ".to_string(), "2.x.typ".to_string()),
+            ("2.x.typ".to_string(), "1.x.typ".to_string()),
+        ];
+        assert_eq!(
+            replace_file_names("1.x.typ 2.x.typ", &renames),
+            "2.x.typ 1.x.typ"
+        );
+    }
+
+    #[test]
+    fn colliding_renames_go_through_temporary_names() {
+        let report = report(
+            "collision",
+            &[("1.x.typ", "= First\n"), ("2.x.typ", "= Second\n")],
+        );
+        let mut changes = Changes::default();
+        plan_move(&report, Kind::Finding, 1, 1, &mut changes).unwrap();
+        changes.apply(false).unwrap();
+
+        let files = findings(&report);
+        let _ = remove_dir_all(&report.path);
+        assert_eq!(
+            files,
+            vec![
+                ("1.x.typ".to_string(), "= Second\n".to_string()),
+                ("2.x.typ".to_string(), "= First\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn references_to_a_removed_finding_dangle() {
+        let report = report(
+            "remove",
+            &[
+                ("1.a.typ", "= A\n"),
+                (
+                    "2.b.typ",
+                    "= B\nSee {{ ref:FND-001 }} and {{ ref:FND-003 }}\n",
+                ),
+                ("3.c.typ", "+++\nrelated = [1, 2]\n+++\n= C\n"),
+            ],
+        );
+        let mut changes = Changes::default();
+        plan_remove(&report, Kind::Finding, 0, &mut changes).unwrap();
+        changes.apply(false).unwrap();
+
+        let files = findings(&report);
+        let _ = remove_dir_all(&report.path);
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "1.b.typ".to_string(),
+                    "= B\nSee {{ ref:FND-000 }} and {{ ref:FND-002 }}\n".to_string()
+                ),
+                (
+                    "2.c.typ".to_string(),
+                    "+++\nrelated = [0, 1]\n+++\n= C\n".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use std::{error::Error, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::renumber;
+use crate::report::{self, Kind, Report};
+
+/// Renumbers the sections or findings from 1, closing gaps in the numbering
+pub fn reorder(
+    report_dir: Option<PathBuf>,
+    kind: Kind,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    let entries: Vec<_> = report.entries_of(kind).iter().collect();
+    let mut changes = Changes::for_report(&report_path);
+    renumber::plan(&report, &entries, &mut changes)?;
+    changes.apply(dry_run)?;
+
+    Ok(())
+}
+
+/// Moves a section or finding (e.g. `f7`) to a new position, shifting the others
+pub fn move_entry(
+    report_dir: Option<PathBuf>,
+    target: String,
+    position: usize,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    let Some((kind, index)) = report.find(&target) else {
+        eprintln!("ERROR: No such section/finding \"{target}\" (expected e.g. s3 or f7)");
+        exit(1);
+    };
+
+    let mut changes = Changes::for_report(&report_path);
+    renumber::plan_move(&report, kind, index, position, &mut changes)?;
+    changes.apply(dry_run)?;
+
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use std::{
+    error::Error,
+    fs::{read_dir, read_to_string, File},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use ::toml::{Table, Value};
+
+use crate::front_matter;
+
+// Front-matter key positioning files named without a number, e.g.
+// `findings/sql_injection.typ` with `order = 3`
+pub const ORDER_KEY: &str = "order";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Section,
+    Finding,
+    Appendix,
+}
+
+impl Kind {
+    /// Accepts `s`, `section`, `sections`, `f`, `finding`, `findings`, `a`,
+    /// `appendix` and `appendices`
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "s" | "section" | "sections" => Some(Kind::Section),
+            "f" | "finding" | "findings" => Some(Kind::Finding),
+            "a" | "appendix" | "appendices" => Some(Kind::Appendix),
+            _ => None,
+        }
+    }
+
+    /// Prefix of targets like `s3`
+    pub fn prefix(self) -> char {
+        match self {
+            Kind::Section => 's',
+            Kind::Finding => 'f',
+            Kind::Appendix => 'a',
+        }
+    }
+}
+
+/// Single section or finding file (e.g. `sections/1.summary.typ`) or a
+/// chapter directory grouping further entries (e.g. `sections/3.technical/`)
+pub struct Entry {
+    pub id: usize,
+    pub name: String,
+    pub path: PathBuf,
+    pub meta: Table,
+    pub body: String,
+    // Line of the file the body starts at (1-based), following the front-matter
+    pub line: usize,
+    pub children: Vec<Entry>,
+}
+
+impl Entry {
+    pub fn is_chapter(&self) -> bool {
+        self.path.is_dir()
+    }
+
+    /// Whether the number comes from the `order` key instead of the file name
+    pub fn is_ordered(&self) -> bool {
+        self.meta.get(ORDER_KEY).is_some()
+    }
+
+    /// Title taken from the first heading in the file, falls back to the file name
+    pub fn title(&self) -> String {
+        self.body
+            .lines()
+            .find(|line| line.starts_with('='))
+            .map(|line| line.trim_start_matches('=').trim().to_string())
+            .unwrap_or_else(|| self.name.clone())
+    }
+}
+
+pub struct Report {
+    pub path: PathBuf,
+    pub metadata: Vec<(String, String)>,
+    pub sections: Vec<Entry>,
+    pub findings: Vec<Entry>,
+    // Rendered after the findings, the directory is optional
+    pub appendices: Vec<Entry>,
+}
+
+impl Report {
+    pub fn load(report_path: &Path) -> Result<Self, Box<dyn Error>> {
+        // Handle metadata file
+        let metadata = parse_metadata(&read_to_string(report_path.join("metadata.typ"))?);
+
+        Ok(Self {
+            path: report_path.to_path_buf(),
+            metadata,
+            sections: load_entries(&report_path.join("sections"))?,
+            findings: load_entries(&report_path.join("findings"))?,
+            appendices: match report_path.join("appendices") {
+                dir if dir.is_dir() => load_entries(&dir)?,
+                _ => Vec::new(),
+            },
+        })
+    }
+
+    /// Loads the report or exits with the reason it is invalid
+    pub fn load_or_exit(report_path: &Path) -> Self {
+        Self::load(report_path).unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        })
+    }
+
+    pub fn metadata_value(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.trim())
+    }
+
+    /// Parses a target like `s3` or `f12` into its kind and index into the entries
+    pub fn find(&self, target: &str) -> Option<(Kind, usize)> {
+        let kind = Kind::parse(target.get(..1)?)?;
+        let id = target.get(1..)?.parse::<usize>().ok()?;
+        let index = self
+            .entries_of(kind)
+            .iter()
+            .position(|entry| entry.id == id)?;
+        Some((kind, index))
+    }
+
+    pub fn entries_of(&self, kind: Kind) -> &Vec<Entry> {
+        match kind {
+            Kind::Section => &self.sections,
+            Kind::Finding => &self.findings,
+            Kind::Appendix => &self.appendices,
+        }
+    }
+
+    /// Number following the highest one of the kind, gaps and `order` values included
+    pub fn next_id(&self, kind: Kind) -> usize {
+        self.entries_of(kind)
+            .iter()
+            .map(|entry| entry.id)
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+
+    /// All sections, findings and appendices in this order, chapters before their children
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        let mut entries = Vec::new();
+        flatten(&self.sections, &mut entries);
+        flatten(&self.findings, &mut entries);
+        flatten(&self.appendices, &mut entries);
+        entries.into_iter()
+    }
+}
+
+fn flatten<'a>(entries: &'a [Entry], flat: &mut Vec<&'a Entry>) {
+    for entry in entries {
+        flat.push(entry);
+        flatten(&entry.children, flat);
+    }
+}
+
+/// Chapter heading derived from the directory name, e.g. `web_apps` -> `Web apps`
+fn chapter_title(name: &str) -> String {
+    let name = name.replace(['_', '-'], " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+/// Parses the `key:value` lines of `metadata.typ`
+pub fn parse_metadata(content: &str) -> Vec<(String, String)> {
+    let mut metadata = Vec::new();
+    for line in content.lines() {
+        // Values may contain colons, e.g. in URLs or version history notes
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        metadata.push((key.to_string(), value.to_string()));
+    }
+    metadata
+}
+
+/// Replaces the `key:value` line of `metadata.typ` content or appends one
+pub fn set_metadata(content: &str, key: &str, value: &str) -> String {
+    let line = format!("{key}:{value}");
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    match lines
+        .iter()
+        .position(|l| l.split_once(':').is_some_and(|(k, _)| k == key))
+    {
+        Some(i) => lines[i] = line,
+        None => lines.push(line),
+    }
+    lines.join("\n") + "\n"
+}
+
+fn load_entries(dir: &Path) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let mut entries = Vec::new();
+    for entry in read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let mut split = file_name.splitn(2, '.');
+        let number = split
+            .next()
+            .and_then(|number| number.parse::<usize>().ok())
+            .map(|id| (id, split.next().unwrap_or_default()));
+
+        // Chapters are walked recursively and get a heading from their name
+        if entry.path().is_dir() {
+            let (id, name) = number.ok_or_else(|| {
+                format!(
+                    "{}: directory name has to start with a number (e.g. 3.technical)",
+                    entry.path().display()
+                )
+            })?;
+            let name = name.to_string();
+            entries.push(Entry {
+                id,
+                body: format!("= {}\n", chapter_title(&name)),
+                name,
+                path: entry.path(),
+                meta: Table::default(),
+                line: 1,
+                children: load_entries(&entry.path())?,
+            });
+            continue;
+        }
+
+        let content =
+            read_to_string(entry.path()).map_err(|e| format!("{}: {e}", entry.path().display()))?;
+        let (meta, body) = front_matter::parse(&content)
+            .map_err(|e| format!("{}: invalid front-matter: {e}", entry.path().display()))?;
+        let order = match meta.get(ORDER_KEY) {
+            Some(Value::Integer(order)) if *order > 0 => Some(*order as usize),
+            Some(_) => {
+                return Err(format!(
+                    "{}: {ORDER_KEY} has to be a positive integer",
+                    entry.path().display()
+                )
+                .into())
+            }
+            None => None,
+        };
+        let (id, name) = match (number, order) {
+            (Some((id, name)), None) => (id, name),
+            (None, Some(order)) => (order, file_name.as_str()),
+            // Either scheme alone is fine, both would contradict each other sooner or later
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "{}: numbered file name and {ORDER_KEY} in the front-matter, use only one of them",
+                    entry.path().display()
+                )
+                .into())
+            }
+            (None, None) => {
+                return Err(format!(
+                    "{}: file name has to start with a number (e.g. 1.name.typ) or the front-matter has to set {ORDER_KEY}",
+                    entry.path().display()
+                )
+                .into())
+            }
+        };
+        let name = name.trim_end_matches(".typ").to_string();
+
+        entries.push(Entry {
+            id,
+            name,
+            path: entry.path(),
+            meta,
+            body: body.to_string(),
+            line: content[..content.len() - body.len()].lines().count() + 1,
+            children: Vec::new(),
+        });
+    }
+    // Gaps in the numbering are fine, duplicates would make the order ambiguous
+    entries.sort_by(|a, b| a.id.cmp(&b.id).then_with(|| a.path.cmp(&b.path)));
+    for pair in entries.windows(2) {
+        if pair[0].id == pair[1].id {
+            return Err(format!(
+                "Duplicate number {} in {} and {}",
+                pair[0].id,
+                pair[0].path.display(),
+                pair[1].path.display()
+            )
+            .into());
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Returns the provided report directory or the current directory if it is a report
+pub fn resolve_path(report_dir: Option<PathBuf>) -> PathBuf {
+    let report_path = report_dir.unwrap_or_else(|| {
+        if File::open("metadata.typ").is_err() {
+            eprintln!("ERROR: current directory is not a valid report");
+            exit(1);
+        }
+        ".".into()
+    });
+
+    // If directory doesn't exist, error out
+    if !report_path.exists() {
+        eprintln!("ERROR: Directory doesn't exist");
+        exit(1);
+    }
+
+    report_path
+}
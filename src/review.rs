@@ -0,0 +1,73 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::front_matter;
+use crate::report::{self, Entry, Report};
+use crate::toml::TableExt;
+
+// Review state of a section or finding, kept in its front-matter:
+//
+//  +++
+//  review = "approved"
+//  reviewer = "jdoe"
+//  +++
+
+// Review states in workflow order, entries without `review` are unreviewed
+pub const STATES: &[&str] = &["unreviewed", "reviewed", "approved"];
+
+pub fn state(entry: &Entry) -> &str {
+    entry.meta.get_str("review").unwrap_or(STATES[0])
+}
+
+pub fn is_approved(entry: &Entry) -> bool {
+    state(entry) == "approved"
+}
+
+/// Sections and findings that still need approval, chapters only group entries
+pub fn unapproved(report: &Report) -> Vec<&Entry> {
+    report
+        .entries()
+        .filter(|entry| !entry.is_chapter() && !is_approved(entry))
+        .collect()
+}
+
+/// Sets the review state (and optionally reviewer) of a section or finding
+pub fn review(
+    report_dir: Option<PathBuf>,
+    target: String,
+    state: String,
+    reviewer: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    if !STATES.contains(&state.as_str()) {
+        eprintln!(
+            "ERROR: Invalid review state \"{state}\", expected one of {}",
+            STATES.join(", ")
+        );
+        exit(1);
+    }
+
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    let Some((kind, index)) = report.find(&target) else {
+        eprintln!("ERROR: No such section/finding \"{target}\" (expected e.g. s3 or f7)");
+        exit(1);
+    };
+    let entry = &report.entries_of(kind)[index];
+    if entry.is_chapter() {
+        eprintln!("ERROR: {target} is a chapter, review its sections instead");
+        exit(1);
+    }
+
+    let mut content = front_matter::set(&read_to_string(&entry.path)?, "review", &state);
+    if let Some(ref reviewer) = reviewer {
+        content = front_matter::set(&content, "reviewer", reviewer);
+    }
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.write(entry.path.clone(), &content);
+    changes.apply(dry_run)?;
+
+    Ok(())
+}
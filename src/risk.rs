@@ -0,0 +1,85 @@
+use crate::report::{Entry, Report};
+use crate::toml::TableExt;
+use crate::utils::typst_escape;
+use crate::xref::{self, IdScheme};
+
+// Likelihood and impact of a finding on a scale of 1 to 5, e.g.
+// `likelihood = 4` and `impact = 3` in its front-matter, plotted in the
+// 5×5 `{{ risk_matrix }}`.
+
+pub const LIKELIHOOD_KEY: &str = "likelihood";
+pub const IMPACT_KEY: &str = "impact";
+pub const SCALE: i64 = 5;
+
+const LEVELS: [&str; 5] = ["Very low", "Low", "Medium", "High", "Very high"];
+
+/// Likelihood or impact of a finding, `None` if missing or out of range
+fn rating(finding: &Entry, key: &str) -> Option<i64> {
+    finding
+        .meta
+        .get_int_list(key)
+        .first()
+        .copied()
+        .filter(|value| is_valid(*value))
+}
+
+pub fn is_valid(value: i64) -> bool {
+    (1..=SCALE).contains(&value)
+}
+
+/// Cell color by risk score (likelihood × impact)
+fn color(score: i64) -> &'static str {
+    match score {
+        15.. => "#d32f2f",
+        8.. => "#f57c00",
+        4.. => "#fbc02d",
+        _ => "#388e3c",
+    }
+}
+
+/// Risk matrix with the IDs of the findings in their cells, empty if no
+/// finding has both a likelihood and an impact
+pub fn render_matrix(report: &Report, ids: &IdScheme) -> String {
+    let rated: Vec<(&Entry, i64, i64)> = report
+        .findings
+        .iter()
+        .filter_map(|f| Some((f, rating(f, LIKELIHOOD_KEY)?, rating(f, IMPACT_KEY)?)))
+        .collect();
+    if rated.is_empty() {
+        return String::new();
+    }
+
+    let mut matrix = format!(
+        "#table(\n  columns: (auto,) + ({SCALE} * (1fr,)),\n  rows: (36pt,) * {SCALE} + (auto,),\n  align: center + horizon,\n"
+    );
+    // Most severe impact on top
+    for impact in (1..=SCALE).rev() {
+        let mut row = vec![format!("[*{}*]", LEVELS[impact as usize - 1])];
+        for likelihood in 1..=SCALE {
+            let findings: Vec<String> = rated
+                .iter()
+                .filter(|(_, l, i)| *l == likelihood && *i == impact)
+                .map(|(f, _, _)| {
+                    format!(
+                        "#link(<{}>)[{}]",
+                        xref::label(f.id),
+                        typst_escape(&ids.id(f))
+                    )
+                })
+                .collect();
+            row.push(format!(
+                "table.cell(fill: rgb(\"{}\"), text(fill: white)[{}])",
+                color(likelihood * impact),
+                findings.join(" \\ ")
+            ));
+        }
+        matrix.push_str(&format!("  {},\n", row.join(", ")));
+    }
+    let header: Vec<String> = LEVELS.iter().map(|level| format!("[*{level}*]")).collect();
+    matrix.push_str(&format!(
+        "  [Impact / Likelihood], {},\n)\n",
+        header.join(", ")
+    ));
+
+    matrix
+}
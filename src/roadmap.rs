@@ -0,0 +1,95 @@
+use crate::report::{Entry, Report};
+use crate::severity::Presentation;
+use crate::toml::TableExt;
+use crate::utils::{typst_escape, typst_str};
+use crate::xref::{self, IdScheme};
+
+// Remediation roadmap grouping the findings by priority, with the timeline
+// of each priority from `[remediation_timelines]` in the config. Priorities are
+// severity levels, findings get the one of their severity unless they set
+// `priority = "high"` in their front-matter. `effort = "low"` sorts quick
+// wins first within a priority.
+
+pub const EFFORT_KEY: &str = "effort";
+pub const PRIORITY_KEY: &str = "priority";
+pub const EFFORTS: &[&str] = &["low", "medium", "high"];
+
+// Timelines of the default levels, others have none unless configured
+pub const DEFAULT_TIMELINES: &[(&str, &str)] = &[
+    ("critical", "7 days"),
+    ("high", "30 days"),
+    ("medium", "90 days"),
+    ("low", "180 days"),
+    ("info", "next maintenance window"),
+];
+
+/// Priority of a finding, its severity unless it sets one
+pub fn priority(finding: &Entry, severities: &[String]) -> Option<String> {
+    finding
+        .meta
+        .get_str(PRIORITY_KEY)
+        .or(finding.meta.get_str("severity"))
+        .map(str::to_lowercase)
+        .filter(|level| severities.contains(level))
+}
+
+fn effort(finding: &Entry) -> Option<usize> {
+    let effort = finding.meta.get_str(EFFORT_KEY)?.to_lowercase();
+    EFFORTS.iter().position(|e| *e == effort)
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Roadmap appendix, empty if no finding has a priority or severity
+pub fn render_appendix(
+    report: &Report,
+    severities: &[String],
+    timelines: &[(String, String)],
+    ids: &IdScheme,
+    presentation: &Presentation,
+) -> String {
+    let mut appendix = String::new();
+    for level in severities {
+        let mut findings: Vec<&Entry> = report
+            .findings
+            .iter()
+            .filter(|f| priority(f, severities).as_ref() == Some(level))
+            .collect();
+        if findings.is_empty() {
+            continue;
+        }
+        // Unknown effort last
+        findings.sort_by_key(|f| effort(f).unwrap_or(EFFORTS.len()));
+
+        let label = capitalize(&presentation.label(level));
+        let heading = match timelines.iter().find(|(l, _)| l == level) {
+            Some((_, timeline)) => format!("{label} priority: remediate within {timeline}"),
+            None => format!("{label} priority"),
+        };
+        appendix.push_str(&format!(
+            "== {}\n#table(\n  columns: (auto, 1fr, auto),\n  table.header([*ID*], [*Finding*], [*Effort*]),\n",
+            typst_escape(&heading)
+        ));
+        for finding in findings {
+            appendix.push_str(&format!(
+                "  [#link(<{}>)[{}]], [{}], {},\n",
+                xref::label(finding.id),
+                typst_escape(&ids.id(finding)),
+                xref::finding_title(finding),
+                typst_str(effort(finding).map_or("-", |e| EFFORTS[e]))
+            ));
+        }
+        appendix.push_str(")\n");
+    }
+
+    if appendix.is_empty() {
+        return appendix;
+    }
+    format!("#pagebreak()\n= Appendix: Remediation Roadmap\n{appendix}")
+}
@@ -0,0 +1,163 @@
+use serde_json::{json, Value};
+
+use crate::model::{Finding, Report};
+use crate::sla::CLOSED_STATUSES;
+
+// Findings as a SARIF 2.1.0 log for GitHub code scanning and other tools
+// reading static analysis results, e.g. after an application security
+// engagement:
+//
+//  report-generator export sarif -o findings.sarif
+//
+// Every finding is a rule with one result. Source code locations are taken
+// from the `locations` front-matter key as `path`, `path:line` or
+// `path:start-end`, relative to the root of the assessed repository. Findings
+// without one are located at their hosts, code scanning doesn't show those.
+// Closed findings are suppressed.
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// Scores GitHub ranks findings without a CVSS score by
+const SEVERITY_SCORES: &[(&str, f64)] = &[
+    ("critical", 9.5),
+    ("high", 8.0),
+    ("medium", 5.5),
+    ("low", 2.0),
+    ("info", 0.0),
+];
+
+fn level(finding: &Finding) -> &'static str {
+    match finding
+        .severity
+        .as_deref()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("critical" | "high") => "error",
+        Some("medium") => "warning",
+        _ => "note",
+    }
+}
+
+/// CVSS score of a finding or the score of its severity
+fn security_severity(finding: &Finding) -> Option<f64> {
+    let severity = finding.severity.as_deref().unwrap_or_default();
+    finding
+        .cvss
+        .as_deref()
+        .and_then(|cvss| cvss.parse::<f64>().ok())
+        .or_else(|| {
+            SEVERITY_SCORES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(severity))
+                .map(|(_, score)| *score)
+        })
+}
+
+/// Physical location of `src/login.py`, `src/login.py:42` or `src/login.py:42-50`
+fn physical_location(location: &str) -> Value {
+    let (path, region) = match location.rsplit_once(':') {
+        Some((path, lines)) => {
+            let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+            match (start.parse::<u32>(), end.parse::<u32>()) {
+                (Ok(start), Ok(end)) => (path, Some((start, end.max(start)))),
+                _ => (location, None),
+            }
+        }
+        None => (location, None),
+    };
+    let mut physical = json!({ "artifactLocation": { "uri": path.replace('\\', "/") } });
+    if let Some((start, end)) = region {
+        physical["region"] = json!({ "startLine": start, "endLine": end });
+    }
+    json!({ "physicalLocation": physical })
+}
+
+fn host_location(host: &str) -> Value {
+    json!({ "logicalLocations": [{ "name": host, "kind": "resource" }] })
+}
+
+fn rule(finding: &Finding) -> Value {
+    let (description, remediation) = finding.description_and_remediation();
+    let mut tags = vec!["security".to_string()];
+    // Tag format of GitHub, linking the CWE
+    tags.extend(
+        finding
+            .cwe
+            .iter()
+            .map(|cwe| format!("external/cwe/{}", cwe.to_lowercase())),
+    );
+    let mut properties = json!({ "tags": tags });
+    if let Some(score) = security_severity(finding) {
+        properties["security-severity"] = json!(format!("{score:.1}"));
+    }
+    let help = match remediation.is_empty() {
+        true => description.clone(),
+        false => remediation,
+    };
+    json!({
+        "id": finding.id,
+        "name": finding.name,
+        "shortDescription": { "text": finding.title },
+        "fullDescription": { "text": description },
+        "help": { "text": help },
+        "defaultConfiguration": { "level": level(finding) },
+        "properties": properties,
+    })
+}
+
+fn result(index: usize, finding: &Finding) -> Value {
+    let locations: Vec<Value> = match finding.locations.is_empty() {
+        true => finding
+            .hosts
+            .iter()
+            .map(|host| host_location(host))
+            .collect(),
+        false => finding
+            .locations
+            .iter()
+            .map(|location| physical_location(location))
+            .collect(),
+    };
+    let mut result = json!({
+        "ruleId": finding.id,
+        "ruleIndex": index,
+        "level": level(finding),
+        "message": { "text": finding.title },
+        "locations": locations,
+    });
+    if let Some(status) = finding
+        .status
+        .as_deref()
+        .filter(|status| CLOSED_STATUSES.contains(&status.to_lowercase().as_str()))
+    {
+        result["suppressions"] = json!([{ "kind": "external", "justification": status }]);
+    }
+    result
+}
+
+/// Findings of `report` as a SARIF log with a single run
+pub fn export(report: &Report) -> Value {
+    let rules: Vec<Value> = report.findings.iter().map(rule).collect();
+    let results: Vec<Value> = report
+        .findings
+        .iter()
+        .enumerate()
+        .map(|(index, finding)| result(index, finding))
+        .collect();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
@@ -0,0 +1,479 @@
+use clap::{Arg, CommandFactory};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::args::Cli;
+use crate::config;
+use crate::front_matter;
+
+// Machine-readable description of the CLI printed by `--schema`: the
+// subcommands and flags of args.rs and the keys of metadata, front-matter,
+// config and placeholders.
+
+#[derive(Serialize)]
+pub struct Key {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub description: &'static str,
+}
+
+pub const METADATA_KEYS: &[Key] = &[
+    Key {
+        name: "report_title",
+        kind: "string",
+        description: "Report title on the cover page",
+    },
+    Key {
+        name: "prepared_for",
+        kind: "string",
+        description: "Client the report is prepared for",
+    },
+    Key {
+        name: "prepared_by",
+        kind: "string",
+        description: "Author or company preparing the report",
+    },
+    Key {
+        name: "company_website",
+        kind: "string",
+        description: "Company website",
+    },
+    Key {
+        name: "company_email",
+        kind: "string",
+        description: "Company e-mail",
+    },
+    Key {
+        name: "company_phone",
+        kind: "string",
+        description: "Company phone number",
+    },
+];
+
+// Metadata keys that may be left out
+pub const OPTIONAL_METADATA_KEYS: &[Key] = &[
+    Key {
+        name: "date_format",
+        kind: "string",
+        description: "Format of current_date, strftime (%d.%m.%Y) or pattern (dd.MM.yyyy)",
+    },
+    Key {
+        name: "locale",
+        kind: "string",
+        description: "Locale of month names in current_date (en, de, fr, es, it, nl, pl)",
+    },
+    Key {
+        name: "report_date",
+        kind: "string",
+        description: "Report date (YYYY-MM-DD) replacing the date of compilation",
+    },
+    Key {
+        name: "classification",
+        kind: "string",
+        description: "Classification (e.g. CONFIDENTIAL) shown in banners at the top and bottom of every page",
+    },
+    Key {
+        name: "engagement_type",
+        kind: "string",
+        description: "Engagement type the report was created for (webapp, internal, external, cloud, mobile, redteam)",
+    },
+    Key {
+        name: "engagement_start",
+        kind: "string",
+        description: "First day of testing (YYYY-MM-DD)",
+    },
+    Key {
+        name: "engagement_end",
+        kind: "string",
+        description: "Last day of testing (YYYY-MM-DD)",
+    },
+    Key {
+        name: "client_address",
+        kind: "string",
+        description: "Client address (from the client profile)",
+    },
+    Key {
+        name: "client_contacts",
+        kind: "string",
+        description: "Client contacts (from the client profile)",
+    },
+    Key {
+        name: "logo",
+        kind: "string",
+        description: "Logo image on the cover page, relative to the report, copied into it as cover-logo.<ext>",
+    },
+    Key {
+        name: "client_logo",
+        kind: "string",
+        description: "Client logo file in the report directory (from the client profile)",
+    },
+    Key {
+        name: "toc",
+        kind: "string",
+        description: "Show the table of contents (true or false), replacing toc of the config",
+    },
+    Key {
+        name: "toc_depth",
+        kind: "integer",
+        description: "Deepest heading level in the table of contents, replacing toc_depth of the config",
+    },
+    Key {
+        name: "page_numbering",
+        kind: "string",
+        description: "Typst numbering pattern of the page numbers (e.g. 1 of 1, i), replacing page_numbering of the config",
+    },
+    Key {
+        name: "paper",
+        kind: "string",
+        description: "Paper size (e.g. a4, us-letter), replacing paper of the config",
+    },
+    Key {
+        name: "page_margin",
+        kind: "string",
+        description: "Page margin (e.g. 2.5cm, auto), replacing page_margin of the config",
+    },
+    Key {
+        name: "previous_report",
+        kind: "string",
+        description: "Report directory of the previous engagement with the client, relative to the report, for severity_trend",
+    },
+    Key {
+        name: "finding_id_prefix",
+        kind: "string",
+        description: "Prefix of the finding IDs shown in the report and exports (e.g. ACME-WEB-), FND- by default",
+    },
+    Key {
+        name: "finding_id_digits",
+        kind: "integer",
+        description: "Digits the finding number is zero-padded to in finding IDs, 3 by default",
+    },
+    Key {
+        name: "qa_passed",
+        kind: "string",
+        description: "Comma-separated QA checklist items that passed, set by qa",
+    },
+    Key {
+        name: "state",
+        kind: "string",
+        description: "Report state (draft, qa, delivered), set by state",
+    },
+    Key {
+        name: "version",
+        kind: "string",
+        description: "Current report version, updated by bump",
+    },
+    Key {
+        name: "versions",
+        kind: "string",
+        description: "Version history entry (version | date | author | changes), one line each",
+    },
+    Key {
+        name: "signatories",
+        kind: "string",
+        description: "Signatory of the sign-off page (name | role), one line each",
+    },
+];
+
+// Placeholders available in the main template and in section/finding content
+pub const PLACEHOLDERS: &[Key] = &[
+    Key {
+        name: "sections",
+        kind: "typst",
+        description: "All sections (main template)",
+    },
+    Key {
+        name: "findings",
+        kind: "typst",
+        description: "All findings (main template)",
+    },
+    Key {
+        name: "appendices",
+        kind: "typst",
+        description: "All appendices lettered A, B, ..., empty without an appendices directory (main template)",
+    },
+    Key {
+        name: "attack_appendix",
+        kind: "typst",
+        description: "ATT&CK techniques appendix (main template)",
+    },
+    Key {
+        name: "environment_matrix",
+        kind: "typst",
+        description: "Table of the environments each finding was verified in (main template)",
+    },
+    Key {
+        name: "host_appendix",
+        kind: "typst",
+        description: "Appendix of affected host lists longer than host_appendix_threshold (main template)",
+    },
+    Key {
+        name: "asset_matrix",
+        kind: "typst",
+        description: "Appendix table of the assets in assets.toml by affecting finding (main template)",
+    },
+    Key {
+        name: "scope_definition",
+        kind: "typst",
+        description: "Testing window and tables of the targets in and out of scope from scope.toml",
+    },
+    Key {
+        name: "attack_surface",
+        kind: "typst",
+        description: "Appendix table of the subdomains in attack-surface.tsv with IP and status (main template)",
+    },
+    Key {
+        name: "remediation_roadmap",
+        kind: "typst",
+        description: "Appendix of the findings grouped by priority with remediation timelines and effort (main template)",
+    },
+    Key {
+        name: "screenshot_gallery",
+        kind: "typst",
+        description: "Appendix grid of the images of all findings with captions, empty unless enabled in the config (main template)",
+    },
+    Key {
+        name: "brand_logo",
+        kind: "typst",
+        description: "Logo image of the branding profile, copied into the report as brand-logo.<ext> (main template)",
+    },
+    Key {
+        name: "brand_accent_color",
+        kind: "string",
+        description: "Accent color (#rrggbb) of the branding profile, #0074d9 by default (main template)",
+    },
+    Key {
+        name: "brand_font",
+        kind: "typst",
+        description: "Quoted body font of the branding profile, \"Noto Sans\" by default (main template)",
+    },
+    Key {
+        name: "brand_heading_font",
+        kind: "typst",
+        description: "Quoted heading font of the branding profile, the body font by default (main template)",
+    },
+    Key {
+        name: "brand_footer",
+        kind: "typst",
+        description: "Footer text of the branding profile, Client Confidential by default (main template)",
+    },
+    Key {
+        name: "toc",
+        kind: "typst",
+        description: "Whether to show the table of contents, true or false (main template)",
+    },
+    Key {
+        name: "toc_depth",
+        kind: "typst",
+        description: "Deepest heading level in the table of contents, none for all (main template)",
+    },
+    Key {
+        name: "page_numbering",
+        kind: "typst",
+        description: "Quoted numbering pattern of the page numbers (main template)",
+    },
+    Key {
+        name: "paper",
+        kind: "typst",
+        description: "Quoted paper size (main template)",
+    },
+    Key {
+        name: "page_margin",
+        kind: "typst",
+        description: "Page margin length or auto (main template)",
+    },
+    Key {
+        name: "git_commit",
+        kind: "string",
+        description: "Commit the report was compiled from with compile --tag, empty otherwise (main template)",
+    },
+    Key {
+        name: "cover_logo",
+        kind: "typst",
+        description: "Logo of the logo metadata scaled to fit the cover page, empty without one (main template)",
+    },
+    Key {
+        name: "sign_off",
+        kind: "typst",
+        description: "Sign-off page of the signatories metadata, empty without one (main template)",
+    },
+    Key {
+        name: "document_control",
+        kind: "typst",
+        description: "Version history table built from the versions metadata (main template)",
+    },
+    Key {
+        name: "current_date",
+        kind: "string",
+        description: "Date of compilation",
+    },
+    Key {
+        name: "executive_summary",
+        kind: "typst",
+        description: "Summary paragraph generated from finding severities and CWEs",
+    },
+    Key {
+        name: "total_findings",
+        kind: "string",
+        description: "Number of findings",
+    },
+    Key {
+        name: "highest_severity",
+        kind: "string",
+        description: "Most severe rating among the findings, none without rated findings",
+    },
+    Key {
+        name: "finding_count_by_severity",
+        kind: "typst",
+        description: "Table of the number of findings per severity",
+    },
+    Key {
+        name: "findings_overview",
+        kind: "typst",
+        description: "Table of all findings with their IDs, titles and severities",
+    },
+    Key {
+        name: "risk_matrix",
+        kind: "typst",
+        description: "5×5 likelihood × impact matrix with the findings plotted into its cells",
+    },
+    Key {
+        name: "severity_trend",
+        kind: "typst",
+        description: "Findings by severity of this and every previous engagement linked by previous_report, empty without one",
+    },
+    Key {
+        name: "severity_legend",
+        kind: "typst",
+        description: "Risk rating definitions, a table of every severity level with its color, CVSS score range and description from the config",
+    },
+    Key {
+        name: "affected_hosts",
+        kind: "typst",
+        description: "Table of the hosts of the finding, or their count with a link to host_appendix (findings)",
+    },
+    Key {
+        name: "affected_assets",
+        kind: "typst",
+        description: "Table of the assets of the finding from assets.toml (findings)",
+    },
+    Key {
+        name: "http_evidence",
+        kind: "typst",
+        description: "HTTP request/response pairs of the finding's http front-matter, with the redact_headers values replaced (findings)",
+    },
+    Key {
+        name: "finding_id",
+        kind: "string",
+        description: "ID of the finding from finding_id_prefix and finding_id_digits (findings)",
+    },
+    Key {
+        name: "assurance_statement",
+        kind: "typst",
+        description: "Positive assurance wording for reports without findings at or above clean_result_threshold",
+    },
+    Key {
+        name: "observations",
+        kind: "typst",
+        description: "Findings below clean_result_threshold under an Observations heading, empty without findings (clean-result template)",
+    },
+    Key {
+        name: "ref:FND-<number>",
+        kind: "typst",
+        description: "Link to a finding by its label, e.g. ref:FND-003 for finding 3",
+    },
+    Key {
+        name: "include:<name>",
+        kind: "typst",
+        description: "Shared snippet <name>.typ from the snippets directory of the config, the report, its workspace or $XDG_CONFIG_HOME/reportgenerator/snippets",
+    },
+    Key {
+        name: "severity_chart",
+        kind: "typst",
+        description: "Bar chart of the number of findings per severity",
+    },
+    Key {
+        name: "severity_label",
+        kind: "string",
+        description: "Displayed name of the severity of the finding (findings)",
+    },
+    Key {
+        name: "severity_badge",
+        kind: "typst",
+        description: "Colored badge with the severity of the finding (findings)",
+    },
+    Key {
+        name: "<severity>_findings",
+        kind: "string",
+        description: "Number of findings of a severity (e.g. high_findings or unrated_findings)",
+    },
+    Key {
+        name: "cve_details",
+        kind: "typst",
+        description: "Details of the enriched CVEs of a finding",
+    },
+];
+
+/// Name of an argument as typed, `-o`, `--format` or `<file>`
+fn usage(arg: &Arg) -> String {
+    match (arg.get_long(), arg.get_short()) {
+        (Some(long), _) => format!("--{long}"),
+        (None, Some(short)) => format!("-{short}"),
+        (None, None) => {
+            let name = arg.get_id().as_str();
+            match arg.is_required_set() {
+                true => format!("<{name}>"),
+                false => format!("[{name}]"),
+            }
+        }
+    }
+}
+
+fn flags<'a>(args: impl Iterator<Item = &'a Arg>) -> Vec<Value> {
+    args.filter(|arg| !arg.is_positional())
+        .map(|arg| {
+            let value = arg
+                .get_value_names()
+                .and_then(|names| names.first())
+                .map(|name| name.to_lowercase())
+                .unwrap_or(arg.get_id().as_str().to_string());
+            json!({
+                "name": usage(arg),
+                "value": arg.get_action().takes_values().then_some(value),
+                "description": arg.get_help().map(|help| help.to_string()).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+pub fn schema() -> Value {
+    let cli = Cli::command();
+    let subcommands: Vec<Value> = cli
+        .get_subcommands()
+        .map(|subcommand| {
+            let arguments: Vec<String> = subcommand.get_positionals().map(usage).collect();
+            json!({
+                "name": subcommand.get_name(),
+                "arguments": arguments,
+                // Global flags like --offline are listed once below
+                "flags": flags(subcommand.get_arguments().filter(|arg| !arg.is_global_set())),
+                "description": subcommand
+                    .get_about()
+                    .map(|about| about.to_string())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "subcommands": subcommands,
+        "flags": flags(cli.get_arguments()),
+        "metadata_keys": METADATA_KEYS,
+        "optional_metadata_keys": OPTIONAL_METADATA_KEYS,
+        "front_matter_keys": front_matter::KEYS,
+        "config_keys": config::KEYS,
+        "placeholders": PLACEHOLDERS,
+    })
+}
@@ -0,0 +1,254 @@
+use std::{fs::read_to_string, io::ErrorKind, net::IpAddr, path::Path};
+
+use ::toml::{Table, Value};
+
+use crate::assets::{self, Asset};
+use crate::consts::SCOPE_FILE;
+use crate::hosts;
+use crate::report::Report;
+use crate::toml::{self, TableExt};
+use crate::utils::typst_str;
+
+// Scope of the engagement, described in `scope.toml` of the report:
+//
+//  [window]
+//  start = "2024-05-06 08:00"
+//  end = "2024-05-17 18:00"
+//  notes = "No testing on weekends"
+//
+//  [in_scope]
+//  ranges = ["10.0.0.0/24", "192.168.1.10-192.168.1.20"]
+//  domains = ["acme.example", "*.acme.example"]
+//  apps = ["https://portal.acme.example"]
+//
+//  [out_of_scope]
+//  ranges = ["10.0.0.1"]
+//  domains = ["mail.acme.example"]
+//
+// It is shown as `{{ scope_definition }}`, and the hosts of findings and the
+// addresses of their assets are checked against it. `*.` domains match every
+// subdomain, apps match the host of their URL.
+
+const KINDS: &[(&str, &str)] = &[
+    ("ranges", "IP range"),
+    ("domains", "Domain"),
+    ("apps", "Application"),
+];
+
+#[derive(Default)]
+pub struct Targets {
+    pub ranges: Vec<String>,
+    pub domains: Vec<String>,
+    pub apps: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct Scope {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub notes: Option<String>,
+    pub included: Targets,
+    pub excluded: Targets,
+}
+
+impl Targets {
+    fn from(table: Option<&Value>) -> Self {
+        let Some(Value::Table(table)) = table else {
+            return Self::default();
+        };
+        Self {
+            ranges: table.get_str_list("ranges"),
+            domains: table.get_str_list("domains"),
+            apps: table.get_str_list("apps"),
+        }
+    }
+
+    fn of_kind(&self, kind: &str) -> &[String] {
+        match kind {
+            "ranges" => &self.ranges,
+            "domains" => &self.domains,
+            _ => &self.apps,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ranges.is_empty() && self.domains.is_empty() && self.apps.is_empty()
+    }
+
+    fn contains(&self, host: &str) -> bool {
+        match host.parse::<IpAddr>() {
+            Ok(ip) => self.ranges.iter().any(|range| in_range(ip, range)),
+            Err(_) => {
+                self.domains
+                    .iter()
+                    .any(|domain| matches_domain(host, domain))
+                    || self.apps.iter().any(|app| host_of(app) == host)
+            }
+        }
+    }
+}
+
+/// Host name or IP of a host entry, URL or `host:port`, in lowercase
+fn host_of(target: &str) -> String {
+    let target = target.trim();
+    let target = target.split_once("://").map_or(target, |(_, rest)| rest);
+    let target = target.split(['/', '?', '#']).next().unwrap_or_default();
+    let target = target.rsplit_once('@').map_or(target, |(_, host)| host);
+    let host = match target.strip_prefix('[') {
+        // [::1]:443
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        // Bare IPv6 addresses have more than one colon
+        None if target.matches(':').count() > 1 => target,
+        None => target.split(':').next().unwrap_or_default(),
+    };
+    host.trim_end_matches('.').to_lowercase()
+}
+
+fn matches_domain(host: &str, domain: &str) -> bool {
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    match domain.strip_prefix("*.") {
+        Some(parent) => host.ends_with(&format!(".{parent}")),
+        None => host == domain,
+    }
+}
+
+/// Addresses as numbers of the same width, `None` for mixed families
+fn numbers(a: IpAddr, b: IpAddr) -> Option<(u128, u128)> {
+    match (a, b) {
+        (IpAddr::V4(a), IpAddr::V4(b)) => Some((u32::from(a) as u128, u32::from(b) as u128)),
+        (IpAddr::V6(a), IpAddr::V6(b)) => Some((u128::from(a), u128::from(b))),
+        _ => None,
+    }
+}
+
+/// Whether `ip` is in `10.0.0.0/24`, `10.0.0.10-10.0.0.20` or is `10.0.0.1`
+fn in_range(ip: IpAddr, range: &str) -> bool {
+    let range = range.trim();
+    if let Some((network, prefix)) = range.split_once('/') {
+        let (Ok(network), Ok(prefix)) = (network.parse::<IpAddr>(), prefix.parse::<u32>()) else {
+            return false;
+        };
+        let bits = if network.is_ipv4() { 32 } else { 128 };
+        let Some((ip, network)) = numbers(ip, network).filter(|_| prefix <= bits) else {
+            return false;
+        };
+        let shift = bits - prefix;
+        return shift == 128 || ip >> shift == network >> shift;
+    }
+    if let Some((first, last)) = range.split_once('-') {
+        let (Ok(first), Ok(last)) = (first.trim().parse(), last.trim().parse()) else {
+            return false;
+        };
+        return match (numbers(ip, first), numbers(ip, last)) {
+            (Some((ip, first)), Some((_, last))) => (first..=last).contains(&ip),
+            _ => false,
+        };
+    }
+    range.parse::<IpAddr>().is_ok_and(|range| range == ip)
+}
+
+/// Scope of the report, `None` without `scope.toml`
+pub fn load(report_path: &Path) -> Result<Option<Scope>, String> {
+    let path = report_path.join(SCOPE_FILE);
+    let content = match read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+    let table = toml::parse(&content).map_err(|e| format!("{}: {e}", path.display()))?;
+    let window = match table.get("window") {
+        Some(Value::Table(window)) => window.clone(),
+        _ => Table::default(),
+    };
+    let string = |key: &str| window.get_str(key).map(str::to_string);
+
+    Ok(Some(Scope {
+        start: string("start"),
+        end: string("end"),
+        notes: string("notes"),
+        included: Targets::from(table.get("in_scope")),
+        excluded: Targets::from(table.get("out_of_scope")),
+    }))
+}
+
+impl Scope {
+    /// Why `host` is outside the scope, `None` if it is inside
+    pub fn violation(&self, host: &str) -> Option<&'static str> {
+        let host = host_of(host);
+        if self.excluded.contains(&host) {
+            Some("is excluded by")
+        } else if !self.included.contains(&host) {
+            Some("isn't covered by")
+        } else {
+            None
+        }
+    }
+
+    /// Hosts of findings and addresses of their assets outside the scope, as
+    /// (finding file, host, reason)
+    pub fn violations<'a>(
+        &self,
+        report: &'a Report,
+        assets: &[Asset],
+    ) -> Vec<(&'a Path, String, &'static str)> {
+        let mut violations = Vec::new();
+        for finding in &report.findings {
+            let addresses = finding
+                .meta
+                .get_str_list(assets::FRONT_MATTER_KEY)
+                .into_iter()
+                .filter_map(|id| assets.iter().find(|asset| asset.id == id))
+                .map(|asset| asset.address.clone())
+                .filter(|address| !address.is_empty());
+            let hosts = finding.meta.get_str_list(hosts::FRONT_MATTER_KEY);
+            for host in hosts.into_iter().chain(addresses) {
+                if let Some(reason) = self.violation(&host) {
+                    violations.push((finding.path.as_path(), host, reason));
+                }
+            }
+        }
+        violations
+    }
+
+    fn table(caption: &str, targets: &Targets) -> String {
+        let mut table = format!(
+            "#table(\n  columns: (auto, 1fr),\n  table.header(table.cell(colspan: 2)[*{caption}*], [*Type*], [*Target*]),\n"
+        );
+        for (kind, label) in KINDS {
+            for target in targets.of_kind(kind) {
+                table.push_str(&format!("  {}, {},\n", typst_str(label), typst_str(target)));
+            }
+        }
+        table.push_str(")\n");
+        table
+    }
+
+    /// `{{ scope_definition }}`, tables of the targets and the testing window
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) => rendered.push_str(&format!(
+                "Testing window: #{} to #{}\n\n",
+                typst_str(start),
+                typst_str(end)
+            )),
+            (Some(start), None) => {
+                rendered.push_str(&format!("Testing from #{}\n\n", typst_str(start)))
+            }
+            (None, Some(end)) => {
+                rendered.push_str(&format!("Testing until #{}\n\n", typst_str(end)))
+            }
+            (None, None) => {}
+        }
+        if let Some(ref notes) = self.notes {
+            rendered.push_str(&format!("#{}\n\n", typst_str(notes)));
+        }
+        if !self.included.is_empty() {
+            rendered.push_str(&Self::table("In scope", &self.included));
+        }
+        if !self.excluded.is_empty() {
+            rendered.push_str(&Self::table("Out of scope", &self.excluded));
+        }
+        rendered
+    }
+}
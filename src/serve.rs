@@ -0,0 +1,313 @@
+use std::{
+    env,
+    error::Error,
+    fs::{create_dir_all, read},
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{exit, Command},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde_json::json;
+
+use crate::config::Config;
+use crate::consts::{SERVE_DIR, SERVE_PAGE, STATE_DIR};
+use crate::digest::html_escape;
+use crate::report::{self, Report};
+use crate::template::Template;
+use crate::watch;
+
+// Live preview in the browser: the report is compiled to a PDF of its own in
+// `.reportgenerator/serve/`, shown on a page at http://127.0.0.1:8000 and
+// rebuilt whenever a watched file changes, like watch does. The page reloads
+// the PDF after every successful build and shows the errors of failed ones.
+// Only connections from the local machine are accepted, and only for
+// 127.0.0.1 or localhost as the host so other sites can't read the report.
+
+pub const DEFAULT_PORT: u16 = 8000;
+// Longest request head read, larger requests are dropped
+const MAX_HEAD: usize = 16 * 1024;
+
+#[derive(Default)]
+struct Status {
+    // Number of builds so far, the page reloads when it changes
+    build: usize,
+    ok: bool,
+    // Errors of the last build
+    log: String,
+}
+
+pub struct Request {
+    pub method: String,
+    // Without the query
+    pub path: String,
+    pub query: String,
+    // Names in lowercase
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Value of a query parameter, empty for one without a value like `?draft`
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .split('&')
+            .find_map(|pair| match pair.split_once('=') {
+                Some((key, value)) => (key == name).then_some(value),
+                None => (pair == name).then_some(""),
+            })
+    }
+}
+
+/// Reads the request line and headers, the body read along with them is
+/// left in `body` for `read_body`
+pub fn read_head(stream: &mut TcpStream) -> Option<Request> {
+    let mut data = Vec::new();
+    let mut buffer = [0; 8192];
+    let head_end = loop {
+        if let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        let read = stream.read(&mut buffer).ok()?;
+        if read == 0 || data.len() > MAX_HEAD {
+            return None;
+        }
+        data.extend_from_slice(&buffer[..read]);
+    };
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    Some(Request {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body: data[head_end + 4..].to_vec(),
+    })
+}
+
+/// Reads the rest of a body of at most `max_body` bytes, larger ones are dropped
+pub fn read_body(stream: &mut TcpStream, request: &mut Request, max_body: usize) -> Option<()> {
+    let length: usize = request
+        .header("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    if length > max_body {
+        return None;
+    }
+    let mut buffer = [0; 8192];
+    while request.body.len() < length {
+        let read = stream.read(&mut buffer).ok()?;
+        if read == 0 {
+            return None;
+        }
+        request.body.extend_from_slice(&buffer[..read]);
+    }
+    request.body.truncate(length);
+    Some(())
+}
+
+/// Reads a request with a body of at most `max_body` bytes, larger ones are dropped
+pub fn read_request(stream: &mut TcpStream, max_body: usize) -> Option<Request> {
+    let mut request = read_head(stream)?;
+    read_body(stream, &mut request, max_body)?;
+    Some(request)
+}
+
+pub fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &[u8]) {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    // The browser may have gone away, it asks again
+    let _ = stream
+        .write_all(head.as_bytes())
+        .and_then(|_| stream.write_all(body));
+}
+
+/// Whether the `Host` of a request names this server, pages of other sites
+/// resolving their name to 127.0.0.1 (DNS rebinding) don't
+fn is_local_host(host: Option<&str>, port: u16) -> bool {
+    [format!("127.0.0.1:{port}"), format!("localhost:{port}")]
+        .iter()
+        .any(|local| host.is_some_and(|host| host.eq_ignore_ascii_case(local)))
+}
+
+fn handle(mut stream: TcpStream, page: &str, pdf: &Path, status: &Mutex<Status>) {
+    let Some(request) = read_request(&mut stream, 0) else {
+        return;
+    };
+    let port = stream.local_addr().map_or(0, |address| address.port());
+    if !is_local_host(request.header("host"), port) {
+        respond(
+            &mut stream,
+            "403 Forbidden",
+            "text/plain",
+            b"Only served to 127.0.0.1 and localhost",
+        );
+        return;
+    }
+    if request.method != "GET" {
+        respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"Method not allowed",
+        );
+        return;
+    }
+    match request.path.as_str() {
+        "/" => respond(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            page.as_bytes(),
+        ),
+        "/status" => {
+            let json = {
+                let status = status.lock().expect("Failed to lock the build status");
+                json!({
+                    "build": status.build,
+                    "ok": status.ok,
+                    "log": status.log,
+                })
+            };
+            respond(
+                &mut stream,
+                "200 OK",
+                "application/json",
+                json.to_string().as_bytes(),
+            );
+        }
+        "/report.pdf" => match read(pdf) {
+            Ok(data) => respond(&mut stream, "200 OK", "application/pdf", &data),
+            Err(_) => respond(
+                &mut stream,
+                "404 Not Found",
+                "text/plain",
+                b"Not compiled yet",
+            ),
+        },
+        _ => respond(&mut stream, "404 Not Found", "text/plain", b"Not found"),
+    }
+}
+
+/// Compiles the report to `pdf` with `compile_args`, recording the result in `status`
+fn compile(
+    exe: &Path,
+    report_path: &Path,
+    pdf: &Path,
+    compile_args: &[String],
+    status: &Mutex<Status>,
+) {
+    let output = Command::new(exe)
+        .arg("compile")
+        .arg(report_path)
+        .args(compile_args)
+        .arg("-o")
+        .arg(pdf)
+        .output();
+    let (ok, log) = match output {
+        Ok(output) => {
+            let _ = io::stdout().write_all(&output.stdout);
+            let _ = io::stderr().write_all(&output.stderr);
+            (
+                output.status.success(),
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            )
+        }
+        Err(e) => {
+            eprintln!("ERROR: Failed to run compile: {e}");
+            (false, format!("Failed to run compile: {e}"))
+        }
+    };
+    let mut status = status.lock().expect("Failed to lock the build status");
+    status.build += 1;
+    status.ok = ok;
+    status.log = log;
+}
+
+/// Serves the report on `port` and recompiles it with `compile_args` on changes
+pub fn serve(
+    report_dir: Option<PathBuf>,
+    port: Option<u16>,
+    compile_args: Vec<String>,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+
+    let dir = report_path.join(STATE_DIR).join(SERVE_DIR);
+    create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    let pdf = dir.join("report.pdf");
+    let title = html_escape(report.metadata_value("report_title").unwrap_or("Report"));
+    let page = Template::from_str(SERVE_PAGE).render(&vec![("title", &title)]);
+
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|e| {
+        eprintln!("ERROR: Failed to listen on port {port}: {e}");
+        exit(1);
+    });
+
+    let exe = env::current_exe()?;
+    let status = Arc::new(Mutex::new(Status::default()));
+    compile(&exe, &report_path, &pdf, &compile_args, &status);
+    println!(
+        "Serving {} at http://127.0.0.1:{port}, Ctrl+C to stop",
+        report_path.display()
+    );
+
+    {
+        let status = Arc::clone(&status);
+        let report_path = report_path.clone();
+        let pdf = pdf.clone();
+        thread::spawn(move || {
+            watch::on_change(&report_path, &config, |path| {
+                println!("\n{} changed, recompiling", path.display());
+                compile(&exe, &report_path, &pdf, &compile_args, &status);
+            })
+        });
+    }
+
+    for stream in listener.incoming().flatten() {
+        // A slow browser doesn't hold up the others
+        let status = Arc::clone(&status);
+        let page = page.clone();
+        let pdf = pdf.clone();
+        thread::spawn(move || handle(stream, &page, &pdf, &status));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_local_hosts_are_served() {
+        assert!(is_local_host(Some("127.0.0.1:8000"), 8000));
+        assert!(is_local_host(Some("LocalHost:8000"), 8000));
+        assert!(!is_local_host(Some("localhost:8001"), 8000));
+        assert!(!is_local_host(Some("attacker.example:8000"), 8000));
+        assert!(!is_local_host(Some("127.0.0.1"), 8000));
+        assert!(!is_local_host(None, 8000));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::utils::{typst_escape, typst_str};
+
+// Finding severity levels, most severe first
+pub const LEVELS: &[&str] = &["critical", "high", "medium", "low", "info"];
+
+// Badge colors of the default levels, other levels and unrated findings are gray
+const COLORS: &[(&str, &str)] = &[
+    ("critical", "#800080"),
+    ("high", "#c81e1e"),
+    ("medium", "#e68200"),
+    ("low", "#288c28"),
+    ("info", "#285ab4"),
+    ("informational", "#285ab4"),
+];
+const FALLBACK_COLOR: &str = "#808080";
+// CVSS score ranges of the default levels, the CVSS v3 qualitative ratings
+const SCORES: &[(&str, &str)] = &[
+    ("critical", "9.0-10.0"),
+    ("high", "7.0-8.9"),
+    ("medium", "4.0-6.9"),
+    ("low", "0.1-3.9"),
+    ("info", "0.0"),
+];
+// Meaning of the default levels in the rating definitions
+const DESCRIPTIONS: &[(&str, &str)] = &[
+    ("critical", "Exploitation is straightforward and leads to a compromise of the system or its data. Fix immediately."),
+    ("high", "Exploitation is likely and has a serious impact on confidentiality, integrity or availability."),
+    ("medium", "Exploitation requires specific conditions or has a limited impact."),
+    ("low", "Exploitation is unlikely or has a minor impact, fix as part of regular maintenance."),
+    ("info", "No direct risk, an observation or a deviation from best practice."),
+];
+
+/// Checks a severity against a scheme (see `Config::severities`), ignoring case
+pub fn is_valid(severity: &str, levels: &[String]) -> bool {
+    levels.contains(&severity.to_lowercase())
+}
+
+/// Checks a `#rrggbb` color
+pub fn is_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Score range like `7.0-8.9` or `0.0` as (lowest, highest), within 0.0 to 10.0
+pub fn parse_range(range: &str) -> Option<(f64, f64)> {
+    let (low, high) = range.split_once('-').unwrap_or((range, range));
+    let (low, high): (f64, f64) = (low.trim().parse().ok()?, high.trim().parse().ok()?);
+    (0.0 <= low && low <= high && high <= 10.0).then_some((low, high))
+}
+
+/// How severities are shown in the deliverable: client mandated names and colors
+/// replace the level names in badges, charts and tables. Sorting, placeholders
+/// like `{{ high_findings }}` and exports keep using the level names. Score
+/// ranges and descriptions make up the rating definitions of `{{ severity_legend }}`.
+#[derive(Default)]
+pub struct Presentation {
+    // Level and its name/color, from `[severity_labels]` and `[severity_colors]`
+    pub labels: Vec<(String, String)>,
+    pub colors: Vec<(String, String)>,
+    // Level and its score range/description, from `[severity_scores]` and `[severity_descriptions]`
+    pub scores: Vec<(String, String)>,
+    pub descriptions: Vec<(String, String)>,
+}
+
+/// Configured value of a level or the one of the defaults
+fn lookup<'a>(
+    configured: &'a [(String, String)],
+    defaults: &[(&str, &'a str)],
+    level: &str,
+) -> Option<&'a str> {
+    configured
+        .iter()
+        .map(|(l, value)| (l.as_str(), value.as_str()))
+        .chain(defaults.iter().copied())
+        .find(|(l, _)| *l == level)
+        .map(|(_, value)| value)
+}
+
+impl Presentation {
+    pub fn label(&self, level: &str) -> String {
+        let level = level.to_lowercase();
+        self.labels
+            .iter()
+            .find(|(l, _)| *l == level)
+            .map_or(level.clone(), |(_, label)| label.clone())
+    }
+
+    /// `#rrggbb` color of a level
+    pub fn color(&self, level: &str) -> &str {
+        lookup(&self.colors, COLORS, &level.to_lowercase()).unwrap_or(FALLBACK_COLOR)
+    }
+
+    /// CVSS score range of a level as (lowest, highest)
+    pub fn score_range(&self, level: &str) -> Option<(f64, f64)> {
+        lookup(&self.scores, SCORES, &level.to_lowercase()).and_then(parse_range)
+    }
+
+    /// `{{ severity_legend }}`, a table defining every level of the scheme
+    pub fn legend(&self, severities: &[String]) -> String {
+        let mut table = String::from(
+            "#table(\n  columns: (auto, auto, 1fr),\n  table.header([*Severity*], [*CVSS score*], [*Description*]),\n",
+        );
+        for level in severities {
+            let range = match self.score_range(level) {
+                Some((low, high)) if low == high => format!("{low:.1}"),
+                Some((low, high)) => format!("{low:.1} - {high:.1}"),
+                None => "-".to_string(),
+            };
+            let description = lookup(&self.descriptions, DESCRIPTIONS, level).unwrap_or_default();
+            table.push_str(&format!(
+                "  [{}], {}, {},\n",
+                self.badge(level),
+                typst_str(&range),
+                typst_str(description)
+            ));
+        }
+        table.push_str(")\n");
+        table
+    }
+
+    /// Colored label as Typst markup, e.g. `#box(fill: rgb("#c81e1e"), ..)[HIGH]`
+    pub fn badge(&self, level: &str) -> String {
+        format!(
+            "#box(fill: rgb({}), inset: (x: 4pt, y: 2pt), radius: 2pt, text(fill: white, weight: \"bold\")[{}])",
+            typst_str(self.color(level)),
+            typst_escape(&self.label(level).to_uppercase())
+        )
+    }
+}
@@ -0,0 +1,28 @@
+use std::{error::Error, fs::rename, path::Path, process::Command};
+
+// Compiled PDFs are signed with pyHanko and a PKCS#12 certificate, typst
+// can't do that itself. pyHanko asks for the password of the certificate.
+// Signing has to come last, any later change breaks the signature.
+
+// Signature field added to the PDF
+const FIELD: &str = "Signature";
+
+/// Signs `pdf` in place with the certificate and key of `certificate`
+pub fn sign_pdf(pdf: &str, certificate: &Path) -> Result<(), Box<dyn Error>> {
+    let signed = format!("{pdf}.signed");
+    let status = Command::new("pyhanko")
+        .args(["sign", "addsig", "--field", FIELD, "pkcs12", pdf, &signed])
+        .arg(certificate)
+        .status()
+        .map_err(|e| {
+            format!(
+                "Failed to execute pyhanko ({e})\nEnsure you have 'pyhanko' installed on your system"
+            )
+        })?;
+    if !status.success() {
+        return Err(format!("pyhanko failed to sign {pdf}").into());
+    }
+    rename(&signed, pdf)
+        .map_err(|e| format!("Failed to replace {pdf} with the signed PDF: {e}"))?;
+    Ok(())
+}
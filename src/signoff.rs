@@ -0,0 +1,57 @@
+use crate::report::Report;
+
+// Sign-off page listing the signatories of the report, one `signatories`
+// line each in `metadata.typ` with name and role separated by `|`:
+//
+//  signatories:Jane Doe | Lead Penetration Tester
+//  signatories:John Roe | Head of Offensive Security
+//
+// Every signatory gets a line to sign and date the printed report, `compile
+// --sign` additionally signs the PDF itself.
+
+pub const SIGNATORIES_KEY: &str = "signatories";
+
+pub struct Signatory {
+    pub name: String,
+    pub role: String,
+}
+
+/// Signatories in the order of the metadata file
+pub fn signatories(metadata: &[(String, String)]) -> Vec<Signatory> {
+    metadata
+        .iter()
+        .filter(|(key, _)| key == SIGNATORIES_KEY)
+        .map(|(_, value)| {
+            let (name, role) = value.split_once('|').unwrap_or((value, ""));
+            Signatory {
+                name: name.trim().to_string(),
+                role: role.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Sign-off page for `{{ sign_off }}`, empty without signatories
+pub fn render(report: &Report) -> String {
+    let signatories = signatories(&report.metadata);
+    if signatories.is_empty() {
+        return String::new();
+    }
+
+    // Names and roles are Typst markup like all metadata
+    let mut page = String::from(
+        "#pagebreak()\n= Sign-off\nThe undersigned approve the findings and conclusions of this report.\n\n#grid(\n  columns: (1fr, 1fr),\n  column-gutter: 32pt,\n  row-gutter: 48pt,\n",
+    );
+    for signatory in signatories {
+        let role = match signatory.role.is_empty() {
+            true => String::new(),
+            false => format!("  {} \\\n", signatory.role),
+        };
+        page.push_str(&format!(
+            "  [#v(40pt)\n  #line(length: 100%)\n  *{}* \\\n{role}  Date:],\n",
+            signatory.name
+        ));
+    }
+    page.push_str(")\n");
+    page
+}
@@ -0,0 +1,150 @@
+use chrono::{Duration, NaiveDate};
+use serde_json::json;
+
+use crate::config::Config;
+use crate::http::Client;
+use crate::report::{Entry, Report};
+use crate::roadmap;
+use crate::toml::TableExt;
+use crate::trend::PREVIOUS_REPORT_KEY;
+
+// Remediation deadlines of retests. While a retest (a report with
+// `previous_report`) isn't delivered, findings still open after their due
+// date breach the SLA and are shown by list, watch and digest, which also
+// posts them to `sla_webhook` of the config. The due date is `due` in the
+// front-matter of the finding:
+//
+//  due = "2026-11-01"
+//
+// or the report date of the previous engagement plus the remediation
+// timeline of the finding's priority, e.g. 7 days for criticals.
+
+pub const DUE_KEY: &str = "due";
+// Statuses of findings that need no more remediation
+pub const CLOSED_STATUSES: &[&str] = &["fixed", "resolved", "closed", "accepted"];
+
+pub struct Breach {
+    pub title: String,
+    pub severity: String,
+    pub due: NaiveDate,
+    // Days since the due date
+    pub overdue: i64,
+}
+
+impl Breach {
+    pub fn line(&self) -> String {
+        format!(
+            "{} ({}) overdue since {}, {} day(s)",
+            self.title, self.severity, self.due, self.overdue
+        )
+    }
+}
+
+/// Days of a timeline like `7 days`, `2 weeks` or `3 months`, `None` for
+/// ones without a duration like `next maintenance window`
+fn timeline_days(timeline: &str) -> Option<i64> {
+    let (count, unit) = timeline.trim().split_once(' ')?;
+    let count = count.parse::<i64>().ok()?;
+    let days = match unit.trim().trim_end_matches('s') {
+        "day" => 1,
+        "week" => 7,
+        "month" => 30,
+        "year" => 365,
+        _ => return None,
+    };
+    Some(count * days)
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Report date of the previous engagement, `None` if it has none in YYYY-MM-DD
+fn previous_date(report: &Report, previous: &str) -> Result<Option<NaiveDate>, String> {
+    let path = report.path.join(previous);
+    let previous = Report::load(&path)
+        .map_err(|e| format!("Failed to load previous report {}: {e}", path.display()))?;
+    Ok(previous.metadata_value("report_date").and_then(parse_date))
+}
+
+fn due(
+    finding: &Entry,
+    previous_date: Option<NaiveDate>,
+    severities: &[String],
+    timelines: &[(String, String)],
+) -> Result<Option<NaiveDate>, String> {
+    if let Some(due) = finding.meta.get_str(DUE_KEY) {
+        return parse_date(due).map(Some).ok_or(format!(
+            "{}: due has to be a date like 2026-11-01, got \"{due}\"",
+            finding.path.display()
+        ));
+    }
+    let Some(previous_date) = previous_date else {
+        return Ok(None);
+    };
+    let days = roadmap::priority(finding, severities).and_then(|priority| {
+        timelines
+            .iter()
+            .find(|(level, _)| *level == priority)
+            .and_then(|(_, timeline)| timeline_days(timeline))
+    });
+    Ok(days.map(|days| previous_date + Duration::days(days)))
+}
+
+/// Open findings past their due date, most overdue first, empty unless the
+/// report is a retest that isn't delivered yet
+pub fn breaches(report: &Report, config: &Config, today: NaiveDate) -> Result<Vec<Breach>, String> {
+    let Some(previous) = report.metadata_value(PREVIOUS_REPORT_KEY) else {
+        return Ok(Vec::new());
+    };
+    if report.metadata_value("state") == Some("delivered") {
+        return Ok(Vec::new());
+    }
+    let previous_date = previous_date(report, previous)?;
+    let severities = config.severities();
+    let timelines = config.remediation_timelines();
+
+    let mut breaches = Vec::new();
+    for finding in &report.findings {
+        let status = finding.meta.get_str("status").unwrap_or_default();
+        if CLOSED_STATUSES.contains(&status.to_lowercase().as_str()) {
+            continue;
+        }
+        let Some(due) = due(finding, previous_date, &severities, &timelines)? else {
+            continue;
+        };
+        if due < today {
+            breaches.push(Breach {
+                title: finding.title(),
+                severity: finding
+                    .meta
+                    .get_str("severity")
+                    .unwrap_or("unrated")
+                    .to_string(),
+                due,
+                overdue: (today - due).num_days(),
+            });
+        }
+    }
+    breaches.sort_by_key(|breach| breach.due);
+    Ok(breaches)
+}
+
+/// Posts the breaches of a report to `sla_webhook` as `{"text": ...}`, the
+/// message format of Slack, Mattermost and Teams incoming webhooks
+pub fn notify(
+    webhook: &str,
+    title: &str,
+    breaches: &[Breach],
+    offline: bool,
+) -> Result<(), String> {
+    let mut text = format!("{} overdue remediation(s) in {title}:", breaches.len());
+    for breach in breaches {
+        text.push_str(&format!("\n- {}", breach.line()));
+    }
+    let body = json!({ "text": text }).to_string();
+    Client::new(offline)
+        .post_json(webhook, &[], &body)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
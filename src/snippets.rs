@@ -0,0 +1,156 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::config::{config_home, Config};
+use crate::consts::{GLOBAL_SNIPPETS_DIR, SNIPPETS_DIR};
+use crate::report::{Entry, Report};
+use crate::source_map;
+
+// Text shared by many reports, like the standard disclaimer, methodology or
+// rating scale, is kept once as a snippet and included in sections, findings
+// and appendices with `{{ include:disclaimer }}`. Snippets are `<name>.typ`
+// files, looked up in order in
+//
+//  - the directory of `snippets` in the config
+//  - `snippets/` of the report or of the workspace it's in, nearest first
+//  - `$XDG_CONFIG_HOME/reportgenerator/snippets/`
+//
+// Snippets can include other snippets and use placeholders. Source markers
+// around snippets spanning several lines keep compile errors pointing at the
+// right file and line, except inside raw blocks where they would be shown.
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+const PREFIX: &str = "include:";
+// Deepest nesting of snippets, deeper ones most likely include themselves
+const MAX_DEPTH: usize = 8;
+
+/// Whether a placeholder name is a snippet like `include:disclaimer`
+pub fn is_include(name: &str) -> bool {
+    name.strip_prefix(PREFIX)
+        .is_some_and(|name| !name.trim().is_empty())
+}
+
+/// Directories snippets of the report are looked up in, in order
+fn dirs(report_path: &Path, config: &Config) -> Vec<PathBuf> {
+    let report_path = report_path
+        .canonicalize()
+        .unwrap_or(report_path.to_path_buf());
+    config
+        .snippets
+        .iter()
+        .cloned()
+        .chain(report_path.ancestors().map(|dir| dir.join(SNIPPETS_DIR)))
+        .chain(config_home().map(|home| home.join(GLOBAL_SNIPPETS_DIR)))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn load(name: &str, dirs: &[PathBuf]) -> Result<(PathBuf, String), String> {
+    // Names are file names, not paths out of the snippet directories
+    if name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("Invalid snippet name \"{name}\""));
+    }
+    let path = dirs
+        .iter()
+        .map(|dir| dir.join(format!("{name}.typ")))
+        .find(|path| path.is_file())
+        .ok_or(format!(
+            "Unknown snippet \"{name}\", add {name}.typ to a {SNIPPETS_DIR} directory of the report, its workspace or $XDG_CONFIG_HOME/{GLOBAL_SNIPPETS_DIR}"
+        ))?;
+    let content = read_to_string(&path)
+        .map_err(|e| format!("Failed to read snippet {}: {e}", path.display()))?;
+    Ok((path, content))
+}
+
+/// A line with its snippets included. With `markers`, a snippet that is the
+/// only thing on the line is marked as coming from its file.
+fn include_line(
+    line: &str,
+    markers: bool,
+    dirs: &[PathBuf],
+    depth: usize,
+) -> Result<String, String> {
+    let mut resolved = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(OPEN) {
+        let Some(end) = rest[start..].find(CLOSE) else {
+            break;
+        };
+        let inner = rest[start + OPEN.len()..start + end].trim();
+        let Some(name) = inner.strip_prefix(PREFIX).map(str::trim) else {
+            // Other placeholders are rendered later
+            resolved.push_str(&rest[..start + end + CLOSE.len()]);
+            rest = &rest[start + end + CLOSE.len()..];
+            continue;
+        };
+        if depth == MAX_DEPTH {
+            return Err(format!(
+                "Snippet \"{name}\" is nested more than {MAX_DEPTH} levels deep, does it include itself?"
+            ));
+        }
+        let (path, snippet) = load(name, dirs)?;
+        let alone = markers
+            && resolved.is_empty()
+            && rest[..start].trim().is_empty()
+            && rest[start + end + CLOSE.len()..].trim().is_empty();
+        let source = alone.then_some((path.as_path(), 1));
+        let snippet = include(&snippet, source, dirs, depth + 1)?;
+        if alone {
+            resolved.push_str(&source_map::marker(&path, 1));
+        }
+        resolved.push_str(&rest[..start]);
+        // Snippets end with a newline, the include line has its own
+        resolved.push_str(snippet.strip_suffix('\n').unwrap_or(&snippet));
+        rest = &rest[start + end + CLOSE.len()..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Text with its snippets included. With the `source` file and line of its
+/// first line, the lines following a snippet that spans several lines are
+/// marked as coming from the file again.
+fn include(
+    text: &str,
+    source: Option<(&Path, usize)>,
+    dirs: &[PathBuf],
+    depth: usize,
+) -> Result<String, String> {
+    let mut resolved = String::new();
+    let mut in_raw = false;
+    let mut shifted = false;
+    for (n, line) in text.split_inclusive('\n').enumerate() {
+        let source = source.filter(|_| !in_raw);
+        if let (true, Some((path, first))) = (shifted, source) {
+            resolved.push_str(&source_map::marker(path, first + n));
+            shifted = false;
+        }
+        if line.trim_start().starts_with("```") {
+            in_raw = !in_raw;
+        }
+        let expanded = include_line(line, source.is_some(), dirs, depth)?;
+        shifted |= expanded.matches('\n').count() != line.matches('\n').count();
+        resolved.push_str(&expanded);
+    }
+    Ok(resolved)
+}
+
+fn include_entries(entries: &mut [Entry], dirs: &[PathBuf]) -> Result<(), String> {
+    for entry in entries {
+        entry.body = include(&entry.body, Some((&entry.path, entry.line)), dirs, 0)
+            .map_err(|e| format!("{}: {e}", entry.path.display()))?;
+        include_entries(&mut entry.children, dirs)?;
+    }
+    Ok(())
+}
+
+/// Includes the snippets of every section, finding and appendix
+pub fn resolve(report: &mut Report, config: &Config) -> Result<(), String> {
+    let dirs = dirs(&report.path, config);
+    include_entries(&mut report.sections, &dirs)?;
+    include_entries(&mut report.findings, &dirs)?;
+    include_entries(&mut report.appendices, &dirs)
+}
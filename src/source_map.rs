@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use crate::report::Entry;
+
+// Maps lines of the assembled report back to the section/finding files they
+// came from. Every entry is wrapped in marker comments, which typst ignores:
+//
+//  // source: report/findings/1.sqli.typ:4
+//  = Finding: SQL Injection
+//  ...
+//  // source: end
+//
+// Snippets spanning several lines add a marker of their own file before them
+// and one resuming the entry's file after them. Placeholders like
+// `{{ findings_overview }}` expand to any number of lines, the line after
+// them is marked again before they are rendered.
+
+const MARKER: &str = "// source: ";
+const END: &str = "end";
+
+// Name of the report passed to typst on stdin, as used in its diagnostics
+const STDIN_NAME: &str = "<stdin>";
+
+/// Marker line mapping the lines that follow to `path` from `line` on
+pub fn marker(path: &Path, line: usize) -> String {
+    format!("{MARKER}{}:{line}\n", path.display())
+}
+
+/// Wraps the rendered `content` of `entry` in source markers
+pub fn wrap(entry: &Entry, content: &str) -> String {
+    let mut wrapped = marker(&entry.path, entry.line) + content;
+    if !wrapped.ends_with('\n') {
+        wrapped.push('\n');
+    }
+    wrapped.push_str(&format!("{MARKER}{END}\n"));
+    wrapped
+}
+
+/// Body of `entry` with a marker after every line with a placeholder, so
+/// lines after a multi-line value keep their place. Raw blocks are marked
+/// after they end, markers in them would be shown.
+pub fn mark_placeholders(entry: &Entry, body: &str) -> String {
+    let mut path = entry.path.display().to_string();
+    let mut next = entry.line;
+    let mut marked = String::with_capacity(body.len());
+    let mut in_raw = false;
+    let mut pending = false;
+    for line in body.split_inclusive('\n') {
+        marked.push_str(line);
+        // Included snippets bring their own markers
+        if let Some((file, start)) = line
+            .trim()
+            .strip_prefix(MARKER)
+            .and_then(|source| source.rsplit_once(':'))
+            .and_then(|(file, start)| Some((file, start.parse::<usize>().ok()?)))
+        {
+            path = file.to_string();
+            next = start;
+            continue;
+        }
+        next += 1;
+        if line.trim_start().starts_with("```") {
+            in_raw = !in_raw;
+        }
+        pending |= line.contains("{{") && line.contains("}}");
+        if pending && !in_raw {
+            if !line.ends_with('\n') {
+                marked.push('\n');
+            }
+            marked.push_str(&format!("{MARKER}{path}:{next}\n"));
+            pending = false;
+        }
+    }
+    marked
+}
+
+/// File and line the 1-based `line` of the assembled `document` originates from
+pub fn locate(document: &str, line: usize) -> Option<(String, usize)> {
+    let lines: Vec<&str> = document.lines().take(line).collect();
+    if lines.len() < line {
+        return None;
+    }
+    // Nearest marker above the line, nested entries close their own block
+    for (index, text) in lines.iter().enumerate().rev() {
+        let Some(source) = text.trim_start().strip_prefix(MARKER) else {
+            continue;
+        };
+        let (path, start) = source.rsplit_once(':')?;
+        let start = start.parse::<usize>().ok()?;
+        return Some((path.to_string(), start + line - index - 2));
+    }
+    None
+}
+
+/// Rewrites typst's short diagnostics (`<stdin>:12:5: error: ..`) to point
+/// at the originating files, other lines are kept as they are
+pub fn translate(diagnostics: &str, document: &str) -> String {
+    let mut translated = String::new();
+    for line in diagnostics.lines() {
+        let location = line
+            .strip_prefix(STDIN_NAME)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .and_then(|rest| {
+                let mut parts = rest.splitn(3, ':');
+                let line = parts.next()?.parse::<usize>().ok()?;
+                let column = parts.next()?;
+                Some((line, column, parts.next()?))
+            });
+        match location.and_then(|(l, column, rest)| {
+            locate(document, l).map(|(path, l)| format!("{path}:{l}:{column}:{rest}"))
+        }) {
+            Some(mapped) => translated.push_str(&mapped),
+            None => translated.push_str(line),
+        }
+        translated.push('\n');
+    }
+    translated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::toml::Table;
+    use std::path::PathBuf;
+
+    fn entry(body: &str) -> Entry {
+        Entry {
+            id: 1,
+            name: "summary".to_string(),
+            path: PathBuf::from("sections/1.summary.typ"),
+            meta: Table::default(),
+            body: body.to_string(),
+            // Below two lines of front-matter
+            line: 4,
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lines_after_multi_line_placeholders_keep_their_place() {
+        let entry = entry("= Summary\n{{ risk_matrix }}\nText\n```\n{{ hosts }}\n```\n#lorem(\n");
+        let marked = mark_placeholders(&entry, &entry.body);
+        let rendered = marked
+            .replace("{{ risk_matrix }}", "#table(\n  a,\n  b,\n)")
+            .replace("{{ hosts }}", "a\nb");
+        let document = format!("#set page()\n{}", wrap(&entry, &rendered));
+        let find = |text: &str| {
+            let line = document.lines().position(|line| line == text).unwrap() + 1;
+            locate(&document, line)
+        };
+        let path = "sections/1.summary.typ".to_string();
+        assert_eq!(find("= Summary"), Some((path.clone(), 4)));
+        assert_eq!(find("Text"), Some((path.clone(), 6)));
+        assert_eq!(find("#lorem("), Some((path, 10)));
+    }
+}
@@ -0,0 +1,162 @@
+use std::{error::Error, path::PathBuf, process::exit};
+
+use ::toml::Value;
+use serde_json::json;
+
+use crate::assets;
+use crate::config::Config;
+use crate::cwe;
+use crate::evidence;
+use crate::http_evidence;
+use crate::report::{self, Entry, Report};
+use crate::summary;
+use crate::timesheet::Timesheet;
+use crate::toml::TableExt;
+
+// Engagement metrics for practice-wide dashboards: findings per severity,
+// CWE, OWASP category and asset, evidence items and an estimate of the pages
+// of the compiled report.
+
+// Words of body text on a page of the built-in template
+const WORDS_PER_PAGE: usize = 450;
+// Screenshots are shown at up to 80% width, about a third of a page
+const SCREENSHOTS_PER_PAGE: usize = 3;
+// Cover, document control and table of contents
+const FRONT_PAGES: usize = 3;
+
+/// Counts of the values a finding lists under `key`, most common first
+fn count_by(findings: &[Entry], key: &str) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for finding in findings {
+        for value in finding.meta.get_str_list(key) {
+            match counts.iter_mut().find(|(v, _)| *v == value) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((value, 1)),
+            }
+        }
+    }
+    counts.sort_by(|(a, x), (b, y)| y.cmp(x).then(a.cmp(b)));
+    counts
+}
+
+/// Screenshots and HTTP exchanges of a finding
+fn evidence_items(finding: &Entry) -> usize {
+    let exchanges = match finding.meta.get(http_evidence::FRONT_MATTER_KEY) {
+        Some(Value::Array(pairs)) => pairs.len(),
+        _ => 0,
+    };
+    evidence::screenshots(finding).len() + exchanges
+}
+
+/// Pages of an entry, every entry starts on a new page
+fn pages(entry: &Entry) -> usize {
+    if entry.is_chapter() {
+        return entry.children.iter().map(pages).sum();
+    }
+    let words = entry.body.split_whitespace().count();
+    let screenshots = evidence::screenshots(entry).len();
+    (words.div_ceil(WORDS_PER_PAGE) + screenshots.div_ceil(SCREENSHOTS_PER_PAGE)).max(1)
+}
+
+fn page_estimate(report: &Report) -> usize {
+    let entries = report
+        .sections
+        .iter()
+        .chain(&report.findings)
+        .chain(&report.appendices);
+    FRONT_PAGES + entries.map(pages).sum::<usize>()
+}
+
+fn counts_json(counts: &[(String, usize)]) -> serde_json::Value {
+    counts
+        .iter()
+        .map(|(key, count)| (key.clone(), json!(count)))
+        .collect()
+}
+
+fn print_counts(title: &str, counts: &[(String, usize)], name: impl Fn(&str) -> String) {
+    println!("\n{title}");
+    if counts.is_empty() {
+        println!("  -");
+    }
+    for (key, count) in counts {
+        println!("  {count:>4}  {}", name(key));
+    }
+}
+
+/// Prints the metrics of a report, or with `effort` its time spent, as a table or JSON
+pub fn stats(
+    report_dir: Option<PathBuf>,
+    format: Option<String>,
+    effort: bool,
+) -> Result<(), Box<dyn Error>> {
+    let json = match format.as_deref().unwrap_or("text") {
+        "text" => false,
+        "json" => true,
+        format => {
+            eprintln!("ERROR: Unknown format \"{format}\", expected text or json");
+            exit(1);
+        }
+    };
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    if effort {
+        let timesheet = Timesheet::of(&report).unwrap_or_else(|e| {
+            eprintln!("ERROR: {e}");
+            exit(1);
+        });
+        match json {
+            true => println!("{}", serde_json::to_string_pretty(&timesheet.to_json())?),
+            false => timesheet.print(),
+        }
+        return Ok(());
+    }
+    let config = Config::load_or_exit(&report_path);
+    let assets = assets::load(&report_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+
+    let severities = summary::severity_counts(&report.findings, &config.severities());
+    let cwes = count_by(&report.findings, "cwe");
+    let owasp = count_by(&report.findings, "owasp");
+    let per_asset = count_by(&report.findings, assets::FRONT_MATTER_KEY);
+    let evidence: usize = report.findings.iter().map(evidence_items).sum();
+    let pages = page_estimate(&report);
+
+    if json {
+        let stats = json!({
+            "findings": report.findings.len(),
+            "severities": counts_json(&severities),
+            "cwe": counts_json(&cwes),
+            "owasp": counts_json(&owasp),
+            "assets": counts_json(&per_asset),
+            "evidence_items": evidence,
+            "estimated_pages": pages,
+        });
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        report.metadata_value("report_title").unwrap_or("Report")
+    );
+    println!("\n  {:>4}  findings", report.findings.len());
+    println!("  {evidence:>4}  evidence items");
+    println!("  {pages:>4}  pages (estimated)");
+    print_counts("Severity", &severities, str::to_string);
+    print_counts("CWE", &cwes, |id| match cwe::lookup(id) {
+        Some(name) => format!("{id} {}", cwe::short_name(name)),
+        None => id.to_string(),
+    });
+    print_counts("OWASP Top 10", &owasp, str::to_string);
+    print_counts("Assets", &per_asset, |id| {
+        match assets.iter().find(|asset| asset.id == id) {
+            Some(asset) if !asset.name.is_empty() => format!("{} ({id})", asset.name),
+            _ => id.to_string(),
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use std::{cmp::Reverse, error::Error, path::PathBuf};
+
+use crate::config::Config;
+use crate::cwe;
+use crate::report::{self, Entry, Report};
+use crate::severity::Presentation;
+use crate::toml::TableExt;
+use crate::utils::{typst_escape, typst_str};
+use crate::xref::{self, IdScheme};
+
+// Starting paragraph for the executive summary built from the finding
+// statistics, available as `{{ executive_summary }}` and printed by `summary`
+// so authors can paste and edit it.
+
+// Number of categories named in the summary
+const TOP_CATEGORIES: usize = 2;
+
+/// Joins items as `a, b and c`
+fn enumerate(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+/// Most common weakness categories of the findings, most frequent first
+fn categories(findings: &[Entry]) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for finding in findings {
+        for id in finding.meta.get_str_list("cwe") {
+            let name = cwe::lookup(&id).map_or(id.clone(), |n| cwe::short_name(n).to_string());
+            match counts.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name, 1)),
+            }
+        }
+    }
+    // Stable sort keeps the report order between equally common categories
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    counts
+        .into_iter()
+        .take(TOP_CATEGORIES)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Lowercased severity of a finding, if it is part of the scheme
+fn severity_of(finding: &Entry, severities: &[String]) -> Option<String> {
+    finding
+        .meta
+        .get_str("severity")
+        .map(str::to_lowercase)
+        .filter(|s| severities.contains(s))
+}
+
+/// Number of findings per severity in the order of the scheme, unrated ones last
+pub fn severity_counts(findings: &[Entry], severities: &[String]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = severities
+        .iter()
+        .map(|severity| {
+            let count = findings
+                .iter()
+                .filter(|f| severity_of(f, severities).as_ref() == Some(severity))
+                .count();
+            (severity.clone(), count)
+        })
+        .collect();
+    let unrated = findings
+        .iter()
+        .filter(|f| severity_of(f, severities).is_none())
+        .count();
+    counts.push(("unrated".to_string(), unrated));
+    counts
+}
+
+/// Horizontal bar per severity, scaled to the most common one
+fn severity_chart(counts: &[(String, usize)], presentation: &Presentation) -> String {
+    let max = counts
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let mut chart = String::from("#table(columns: (auto, 1fr, auto), stroke: none, align: horizon");
+    for (severity, count) in counts {
+        chart.push_str(&format!(
+            ", [{}], rect(width: {}%, height: 10pt, fill: rgb({})), [{count}]",
+            typst_escape(&presentation.label(severity)),
+            count * 100 / max,
+            typst_str(presentation.color(severity))
+        ));
+    }
+    chart.push(')');
+    chart
+}
+
+/// Computed finding statistics placeholders, e.g. `{{ total_findings }}` or `{{ high_findings }}`
+pub fn statistics(
+    report: &Report,
+    severities: &[String],
+    presentation: &Presentation,
+) -> Vec<(String, String)> {
+    let counts = severity_counts(&report.findings, severities);
+    let highest = counts
+        .iter()
+        .find(|(severity, count)| *count > 0 && severity != "unrated")
+        .map_or("none".to_string(), |(severity, _)| {
+            presentation.label(severity)
+        });
+
+    let mut table = String::from("#table(columns: 2, [*Severity*], [*Findings*]");
+    for (severity, count) in &counts {
+        table.push_str(&format!(", [{}], [{count}]", presentation.badge(severity)));
+    }
+    table.push(')');
+
+    let mut statistics = vec![
+        (
+            "total_findings".to_string(),
+            report.findings.len().to_string(),
+        ),
+        ("highest_severity".to_string(), typst_escape(&highest)),
+        ("finding_count_by_severity".to_string(), table),
+        (
+            "severity_chart".to_string(),
+            severity_chart(&counts, presentation),
+        ),
+    ];
+    for (severity, count) in counts {
+        statistics.push((format!("{severity}_findings"), count.to_string()));
+    }
+    statistics
+}
+
+/// Table of every finding with its ID and severity for `{{ findings_overview }}`
+pub fn findings_overview(report: &Report, ids: &IdScheme, presentation: &Presentation) -> String {
+    if report.findings.is_empty() {
+        return String::new();
+    }
+    let mut table = String::from(
+        "#table(\n  columns: (auto, 1fr, auto),\n  [*ID*], [*Finding*], [*Severity*],\n",
+    );
+    for finding in &report.findings {
+        let severity = finding
+            .meta
+            .get_str("severity")
+            .map_or(String::new(), |s| presentation.badge(s));
+        table.push_str(&format!(
+            "  [#link(<{}>)[{}]], [{}], [{severity}],\n",
+            xref::label(finding.id),
+            typst_escape(&ids.id(finding)),
+            xref::finding_title(finding),
+        ));
+    }
+    table.push_str(")\n");
+    table
+}
+
+/// Whether no finding is rated `threshold` or more severe, unrated findings count
+pub fn is_clean(findings: &[Entry], severities: &[String], threshold: &str) -> bool {
+    let Some(limit) = severities.iter().position(|s| s == threshold) else {
+        return false;
+    };
+    findings.iter().all(|finding| {
+        severity_of(finding, severities)
+            .and_then(|s| severities.iter().position(|level| *level == s))
+            .is_some_and(|level| level > limit)
+    })
+}
+
+/// Positive assurance wording for a clean result, `client` is Typst markup
+pub fn assurance_statement(client: Option<&str>, threshold: &str) -> String {
+    let tested = match client {
+        Some(client) => format!("testing of {client}"),
+        None => "testing".to_string(),
+    };
+    format!(
+        "Within the agreed scope and timeframe, {tested} did not identify any vulnerabilities \
+         rated {} or higher. This result reflects the security posture of the tested systems \
+         at the time of testing and is no guarantee that no vulnerabilities exist.",
+        typst_escape(threshold)
+    )
+}
+
+pub fn executive_summary(
+    report: &Report,
+    severities: &[String],
+    presentation: &Presentation,
+) -> String {
+    let findings = &report.findings;
+    if findings.is_empty() {
+        return "Testing identified no security issues.".to_string();
+    }
+
+    // Counts in the order of the severity scheme, unrated findings last
+    let counts: Vec<String> = severity_counts(findings, severities)
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(severity, count)| format!("{count} {}", presentation.label(&severity)))
+        .collect();
+
+    let issues = if findings.len() == 1 {
+        "issue"
+    } else {
+        "issues"
+    };
+    let mut summary = format!("Testing identified {} risk {issues}", enumerate(&counts));
+
+    let categories = categories(findings);
+    if !categories.is_empty() {
+        summary.push_str(&format!(
+            ", primarily related to {}",
+            enumerate(&categories)
+        ));
+    }
+    summary.push('.');
+
+    // Findings are expected to be ordered by severity already, pick the worst rated one
+    let most_severe = severities.iter().find_map(|severity| {
+        findings
+            .iter()
+            .find(|f| severity_of(f, severities).as_ref() == Some(severity))
+    });
+    if let Some(finding) = most_severe {
+        summary.push_str(&format!(
+            " The most severe issue is {}.",
+            finding.title().trim_start_matches("Finding:").trim()
+        ));
+    }
+
+    typst_escape(&summary)
+}
+
+pub fn summary(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+
+    println!(
+        "{}",
+        executive_summary(
+            &report,
+            &config.severities(),
+            &config.severity_presentation()
+        )
+    );
+
+    Ok(())
+}
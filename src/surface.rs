@@ -0,0 +1,183 @@
+use std::{
+    error::Error,
+    fs::read_to_string,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use serde_json::Value;
+
+use crate::changes::Changes;
+use crate::consts::ATTACK_SURFACE_FILE;
+use crate::report::{self, Report};
+use crate::utils::typst_str;
+
+// Subdomains found by amass or subfinder, kept in `attack-surface.tsv` of the
+// report as `subdomain<TAB>ip<TAB>status` lines and shown as the
+// `{{ attack_surface }}` appendix. Imports merge into the file, statuses
+// edited by hand are kept.
+
+pub struct Subdomain {
+    pub name: String,
+    // Comma-separated addresses, empty if it didn't resolve
+    pub ip: String,
+    pub status: String,
+}
+
+fn default_status(ip: &str) -> &'static str {
+    match ip.is_empty() {
+        true => "unresolved",
+        false => "resolved",
+    }
+}
+
+/// Subdomains of the report, none without the file
+pub fn load(report_path: &Path) -> Result<Vec<Subdomain>, String> {
+    let path = report_path.join(ATTACK_SURFACE_FILE);
+    let content = match read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.split('\t').map(|f| f.trim().to_string());
+            let name = fields.next().unwrap_or_default();
+            let ip = fields.next().unwrap_or_default();
+            let status = fields
+                .next()
+                .unwrap_or_else(|| default_status(&ip).to_string());
+            Subdomain { name, ip, status }
+        })
+        .collect())
+}
+
+/// Subdomains and addresses of amass/subfinder JSON lines or plain
+/// `subdomain[,ip]` lines, as both tools print by default
+fn parse(content: &str) -> Vec<(String, Vec<String>)> {
+    let mut found = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let (name, ips) = match serde_json::from_str::<Value>(line) {
+            // amass names the subdomain `name`, subfinder `host`
+            Ok(json) => {
+                let field = |key: &str| json[key].as_str().map(str::to_string);
+                let Some(name) = field("name").or_else(|| field("host")) else {
+                    continue;
+                };
+                let mut ips: Vec<String> = json["addresses"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|a| a["ip"].as_str().map(str::to_string))
+                    .collect();
+                ips.extend(field("ip"));
+                (name, ips)
+            }
+            Err(_) => {
+                let mut fields = line.split(',').map(str::trim);
+                let name = fields.next().unwrap_or_default().to_string();
+                (name, fields.map(str::to_string).collect())
+            }
+        };
+        found.push((name.to_lowercase(), ips));
+    }
+    found
+}
+
+fn serialize(subdomains: &[Subdomain]) -> String {
+    subdomains
+        .iter()
+        .map(|s| format!("{}\t{}\t{}\n", s.name, s.ip, s.status))
+        .collect()
+}
+
+/// Attack surface appendix, empty without subdomains
+pub fn render_appendix(subdomains: &[Subdomain]) -> String {
+    if subdomains.is_empty() {
+        return String::new();
+    }
+    let mut appendix = String::from(
+        "#pagebreak()\n= Appendix: Attack Surface\n#table(\n  columns: (1fr, auto, auto),\n  \
+         table.header([*Subdomain*], [*IP*], [*Status*]),\n",
+    );
+    for subdomain in subdomains {
+        appendix.push_str(&format!(
+            "  {}, {}, {},\n",
+            typst_str(&subdomain.name),
+            typst_str(&subdomain.ip.replace(',', ", ")),
+            typst_str(&subdomain.status)
+        ));
+    }
+    appendix.push_str(")\n");
+    appendix
+}
+
+pub fn import_subdomains(
+    file: String,
+    report_dir: Option<PathBuf>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    Report::load_or_exit(&report_path);
+
+    let content = read_to_string(&file).unwrap_or_else(|e| {
+        eprintln!("ERROR: Failed to read {file}: {e}");
+        exit(1);
+    });
+    let mut subdomains = load(&report_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+
+    let mut added = 0;
+    for (name, ips) in parse(&content) {
+        let existing = match subdomains.iter_mut().find(|s| s.name == name) {
+            Some(existing) => existing,
+            None => {
+                added += 1;
+                subdomains.push(Subdomain {
+                    name,
+                    ip: String::new(),
+                    status: String::new(),
+                });
+                subdomains.last_mut().unwrap()
+            }
+        };
+        let mut known: Vec<String> = existing
+            .ip
+            .split(',')
+            .filter(|ip| !ip.is_empty())
+            .map(str::to_string)
+            .collect();
+        for ip in ips {
+            if !known.contains(&ip) {
+                known.push(ip);
+            }
+        }
+        existing.ip = known.join(",");
+        // Only generated statuses are updated, edited ones are kept
+        if existing.status.is_empty() || existing.status == "unresolved" {
+            existing.status = default_status(&existing.ip).to_string();
+        }
+    }
+    subdomains.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.write(
+        report_path.join(ATTACK_SURFACE_FILE),
+        &serialize(&subdomains),
+    );
+    changes.apply(dry_run)?;
+
+    if !dry_run {
+        println!(
+            "Imported {added} new subdomain(s), {} in total",
+            subdomains.len()
+        );
+    }
+
+    Ok(())
+}
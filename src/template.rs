@@ -1,17 +1,8 @@
-use std::{fs::read_to_string, path::PathBuf};
-
 pub struct Template {
     template: String,
 }
 
 impl Template {
-    #[allow(dead_code)]
-    pub fn new(file: PathBuf) -> Self {
-        Self {
-            template: read_to_string(file).expect("Failed to read template file"),
-        }
-    }
-
     pub fn from_str(template: &str) -> Self {
         Self {
             template: template.to_string(),
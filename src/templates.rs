@@ -0,0 +1,217 @@
+use std::{
+    error::Error,
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    path::{Path, PathBuf},
+    process::{exit, Command},
+};
+
+use crate::brand::Brand;
+use crate::changes::Changes;
+use crate::check;
+use crate::config::Config;
+use crate::consts::*;
+use crate::page;
+use crate::partials;
+use crate::report;
+use crate::summary;
+use crate::template::Template;
+use crate::utils::get_current_date;
+
+// Resolution of the preview thumbnails
+const PREVIEW_PPI: &str = "40";
+
+// Placeholders renamed in the built-in template, old name first. Firm templates
+// list theirs under `[renames]` in the config.
+const RENAMES: &[(&str, &str)] = &[];
+
+/// Renders the first page of a Typst document to `<name>.png` in the gallery
+fn render_preview(
+    typst: &str,
+    gallery: &Path,
+    name: &str,
+    document: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let input = gallery.join(format!("{name}.typ"));
+    let output = gallery.join(format!("{name}.png"));
+    write(&input, document)?;
+
+    let status = Command::new(typst)
+        .arg("compile")
+        .arg(&input)
+        .arg(&output)
+        .args(["--format", "png", "--pages", "1", "--ppi", PREVIEW_PPI])
+        .status()
+        .expect("Failed to execute typst\nEnsure you have 'typst' installed on your system");
+
+    remove_file(&input)?;
+
+    if !status.success() {
+        return Err(format!("typst failed to render the \"{name}\" preview").into());
+    }
+
+    Ok(output)
+}
+
+/// Renders previews of the report layout and every section/finding template
+fn gallery(output: Option<String>) -> Result<(), Box<dyn Error>> {
+    let gallery = PathBuf::from(output.as_deref().unwrap_or(DEFAULT_GALLERY_DIR));
+    // Config of the report in the current directory, if any
+    let config = Config::load_or_exit(Path::new("."));
+    create_dir_all(&gallery)?;
+
+    // Report layout with the example metadata, its first page is the cover
+    let metadata = report::parse_metadata(T_METADATA);
+    let current_date = get_current_date();
+    let mut context: Vec<(&str, &str)> = vec![
+        ("sections", T_SECTION),
+        ("findings", T_FINDING),
+        ("appendices", ""),
+        ("current_date", &current_date),
+        ("attack_appendix", ""),
+        ("environment_matrix", ""),
+        ("host_appendix", ""),
+        ("asset_matrix", ""),
+        ("attack_surface", ""),
+        ("remediation_roadmap", ""),
+        ("screenshot_gallery", ""),
+        ("document_control", ""),
+        ("cover_logo", ""),
+        ("sign_off", ""),
+        ("git_commit", ""),
+        ("observations", ""),
+    ];
+    for (key, value) in &metadata {
+        context.push((key, value));
+    }
+    let assurance_statement = summary::assurance_statement(None, "low");
+    context.push(("assurance_statement", &assurance_statement));
+    let brand = Brand::default().context(Path::new("."), true)?;
+    let page_layout = page::context(None, &config)?;
+    for (key, value) in brand.iter().chain(&page_layout) {
+        context.push((key, value));
+    }
+    let mut previews = vec![
+        (
+            "report".to_string(),
+            Template::from_str(&partials::resolve(MAIN_TEMPLATE, &config)?).render(&context),
+        ),
+        (
+            "report-clean-result".to_string(),
+            Template::from_str(&partials::resolve(CLEAN_RESULT_TEMPLATE, &config)?)
+                .render(&context),
+        ),
+    ];
+
+    // Cover pages of the themes
+    for theme in partials::themes() {
+        previews.push((
+            format!("theme-{theme}"),
+            Template::from_str(&partials::resolve_themed(MAIN_TEMPLATE, &config, theme)?)
+                .render(&context),
+        ));
+    }
+
+    for (name, content) in SECTION_TEMPLATES {
+        previews.push((format!("section-{name}"), content.to_string()));
+    }
+    for (name, content) in FINDING_TEMPLATES {
+        previews.push((format!("finding-{name}"), content.to_string()));
+    }
+
+    let mut index = String::from(
+        "<!DOCTYPE html>\n<html>\n<head><title>Report templates</title></head>\n<body>\n",
+    );
+    for (name, document) in previews {
+        let preview = render_preview(config.typst(), &gallery, &name, &document)?;
+        let file_name = preview.file_name().unwrap_or_default().to_string_lossy();
+        index.push_str(&format!(
+            "<figure style=\"display:inline-block\"><img src=\"{file_name}\" alt=\"{name}\">\
+             <figcaption>{name}</figcaption></figure>\n"
+        ));
+        println!("Rendered {}", preview.display());
+    }
+    index.push_str("</body>\n</html>\n");
+    write(gallery.join("index.html"), index)?;
+
+    println!("Template gallery written to {}", gallery.display());
+
+    Ok(())
+}
+
+/// Renames metadata keys and `{{ placeholders }}` of a report to the current template
+fn migrate(report_dir: Option<PathBuf>, dry_run: bool) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let config = Config::load_or_exit(&report_path);
+
+    let renames: Vec<(String, String)> = RENAMES
+        .iter()
+        .map(|(old, new)| (old.to_string(), new.to_string()))
+        .chain(config.renames.unwrap_or_default())
+        .collect();
+    let rename = |key: &str| {
+        renames
+            .iter()
+            .find(|(old, _)| old == key)
+            .map(|(_, new)| new.clone())
+    };
+
+    let mut changes = Changes::for_report(&report_path);
+    let mut migrated = 0;
+
+    // Metadata keys are used as placeholders of the same name
+    let metadata_path = report_path.join("metadata.typ");
+    let metadata = read_to_string(&metadata_path)?;
+    let content: String = metadata
+        .split_inclusive('\n')
+        .map(|line| match line.split_once(':') {
+            Some((key, value)) => match rename(key.trim()) {
+                Some(new) => format!("{new}:{value}"),
+                None => line.to_string(),
+            },
+            None => line.to_string(),
+        })
+        .collect();
+    if content != metadata {
+        changes.write(metadata_path, &content);
+        migrated += 1;
+    }
+
+    for path in check::report_files(&report_path)? {
+        let text = read_to_string(&path)?;
+        let mut content = text.clone();
+        for (old, new) in &renames {
+            content = content.replace(&format!("{{{{ {old} }}}}"), &format!("{{{{ {new} }}}}"));
+        }
+        if content != text {
+            changes.write(path, &content);
+            migrated += 1;
+        }
+    }
+
+    if migrated == 0 {
+        println!("Nothing to migrate");
+        return Ok(());
+    }
+    changes.apply(dry_run)?;
+    if !dry_run {
+        println!("Migrated {migrated} file(s)");
+    }
+
+    Ok(())
+}
+
+pub fn templates(
+    action: String,
+    report_dir: Option<PathBuf>,
+    output: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    match action.as_str() {
+        "gallery" => gallery(output),
+        "migrate" => migrate(report_dir, dry_run),
+        _ => {
+            eprintln!("ERROR: Unknown templates action \"{action}\", expected gallery or migrate");
+            exit(1);
+        }
+    }
+}
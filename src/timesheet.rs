@@ -0,0 +1,150 @@
+use serde_json::{json, Value};
+
+use crate::report::{Entry, Kind, Report};
+use crate::toml::TableExt;
+
+// Time spent on an engagement for billing and estimating the next one. Any
+// section, finding or appendix records it in its front-matter, optionally
+// with the phase of the engagement it belongs to:
+//
+//  time_spent = "1h30m"
+//  phase = "exploitation"
+//
+// Durations are given in days (8 hours), hours and minutes. Entries without
+// a phase count for the kind of entry, e.g. reporting time for sections.
+// `effort` is the remediation effort of a finding, see roadmap.
+
+pub const TIME_SPENT_KEY: &str = "time_spent";
+pub const PHASE_KEY: &str = "phase";
+
+const HOURS_PER_DAY: f64 = 8.0;
+
+/// Hours of a duration like `2h`, `45m`, `1.5d` or `1h30m`
+pub fn parse_hours(duration: &str) -> Option<f64> {
+    let mut hours = 0.0;
+    let mut number = String::new();
+    let mut parts = 0;
+    for c in duration.trim().chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'd' | 'h' | 'm' => {
+                let value = number.parse::<f64>().ok()?;
+                hours += match c {
+                    'd' => value * HOURS_PER_DAY,
+                    'h' => value,
+                    _ => value / 60.0,
+                };
+                number.clear();
+                parts += 1;
+            }
+            ' ' => continue,
+            _ => return None,
+        }
+    }
+    (number.is_empty() && parts > 0).then_some(hours)
+}
+
+/// Hours without trailing zeros, e.g. `1.5h`
+pub fn format_hours(hours: f64) -> String {
+    let hours = format!("{hours:.2}");
+    format!("{}h", hours.trim_end_matches('0').trim_end_matches('.'))
+}
+
+fn default_phase(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Section => "sections",
+        Kind::Finding => "findings",
+        Kind::Appendix => "appendices",
+    }
+}
+
+pub struct Timesheet {
+    // Phases in order of appearance with their hours
+    pub phases: Vec<(String, f64)>,
+    // Entries not recording their time
+    pub untracked: usize,
+}
+
+impl Timesheet {
+    pub fn of(report: &Report) -> Result<Self, String> {
+        let mut timesheet = Timesheet {
+            phases: Vec::new(),
+            untracked: 0,
+        };
+        for kind in [Kind::Section, Kind::Finding, Kind::Appendix] {
+            let mut entries = Vec::new();
+            flatten(report.entries_of(kind), &mut entries);
+            for entry in entries {
+                timesheet.add(entry, default_phase(kind))?;
+            }
+        }
+        Ok(timesheet)
+    }
+
+    fn add(&mut self, entry: &Entry, default_phase: &str) -> Result<(), String> {
+        let Some(duration) = entry.meta.get_str(TIME_SPENT_KEY) else {
+            // Chapters only group their entries
+            if !entry.is_chapter() {
+                self.untracked += 1;
+            }
+            return Ok(());
+        };
+        let hours = parse_hours(duration).ok_or(format!(
+            "{}: {TIME_SPENT_KEY} has to be a duration like 2h, 45m or 1h30m, got \"{duration}\"",
+            entry.path.display()
+        ))?;
+        let phase = entry.meta.get_str(PHASE_KEY).unwrap_or(default_phase);
+        match self.phases.iter_mut().find(|(p, _)| p == phase) {
+            Some((_, total)) => *total += hours,
+            None => self.phases.push((phase.to_string(), hours)),
+        }
+        Ok(())
+    }
+
+    pub fn total(&self) -> f64 {
+        self.phases
+            .iter()
+            .fold(0.0, |total, (_, hours)| total + hours)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let phases: Value = self
+            .phases
+            .iter()
+            .map(|(phase, hours)| (phase.clone(), json!(hours)))
+            .collect();
+        json!({
+            "phases": phases,
+            "total_hours": self.total(),
+            "untracked_entries": self.untracked,
+        })
+    }
+
+    pub fn print(&self) {
+        let width = self
+            .phases
+            .iter()
+            .map(|(phase, _)| phase.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("total".len());
+        for (phase, hours) in &self.phases {
+            println!("  {phase:<width$}  {:>8}", format_hours(*hours));
+        }
+        println!("  {:<width$}  {:>8}", "total", format_hours(self.total()));
+        if self.untracked > 0 {
+            println!(
+                "\n{} entr{} without {TIME_SPENT_KEY}",
+                self.untracked,
+                if self.untracked == 1 { "y" } else { "ies" }
+            );
+        }
+    }
+}
+
+fn flatten<'a>(entries: &'a [Entry], flat: &mut Vec<&'a Entry>) {
+    for entry in entries {
+        flat.push(entry);
+        flatten(&entry.children, flat);
+    }
+}
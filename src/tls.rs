@@ -0,0 +1,399 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use serde_json::Value;
+
+use crate::changes::Changes;
+use crate::report::{self, Report};
+use crate::toml;
+use crate::utils::{typst_escape, typst_str};
+
+// Imports TLS scan results of testssl.sh (`--jsonfile` or `--jsonfile-pretty`)
+// or sslyze (`--json_out`) into a `tls` section with one table of the
+// protocol, cipher and certificate issues of every host. With `--findings`
+// the serious issues become findings of their own, each listing its hosts.
+
+const SECTION_NAME: &str = "tls";
+// Issues at these levels become findings with `--findings`
+const FINDING_SEVERITIES: &[&str] = &["critical", "high"];
+const SEVERITY_ORDER: &[&str] = &["critical", "high", "medium", "low"];
+
+struct Issue {
+    // host:port
+    host: String,
+    id: String,
+    finding: String,
+    severity: String,
+    cves: Vec<String>,
+}
+
+/// Issues of testssl.sh output, its OK/INFO results are left out
+fn parse_testssl(json: &Value) -> Vec<Issue> {
+    // The pretty format groups results per scanned host and section
+    let results: Vec<(Option<String>, &Value)> = match json.get("scanResult") {
+        Some(scans) => items(Some(scans))
+            .iter()
+            .flat_map(|scan| {
+                let host = format!(
+                    "{}:{}",
+                    scan.get("targetHost")
+                        .and_then(Value::as_str)
+                        .unwrap_or("-"),
+                    scan.get("port").and_then(Value::as_str).unwrap_or("-")
+                );
+                let Value::Object(sections) = scan else {
+                    return Vec::new();
+                };
+                sections
+                    .iter()
+                    .flat_map(|(_, section)| items(Some(section)))
+                    .map(|result| (Some(host.clone()), result))
+                    .collect()
+            })
+            .collect(),
+        None => items(Some(json))
+            .iter()
+            .map(|result| (None, result))
+            .collect(),
+    };
+
+    let mut issues = Vec::new();
+    for (host, result) in results {
+        let field = |key: &str| result.get(key).and_then(Value::as_str).unwrap_or_default();
+        let severity = field("severity").to_lowercase();
+        if !SEVERITY_ORDER.contains(&severity.as_str()) {
+            continue;
+        }
+        // The flat format names the host as `name/ip`
+        let host = host.unwrap_or_else(|| {
+            let name = field("ip").split('/').next().unwrap_or_default();
+            format!("{name}:{}", field("port"))
+        });
+        issues.push(Issue {
+            host,
+            id: field("id").to_string(),
+            finding: field("finding").to_string(),
+            severity,
+            cves: field("cve")
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+    issues
+}
+
+/// Items of an array, none for any other value
+fn items(json: Option<&Value>) -> &[Value] {
+    json.and_then(Value::as_array).map_or(&[], Vec::as_slice)
+}
+
+fn lookup<'a>(json: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(json, |json, key| json.get(key))
+}
+
+fn is_true(json: &Value, path: &[&str]) -> bool {
+    lookup(json, path) == Some(&Value::Bool(true))
+}
+
+/// Issues of sslyze output, rated like testssl.sh rates them
+fn parse_sslyze(json: &Value) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for server in items(lookup(json, &["server_scan_results"])) {
+        let location = |key: &str| {
+            lookup(server, &["server_location", key]).map_or("-".to_string(), |v| match v {
+                Value::Number(n) => n.to_string(),
+                v => v.as_str().unwrap_or("-").to_string(),
+            })
+        };
+        let host = format!("{}:{}", location("hostname"), location("port"));
+        let Some(result) = server.get("scan_result") else {
+            continue;
+        };
+        let mut push = |id: &str, finding: &str, severity: &str| {
+            issues.push(Issue {
+                host: host.clone(),
+                id: id.to_string(),
+                finding: finding.to_string(),
+                severity: severity.to_string(),
+                cves: Vec::new(),
+            })
+        };
+
+        for (command, protocol, severity) in [
+            ("ssl_2_0_cipher_suites", "SSLv2", "critical"),
+            ("ssl_3_0_cipher_suites", "SSLv3", "high"),
+            ("tls_1_0_cipher_suites", "TLS1", "low"),
+            ("tls_1_1_cipher_suites", "TLS1_1", "low"),
+        ] {
+            let accepted = items(lookup(
+                result,
+                &[command, "result", "accepted_cipher_suites"],
+            ))
+            .len();
+            if accepted > 0 {
+                push(protocol, &format!("{protocol} offered"), severity);
+            }
+        }
+
+        // Broken ciphers accepted by any protocol
+        let mut weak: Vec<String> = Vec::new();
+        if let Value::Object(commands) = result {
+            for (command, _) in commands
+                .iter()
+                .filter(|(c, _)| c.ends_with("_cipher_suites"))
+            {
+                let suites = lookup(result, &[command, "result", "accepted_cipher_suites"]);
+                for suite in items(suites) {
+                    let name = lookup(suite, &["cipher_suite", "name"])
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    let broken = ["NULL", "EXPORT", "anon", "RC4", "_DES_", "3DES"]
+                        .iter()
+                        .any(|weak| name.contains(weak));
+                    if broken && !weak.iter().any(|w| w == name) {
+                        weak.push(name.to_string());
+                    }
+                }
+            }
+        }
+        if !weak.is_empty() {
+            push(
+                "cipherlist_weak",
+                &format!("Weak cipher suites offered: {}", weak.join(", ")),
+                "medium",
+            );
+        }
+
+        for (path, id, finding, severity) in [
+            (
+                &["heartbleed", "result", "is_vulnerable_to_heartbleed"][..],
+                "heartbleed",
+                "Vulnerable to Heartbleed (CVE-2014-0160)",
+                "critical",
+            ),
+            (
+                &[
+                    "openssl_ccs_injection",
+                    "result",
+                    "is_vulnerable_to_ccs_injection",
+                ][..],
+                "CCS",
+                "Vulnerable to OpenSSL CCS injection (CVE-2014-0224)",
+                "high",
+            ),
+            (
+                &["tls_compression", "result", "supports_compression"][..],
+                "CRIME_TLS",
+                "TLS compression enabled (CRIME)",
+                "medium",
+            ),
+            (
+                &[
+                    "session_renegotiation",
+                    "result",
+                    "is_vulnerable_to_client_renegotiation_dos",
+                ][..],
+                "secure_client_renego",
+                "Client-initiated renegotiation enabled",
+                "medium",
+            ),
+        ] {
+            if is_true(result, path) {
+                push(id, finding, severity);
+            }
+        }
+
+        let robot = lookup(result, &["robot", "result", "robot_result"])
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if robot.starts_with("VULNERABLE") {
+            push("ROBOT", "Vulnerable to ROBOT", "high");
+        }
+
+        let deployments = lookup(
+            result,
+            &["certificate_info", "result", "certificate_deployments"],
+        );
+        for deployment in items(deployments) {
+            let trusted = items(deployment.get("path_validation_results"))
+                .iter()
+                .any(|v| is_true(v, &["was_validation_successful"]));
+            if !trusted {
+                push("cert_chain_of_trust", "Certificate not trusted", "high");
+            }
+            if lookup(deployment, &["leaf_certificate_subject_matches_hostname"])
+                == Some(&Value::Bool(false))
+            {
+                push(
+                    "cert_commonName",
+                    "Certificate does not match the hostname",
+                    "medium",
+                );
+            }
+            if is_true(deployment, &["verified_chain_has_sha1_signature"]) {
+                push(
+                    "cert_signatureAlgorithm",
+                    "SHA1 signed certificate",
+                    "medium",
+                );
+            }
+        }
+    }
+    issues
+}
+
+/// `tls` section with a table of the issues of every host
+fn render_section(issues: &[Issue]) -> String {
+    let mut section = String::from("= TLS Configuration\n");
+    if issues.is_empty() {
+        section.push_str("The TLS scan did not identify any issues.\n");
+        return section;
+    }
+    section.push_str(
+        "#table(\n  columns: (auto, auto, 1fr, auto),\n  table.header([*Host*], [*Check*], [*Issue*], [*Severity*]),\n",
+    );
+    for issue in issues {
+        section.push_str(&format!(
+            "  {}, {}, {}, {},\n",
+            typst_str(&issue.host),
+            typst_str(&issue.id),
+            typst_str(&issue.finding),
+            typst_str(&issue.severity)
+        ));
+    }
+    section.push_str(")\n");
+    section
+}
+
+/// Finding of a serious issue with all hosts it affects
+fn render_finding(issues: &[&Issue]) -> String {
+    let issue = issues[0];
+    let mut hosts: Vec<String> = issues.iter().map(|i| i.host.clone()).collect();
+    hosts.dedup();
+    let mut cves: Vec<String> = issues.iter().flat_map(|i| i.cves.clone()).collect();
+    cves.dedup();
+    let list = |items: &[String]| {
+        items
+            .iter()
+            .map(|i| toml::quote(i))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut front_matter = format!(
+        "+++\nseverity = {}\nhosts = [{}]\n",
+        toml::quote(&issue.severity),
+        list(&hosts)
+    );
+    if !cves.is_empty() {
+        front_matter.push_str(&format!("cve = [{}]\n", list(&cves)));
+    }
+    // testssl.sh findings like "offered" need the check to make sense
+    let title = match issue
+        .finding
+        .to_lowercase()
+        .contains(&issue.id.to_lowercase())
+    {
+        true => issue.finding.clone(),
+        false => format!("{}: {}", issue.id, issue.finding),
+    };
+    format!(
+        "{front_matter}+++\n= Finding: {}\n{}\n\n{{{{ affected_hosts }}}}\n",
+        typst_escape(&title),
+        typst_escape(&format!("Reported by the TLS scan as {}.", issue.id))
+    )
+}
+
+/// File name part of an issue ID, e.g. `tls-heartbleed`
+fn finding_name(id: &str) -> String {
+    let slug: String = id
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '-',
+        })
+        .collect();
+    format!("tls-{slug}")
+}
+
+pub fn import_tls(
+    file: String,
+    report_dir: Option<PathBuf>,
+    findings: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    let json = read_to_string(&file)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<Value>(&content).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("ERROR: Failed to read {file}: {e}");
+            exit(1);
+        });
+    let mut issues = match json.get("server_scan_results") {
+        Some(_) => parse_sslyze(&json),
+        None => parse_testssl(&json),
+    };
+    let rank = |severity: &str| SEVERITY_ORDER.iter().position(|s| *s == severity);
+    issues.sort_by(|a, b| {
+        (a.host.as_str(), rank(&a.severity)).cmp(&(b.host.as_str(), rank(&b.severity)))
+    });
+
+    // Imports replace the section of earlier ones
+    let mut changes = Changes::for_report(&report_path);
+    let section = match report.sections.iter().find(|s| s.name == SECTION_NAME) {
+        Some(section) => section.path.clone(),
+        None => {
+            let id = report.sections.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+            report_path
+                .join("sections")
+                .join(format!("{id}.{SECTION_NAME}.typ"))
+        }
+    };
+    changes.write(section, &render_section(&issues));
+
+    // One finding per serious issue, findings of earlier imports are kept
+    let mut created = 0;
+    if findings {
+        let mut id = report.findings.iter().map(|f| f.id).max().unwrap_or(0);
+        let mut serious: Vec<&str> = issues
+            .iter()
+            .filter(|i| FINDING_SEVERITIES.contains(&i.severity.as_str()))
+            .map(|i| i.id.as_str())
+            .collect();
+        serious.sort();
+        serious.dedup();
+        for issue_id in serious {
+            let name = finding_name(issue_id);
+            if report.findings.iter().any(|f| f.name == name) {
+                continue;
+            }
+            let affected: Vec<&Issue> = issues.iter().filter(|i| i.id == issue_id).collect();
+            id += 1;
+            created += 1;
+            changes.create(
+                report_path
+                    .join("findings")
+                    .join(format!("{id}.{name}.typ")),
+                &render_finding(&affected),
+            );
+        }
+    }
+    changes.apply(dry_run)?;
+
+    if !dry_run {
+        let hosts = {
+            let mut hosts: Vec<&str> = issues.iter().map(|i| i.host.as_str()).collect();
+            hosts.dedup();
+            hosts.len()
+        };
+        println!(
+            "Imported {} TLS issue(s) of {hosts} host(s), created {created} finding(s)",
+            issues.len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+// TOML of front-matter and configuration files, parsed by the toml crate into
+// tables that keep the order of their keys. Dates are read as plain strings.
+
+use ::toml::{Table, Value};
+
+/// Lookups of string and integer values in tables of the toml crate
+pub trait TableExt {
+    fn get_str(&self, key: &str) -> Option<&str>;
+
+    /// Returns a list of strings, accepting both `key = "a"` and `key = ["a", "b"]`
+    fn get_str_list(&self, key: &str) -> Vec<String>;
+
+    /// Returns a list of integers, accepting both `key = 1` and `key = [1, 2]`
+    fn get_int_list(&self, key: &str) -> Vec<i64>;
+}
+
+impl TableExt for Table {
+    fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    fn get_str_list(&self, key: &str) -> Vec<String> {
+        match self.get(key) {
+            Some(Value::String(s)) => vec![s.clone()],
+            Some(Value::Array(a)) => a
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_int_list(&self, key: &str) -> Vec<i64> {
+        match self.get(key) {
+            Some(Value::Integer(i)) => vec![*i],
+            Some(Value::Array(a)) => a.iter().filter_map(Value::as_integer).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Basic string literal of `s`
+pub fn quote(s: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            '\r' => {}
+            // Other control characters aren't allowed in strings
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Parses a TOML document, errors read "line N: message"
+pub fn parse(input: &str) -> Result<Table, String> {
+    let mut table: Table = ::toml::from_str(input).map_err(|e| located(input, &e))?;
+    table.iter_mut().for_each(|(_, v)| dates_to_strings(v));
+    Ok(table)
+}
+
+/// Message of a parse error with the line it occurred on
+pub fn located(input: &str, error: &::toml::de::Error) -> String {
+    let message = error
+        .message()
+        .trim_end()
+        .lines()
+        .collect::<Vec<_>>()
+        .join(", ");
+    match error.span() {
+        Some(span) => {
+            let line = input[..span.start.min(input.len())].matches('\n').count() + 1;
+            format!("line {line}: {message}")
+        }
+        None => message.to_string(),
+    }
+}
+
+// Dates and times are kept as strings
+fn dates_to_strings(value: &mut Value) {
+    match value {
+        Value::Datetime(d) => *value = Value::String(d.to_string()),
+        Value::Array(a) => a.iter_mut().for_each(dates_to_strings),
+        Value::Table(t) => t.iter_mut().for_each(|(_, v)| dates_to_strings(v)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_are_read() {
+        let table = parse(r#"s = "tab\t quote\" backslash\\ \u00e9""#).unwrap();
+        assert_eq!(table.get_str("s"), Some("tab\t quote\" backslash\\ é"));
+        let table = parse(r"s = 'C:\no\escapes'").unwrap();
+        assert_eq!(table.get_str("s"), Some(r"C:\no\escapes"));
+    }
+
+    #[test]
+    fn multi_line_strings_are_read() {
+        let table =
+            parse("request = \"\"\"\nGET / HTTP/1.1\nHost: a\n\"\"\"\nraw = '''\n\\d+\n'''")
+                .unwrap();
+        assert_eq!(table.get_str("request"), Some("GET / HTTP/1.1\nHost: a\n"));
+        assert_eq!(table.get_str("raw"), Some("\\d+\n"));
+    }
+
+    #[test]
+    fn quote_round_trips() {
+        let s = "line\n\"quoted\"\\ \u{7}";
+        assert_eq!(
+            parse(&format!("s = {}", quote(s))).unwrap().get_str("s"),
+            Some(s)
+        );
+    }
+
+    #[test]
+    fn inline_and_dotted_tables_are_read() {
+        let table = parse("a = { b = 1, c = [2, 3] }\nx.y = \"z\"").unwrap();
+        let Some(Value::Table(a)) = table.get("a") else {
+            panic!("a is not a table");
+        };
+        assert_eq!(a.get("b"), Some(&Value::Integer(1)));
+        assert_eq!(a.get_int_list("c"), vec![2, 3]);
+        let Some(Value::Table(x)) = table.get("x") else {
+            panic!("x is not a table");
+        };
+        assert_eq!(x.get_str("y"), Some("z"));
+    }
+
+    #[test]
+    fn arrays_of_tables_are_read() {
+        let table =
+            parse("[[http]]\nlabel = \"one\"\n[[http]]\nlabel = \"two\"\nredact = false").unwrap();
+        let Some(Value::Array(pairs)) = table.get("http") else {
+            panic!("http is not an array");
+        };
+        let labels: Vec<_> = pairs
+            .iter()
+            .map(|pair| match pair {
+                Value::Table(pair) => pair.get_str("label"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(labels, vec![Some("one"), Some("two")]);
+    }
+
+    #[test]
+    fn keys_keep_their_order_and_dates_are_strings() {
+        let table = parse("zeta = 1\nalpha = 2.5\ndue = 2024-05-01").unwrap();
+        assert_eq!(
+            table.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["zeta", "alpha", "due"]
+        );
+        assert_eq!(table.get("alpha"), Some(&Value::Float(2.5)));
+        assert_eq!(table.get_str("due"), Some("2024-05-01"));
+    }
+
+    #[test]
+    fn invalid_input_reports_its_line() {
+        for input in [
+            "a = 1\nb = \"unclosed",
+            "a = 1\na = 2",
+            "a = 1\n= 2",
+            "a = [1,",
+        ] {
+            let error = parse(input).unwrap_err();
+            assert!(error.starts_with("line "), "{error}");
+            assert!(!error.contains('\n'), "{error}");
+        }
+        assert!(parse("a = 1\nb = \"unclosed")
+            .unwrap_err()
+            .starts_with("line 2: "));
+    }
+}
@@ -0,0 +1,114 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::report::Report;
+use crate::severity::Presentation;
+use crate::summary;
+use crate::utils::{typst_escape, typst_str};
+
+// Retests link the report of the previous engagement with the client by
+// `previous_report` in their metadata, a path relative to the report. The
+// `{{ severity_trend }}` chart shows the findings by severity of every
+// engagement along the chain, oldest first.
+
+pub const PREVIOUS_REPORT_KEY: &str = "previous_report";
+
+/// The report and its predecessors, oldest first
+fn engagements(report: &Report) -> Result<Vec<Report>, String> {
+    let canonical = |path: &PathBuf| path.canonicalize().unwrap_or_else(|_| path.clone());
+    let mut seen = HashSet::from([canonical(&report.path)]);
+    let mut chain = Vec::new();
+    let mut current = report
+        .metadata_value(PREVIOUS_REPORT_KEY)
+        .map(|previous| report.path.join(previous));
+    while let Some(path) = current {
+        // Reports linking each other would loop forever
+        if !seen.insert(canonical(&path)) {
+            break;
+        }
+        let previous = Report::load(&path)
+            .map_err(|e| format!("Failed to load previous report {}: {e}", path.display()))?;
+        current = previous
+            .metadata_value(PREVIOUS_REPORT_KEY)
+            .map(|p| previous.path.join(p));
+        chain.push(previous);
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Name of an engagement in the chart, its report date or directory
+fn label(report: &Report) -> String {
+    match report.metadata_value("report_date") {
+        Some(date) if !date.is_empty() => date.to_string(),
+        _ => report
+            .path
+            .file_name()
+            .map_or(PathBuf::from("."), PathBuf::from)
+            .display()
+            .to_string(),
+    }
+}
+
+/// Stacked bar per engagement scaled to the one with the most findings,
+/// empty without a previous report
+pub fn render_chart(
+    report: &Report,
+    severities: &[String],
+    presentation: &Presentation,
+) -> Result<String, String> {
+    let previous = engagements(report)?;
+    if previous.is_empty() {
+        return Ok(String::new());
+    }
+
+    let rows: Vec<(String, Vec<(String, usize)>)> = previous
+        .iter()
+        .chain([report])
+        .map(|engagement| {
+            let mut counts = summary::severity_counts(&engagement.findings, severities);
+            counts.retain(|(severity, count)| *count > 0 && severity != "unrated");
+            (label(engagement), counts)
+        })
+        .collect();
+    let max = rows
+        .iter()
+        .map(|(_, counts)| counts.iter().map(|(_, count)| count).sum::<usize>())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut chart = String::from(
+        "#table(\n  columns: (auto, 1fr, auto),\n  stroke: none,\n  align: horizon,\n",
+    );
+    for (label, counts) in &rows {
+        let total: usize = counts.iter().map(|(_, count)| count).sum();
+        let bars: Vec<String> = counts
+            .iter()
+            .map(|(severity, count)| {
+                format!(
+                    "rect(width: {:.1}%, height: 12pt, fill: rgb({}))",
+                    *count as f64 * 100.0 / max as f64,
+                    typst_str(presentation.color(severity))
+                )
+            })
+            .collect();
+        chart.push_str(&format!(
+            "  [{}], stack(dir: ltr, {}), [{total}],\n",
+            typst_escape(label),
+            bars.join(", ")
+        ));
+    }
+    let legend: Vec<String> = severities
+        .iter()
+        .map(|severity| {
+            format!(
+                "#box(rect(width: 8pt, height: 8pt, fill: rgb({}))) {}",
+                typst_str(presentation.color(severity)),
+                typst_escape(&presentation.label(severity))
+            )
+        })
+        .collect();
+    chart.push_str(&format!("  [], [{}], [],\n)\n", legend.join(" #h(8pt) ")));
+
+    Ok(chart)
+}
@@ -0,0 +1,308 @@
+use std::{
+    env,
+    error::Error,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    DefaultTerminal, Frame,
+};
+
+use crate::desktop;
+use crate::health;
+use crate::report::{self, Entry, Kind, Report};
+use crate::review;
+use crate::toml::TableExt;
+
+// Commands run the binary itself, like serve and watch compile, so their
+// output lands in the message pane and a failing command never ends the
+// session. Deleting goes through `rm`, which `u` undoes.
+
+const HELP: &str = "Commands:
+  n <s|f|a> <name> [template] create a section/finding/appendix (optionally from a template)
+  r <s|f|a><id> <title>       retitle a section/finding/appendix
+  m <s|f|a><id> <position>    move a section/finding/appendix to a new position
+  x <s|f|a><id>               delete a section/finding/appendix
+  u                           undo the last operation
+  c                           compile the report
+  d                           toggle the health dashboard
+  q                           quit";
+
+// The report is reloaded this often so external edits show up as well
+const RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+const BOLD: Style = Style::new().add_modifier(Modifier::BOLD);
+
+struct App {
+    report_path: PathBuf,
+    // The last report that loaded, kept while the files don't parse
+    report: Option<Report>,
+    input: String,
+    message: String,
+    dashboard: bool,
+}
+
+impl App {
+    fn reload(&mut self) {
+        match Report::load(&self.report_path) {
+            Ok(report) => self.report = Some(report),
+            Err(e) => self.message = format!("ERROR: Failed to load the report: {e}"),
+        }
+    }
+}
+
+fn truncate(s: &str, width: usize) -> String {
+    if s.chars().count() > width {
+        let mut truncated: String = s.chars().take(width - 1).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn entries_table<'a>(title: &'a str, prefix: char, entries: &[Entry]) -> Table<'a> {
+    let rows = entries.iter().map(|entry| {
+        Row::new([
+            format!("{prefix}{}", entry.id),
+            truncate(&entry.title(), 45),
+            entry.meta.get_str("severity").unwrap_or("-").to_string(),
+            entry.meta.get_str("status").unwrap_or("-").to_string(),
+            review::state(entry).to_string(),
+        ])
+    });
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(11),
+    ];
+    Table::new(rows, widths)
+        .header(Row::new(["#", "Title", "Severity", "Status", "Review"]).style(BOLD))
+        .block(Block::new().title(Line::styled(title, BOLD)))
+}
+
+fn draw_dashboard(frame: &mut Frame, app: &App, report: &Report, area: Rect) {
+    let text = match health::health(&app.report_path, report) {
+        Ok(health) => health
+            .lines()
+            .into_iter()
+            .map(|(label, value)| Line::raw(format!("{label:<14} {value}")))
+            .collect(),
+        Err(e) => vec![Line::raw(format!("ERROR: {e}"))],
+    };
+    frame.render_widget(
+        Paragraph::new(text).block(Block::new().title(Line::styled("Health", BOLD))),
+        area,
+    );
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let help_height = HELP.lines().count() as u16;
+    let [header, body, help, message, input] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(5),
+        Constraint::Length(help_height),
+        Constraint::Length(6),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+
+    let title = app
+        .report
+        .as_ref()
+        .and_then(|report| report.metadata_value("report_title"))
+        .unwrap_or("Report");
+    frame.render_widget(
+        Line::from(vec![
+            Span::styled(title, BOLD),
+            Span::raw(format!(" ({})", app.report_path.display())),
+        ]),
+        header,
+    );
+
+    if let Some(report) = &app.report {
+        if app.dashboard {
+            draw_dashboard(frame, app, report, body);
+        } else {
+            let mut tables = vec![
+                ("Sections", Kind::Section, &report.sections),
+                ("Findings", Kind::Finding, &report.findings),
+            ];
+            if !report.appendices.is_empty() {
+                tables.push(("Appendices", Kind::Appendix, &report.appendices));
+            }
+            let areas = Layout::vertical(
+                tables
+                    .iter()
+                    .map(|(_, _, entries)| Constraint::Min(entries.len() as u16 + 2)),
+            )
+            .split(body);
+            for ((title, kind, entries), area) in tables.into_iter().zip(areas.iter()) {
+                frame.render_widget(entries_table(title, kind.prefix(), entries), *area);
+            }
+        }
+    }
+
+    frame.render_widget(Paragraph::new(HELP), help);
+    // The end of long output like compile errors
+    let lines: Vec<&str> = app.message.lines().collect();
+    let shown = &lines[lines.len().saturating_sub(message.height as usize)..];
+    frame.render_widget(Paragraph::new(shown.join("\n")), message);
+    frame.render_widget(
+        Paragraph::new(format!("> {}", app.input)).block(Block::new().borders(Borders::TOP)),
+        input,
+    );
+}
+
+/// Runs a subcommand on the report, its output or the reason it failed
+fn run(report_path: &Path, args: &[String]) -> String {
+    let output = env::current_exe().and_then(|exe| {
+        Command::new(exe)
+            .args(args)
+            .arg(report_path)
+            .stdin(Stdio::null())
+            .output()
+    });
+    match output {
+        Ok(output) => {
+            let text = String::from_utf8_lossy(&output.stdout).to_string()
+                + &String::from_utf8_lossy(&output.stderr);
+            match (output.status.success(), text.trim().is_empty()) {
+                (true, true) => "Done".to_string(),
+                (false, true) => format!("ERROR: {} failed", args[0]),
+                _ => text.trim().to_string(),
+            }
+        }
+        Err(e) => format!("ERROR: Failed to run {}: {e}", args[0]),
+    }
+}
+
+/// Arguments of the subcommand of a command line
+fn parse_command(report: Option<&Report>, line: &str) -> Result<Vec<String>, String> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    // Section, finding or appendix of a target like f3
+    let target = |target: &str| {
+        report
+            .and_then(|report| {
+                let (kind, index) = report.find(target)?;
+                Some((kind, report.entries_of(kind)[index].id))
+            })
+            .ok_or(format!("No such section/finding \"{target}\""))
+    };
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+
+    match parts.as_slice() {
+        ["c"] => Ok(args(&["compile", "--no-notify"])),
+        ["u"] => Ok(args(&["undo"])),
+        ["n", kind, name, template @ ..] => {
+            let mut command = match Kind::parse(kind) {
+                Some(Kind::Section) => args(&["new-section", "--name", name]),
+                Some(Kind::Finding) => args(&["new-finding", "--name", name]),
+                Some(Kind::Appendix) => args(&["new-appendix", "--name", name]),
+                None => return Err(format!("Unknown kind \"{kind}\", use s, f or a")),
+            };
+            if let (Some(template), false) =
+                (template.first(), Kind::parse(kind) == Some(Kind::Appendix))
+            {
+                command.extend(args(&["--template", template]));
+            }
+            Ok(command)
+        }
+        ["r", entry, title @ ..] if !title.is_empty() => {
+            let (kind, id) = target(entry)?;
+            let mut command = args(&["rename", &kind.prefix().to_string(), &id.to_string()]);
+            command.push(title.join(" "));
+            Ok(command)
+        }
+        ["m", entry, position] => {
+            target(entry)?;
+            if position.parse::<usize>().is_err() {
+                return Err(format!("Invalid position \"{position}\""));
+            }
+            Ok(args(&["move", entry, position]))
+        }
+        ["x", entry] => {
+            let (kind, id) = target(entry)?;
+            Ok(args(&["rm", &kind.prefix().to_string(), &id.to_string()]))
+        }
+        _ => Err(format!("Unknown command \"{line}\"")),
+    }
+}
+
+fn run_app(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), Box<dyn Error>> {
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if !event::poll(RELOAD_INTERVAL)? {
+            app.reload();
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char(c) => app.input.push(c),
+            KeyCode::Backspace => {
+                app.input.pop();
+            }
+            KeyCode::Esc => app.input.clear(),
+            KeyCode::Enter => {
+                let line = std::mem::take(&mut app.input);
+                match line.trim() {
+                    "" => {}
+                    "q" => break,
+                    "d" => {
+                        app.dashboard = !app.dashboard;
+                        app.message.clear();
+                    }
+                    line => {
+                        app.message = match parse_command(app.report.as_ref(), line) {
+                            Ok(args) => {
+                                // Compiling takes a while, say so meanwhile
+                                app.message = format!("Running {}…", args[0]);
+                                terminal.draw(|frame| draw(frame, app))?;
+                                run(&app.report_path, &args)
+                            }
+                            Err(e) => e,
+                        };
+                        app.reload();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+pub fn tui(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    desktop::remember(&report_path);
+
+    let mut app = App {
+        report_path,
+        report: None,
+        input: String::new(),
+        message: String::new(),
+        dashboard: false,
+    };
+    app.reload();
+
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,125 @@
+use std::{error::Error, path::PathBuf, process::exit};
+
+use crate::changes::Changes;
+use crate::config::Config;
+use crate::consts::*;
+use crate::new_report;
+use crate::nvd;
+use crate::report::{self, Report};
+use crate::severity;
+use crate::template::Template;
+use crate::toml::TableExt;
+
+// Guided sample engagement for new joiners. The first run scaffolds a report
+// with instructions, every following run checks which tasks are done.
+
+struct Task {
+    description: &'static str,
+    done: fn(&Report) -> bool,
+}
+
+const TASKS: &[Task] = &[
+    Task {
+        description: "Fill in the metadata",
+        done: |report| {
+            let example = report::parse_metadata(T_METADATA);
+            let example_title = example.iter().find(|(k, _)| k == "report_title");
+            report.metadata_value("report_title").is_some_and(|title| {
+                !title.is_empty() && Some(title) != example_title.map(|(_, v)| v.trim())
+            })
+        },
+    },
+    Task {
+        description: "Add a finding",
+        done: |report| report.findings.iter().any(|f| f.name != "example_finding"),
+    },
+    Task {
+        description: "Rate the finding",
+        done: |report| {
+            let severities = Config::load_or_exit(&report.path).severities();
+            report.findings.iter().any(|f| {
+                f.meta
+                    .get_str("severity")
+                    .is_some_and(|s| severity::is_valid(s, &severities))
+            })
+        },
+    },
+    Task {
+        description: "Import the scan results",
+        done: |report| {
+            report.findings.iter().any(|f| {
+                nvd::finding_cve_ids(f)
+                    .iter()
+                    .any(|id| nvd::load_cached(&report.path, id).is_some())
+            })
+        },
+    },
+    Task {
+        description: "Compile the report",
+        done: |report| report.path.join(DEFAULT_REPORT_FILE).exists(),
+    },
+];
+
+fn scaffold(report_path: PathBuf) -> Result<(), Box<dyn Error>> {
+    new_report::new_report(Some(report_path.clone()), None, false, None, false, false)?;
+
+    let dir = report_path.display().to_string();
+    let instructions = Template::from_str(T_TUTORIAL).render(&vec![("dir", &dir)]);
+
+    let mut changes = Changes::default();
+    changes.create(report_path.join("TUTORIAL.md"), &instructions);
+    changes.create_dir(report_path.join("scans"));
+    changes.create(
+        report_path.join("scans").join("nvd.json"),
+        TUTORIAL_NVD_DUMP,
+    );
+    changes.apply(false)?;
+
+    println!("{instructions}");
+
+    Ok(())
+}
+
+pub fn tutorial(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    // Ensure user provided the report path
+    let report_path = report_dir.unwrap_or_else(|| {
+        eprintln!("ERROR: Report path not provided");
+        exit(1);
+    });
+
+    if !report_path.exists() {
+        return scaffold(report_path);
+    }
+
+    if !report_path.join("TUTORIAL.md").exists() {
+        eprintln!("ERROR: Directory is not a tutorial report");
+        exit(1);
+    }
+
+    let report = Report::load_or_exit(&report_path);
+    let mut completed = 0;
+    for (i, task) in TASKS.iter().enumerate() {
+        let done = (task.done)(&report);
+        if done {
+            completed += 1;
+        }
+        println!(
+            "[{}] {}. {}",
+            if done { "x" } else { " " },
+            i + 1,
+            task.description
+        );
+    }
+
+    if completed == TASKS.len() {
+        println!("\nAll tasks completed, welcome aboard!");
+    } else {
+        println!(
+            "\n{completed} of {} tasks completed, see {} for instructions",
+            TASKS.len(),
+            report_path.join("TUTORIAL.md").display()
+        );
+    }
+
+    Ok(())
+}
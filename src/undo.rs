@@ -0,0 +1,137 @@
+use std::{
+    error::Error,
+    fs::{
+        copy, create_dir_all, read_dir, read_to_string, remove_dir, remove_dir_all, remove_file,
+        rename, write,
+    },
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use chrono::Local;
+
+use crate::changes::Change;
+use crate::consts::*;
+use crate::report;
+
+// Every applied operation gets a numbered directory on the undo stack
+// (`.reportgenerator/undo/<n>/`) holding a `manifest` with one tab separated
+// line per change and backups of the files it overwrote.
+
+fn stack_path(report_path: &Path) -> PathBuf {
+    report_path.join(STATE_DIR).join(UNDO_DIR)
+}
+
+/// Numbers of the operations on the stack, oldest first
+fn operations(stack: &Path) -> Result<Vec<usize>, Box<dyn Error>> {
+    let mut operations = Vec::new();
+    if !stack.exists() {
+        return Ok(operations);
+    }
+    for entry in read_dir(stack)? {
+        if let Ok(n) = entry?.file_name().to_string_lossy().parse::<usize>() {
+            operations.push(n);
+        }
+    }
+    operations.sort();
+    Ok(operations)
+}
+
+fn relative(report_path: &Path, path: &Path) -> String {
+    path.strip_prefix(report_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Records the information needed to revert `changes` before they are applied
+pub fn record(report_path: &Path, changes: &[Change]) -> Result<(), Box<dyn Error>> {
+    let stack = stack_path(report_path);
+    let existing = operations(&stack)?;
+    let dir = stack.join((existing.last().unwrap_or(&0) + 1).to_string());
+    create_dir_all(&dir)?;
+
+    let mut manifest = format!("# {}\n", Local::now().format("%Y-%m-%d %H:%M:%S"));
+    for (i, change) in changes.iter().enumerate() {
+        let line = match change {
+            Change::CreateDir(path) if path.exists() => continue,
+            Change::CreateDir(path) => format!("mkdir\t{}", relative(report_path, path)),
+            Change::Create(path, _) => format!("create\t{}", relative(report_path, path)),
+            Change::Write(path, _) if path.exists() => {
+                copy(path, dir.join(i.to_string()))?;
+                format!("write\t{}\t{i}", relative(report_path, path))
+            }
+            Change::Write(path, _) => format!("create\t{}", relative(report_path, path)),
+            Change::Rename(from, to) => format!(
+                "rename\t{}\t{}",
+                relative(report_path, from),
+                relative(report_path, to)
+            ),
+            Change::Copy(_, to) if to.exists() => {
+                copy(to, dir.join(i.to_string()))?;
+                format!("write\t{}\t{i}", relative(report_path, to))
+            }
+            Change::Copy(_, to) => format!("create\t{}", relative(report_path, to)),
+            Change::Remove(path) => {
+                copy(path, dir.join(i.to_string()))?;
+                format!("remove\t{}\t{i}", relative(report_path, path))
+            }
+        };
+        manifest.push_str(&line);
+        manifest.push('\n');
+    }
+    write(dir.join("manifest"), manifest)?;
+
+    // Drop the oldest operations over the limit
+    let count = existing.len() + 1;
+    for old in existing.iter().take(count.saturating_sub(UNDO_LIMIT)) {
+        remove_dir_all(stack.join(old.to_string()))?;
+    }
+
+    Ok(())
+}
+
+pub fn undo(report_dir: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    let stack = stack_path(&report_path);
+
+    let Some(last) = operations(&stack)?.pop() else {
+        eprintln!("ERROR: Nothing to undo");
+        exit(1);
+    };
+    let dir = stack.join(last.to_string());
+    let manifest = read_to_string(dir.join("manifest"))?;
+
+    // Revert the changes in reverse order
+    for line in manifest.lines().rev() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            // Directories are only removed when empty
+            ["mkdir", path] => {
+                let _ = remove_dir(report_path.join(path));
+            }
+            ["create", path] => remove_file(report_path.join(path))?,
+            ["write", path, backup] | ["remove", path, backup] => {
+                copy(dir.join(backup), report_path.join(path))?;
+            }
+            ["rename", from, to] => rename(report_path.join(to), report_path.join(from))?,
+            _ => {
+                eprintln!("ERROR: Corrupted undo entry \"{}\"", dir.display());
+                exit(1);
+            }
+        }
+    }
+
+    remove_dir_all(&dir)?;
+
+    let recorded_at = manifest.lines().next().unwrap_or_default();
+    println!(
+        "Undid last operation ({})",
+        recorded_at.trim_start_matches("# ")
+    );
+
+    Ok(())
+}
@@ -1,6 +1,124 @@
-use chrono::Local;
+use chrono::{Datelike, Local, NaiveDate};
+
+const DEFAULT_DATE_FORMAT: &str = "%B %d, %Y";
+
+// Month names per locale, English is used for unknown locales
+const MONTH_NAMES: &[(&str, &str)] = &[
+    ("en", "January February March April May June July August September October November December"),
+    ("de", "Januar Februar März April Mai Juni Juli August September Oktober November Dezember"),
+    ("fr", "janvier février mars avril mai juin juillet août septembre octobre novembre décembre"),
+    ("es", "enero febrero marzo abril mayo junio julio agosto septiembre octubre noviembre diciembre"),
+    ("it", "gennaio febbraio marzo aprile maggio giugno luglio agosto settembre ottobre novembre dicembre"),
+    ("nl", "januari februari maart april mei juni juli augustus september oktober november december"),
+    ("pl", "stycznia lutego marca kwietnia maja czerwca lipca sierpnia września października listopada grudnia"),
+];
+
+/// Converts a pattern like `dd.MM.yyyy` or `MMMM d, yyyy` to a strftime format
+fn pattern_to_strftime(pattern: &str) -> String {
+    const TOKENS: &[(&str, &str)] = &[
+        ("yyyy", "%Y"),
+        ("yy", "%y"),
+        ("MMMM", "%B"),
+        ("MMM", "%b"),
+        ("MM", "%m"),
+        ("M", "%-m"),
+        ("dd", "%d"),
+        ("d", "%-d"),
+    ];
+
+    let mut format = String::new();
+    let mut rest = pattern;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (token, replacement) in TOKENS {
+            if let Some(after) = rest.strip_prefix(token) {
+                format.push_str(replacement);
+                rest = after;
+                continue 'outer;
+            }
+        }
+        format.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    format
+}
+
+/// Formats a date using a strftime format or a `dd.MM.yyyy` style pattern,
+/// with month names in the given locale (e.g. `de` or `de_DE`)
+pub fn format_date(date: NaiveDate, format: &str, locale: &str) -> String {
+    let format = if format.contains('%') {
+        format.to_string()
+    } else {
+        pattern_to_strftime(format)
+    };
+
+    let language = locale.split(['_', '-']).next().unwrap_or_default();
+    let months = MONTH_NAMES
+        .iter()
+        .find(|(l, _)| l.eq_ignore_ascii_case(language))
+        .unwrap_or(&MONTH_NAMES[0])
+        .1;
+    let month = months
+        .split(' ')
+        .nth(date.month0() as usize)
+        .unwrap_or_default();
+    let abbreviation: String = month.chars().take(3).collect();
+
+    // Month names are substituted before chrono formats the rest
+    let format = format
+        .replace("%B", &month.replace('%', "%%"))
+        .replace("%b", &abbreviation.replace('%', "%%"));
+    date.format(&format).to_string()
+}
 
 pub fn get_current_date() -> String {
-    let date = Local::now();
-    date.format("%B %d, %Y").to_string()
+    format_date(Local::now().date_naive(), DEFAULT_DATE_FORMAT, "en")
+}
+
+/// Date shown in the report. `report_date` (YYYY-MM-DD) replaces today's date for
+/// backdated deliverables, any other value is used verbatim.
+pub fn get_report_date(
+    report_date: Option<&str>,
+    format: Option<&str>,
+    locale: Option<&str>,
+) -> String {
+    let date = match report_date {
+        Some(report_date) => match NaiveDate::parse_from_str(report_date, "%Y-%m-%d") {
+            Ok(date) => date,
+            Err(_) => return report_date.to_string(),
+        },
+        None => Local::now().date_naive(),
+    };
+    format_date(
+        date,
+        format.unwrap_or(DEFAULT_DATE_FORMAT),
+        locale.unwrap_or("en"),
+    )
+}
+
+/// Formats a string as a Typst string literal
+pub fn typst_str(s: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => {}
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Escapes text so it can be inserted into Typst markup verbatim
+pub fn typst_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\[]#*_`$<>@~/=-+".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
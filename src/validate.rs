@@ -0,0 +1,192 @@
+use std::{
+    error::Error,
+    fs::{read_dir, read_to_string},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use ::toml::Value;
+
+use crate::check;
+use crate::consts::PLACEHOLDER_TEXT;
+use crate::diagnostics::{Diagnostic, Level};
+use crate::front_matter;
+use crate::report::{self, ORDER_KEY};
+use crate::schema::METADATA_KEYS;
+
+// Pre-flight checks run before compiling, on top of everything `check` reports
+
+/// Duplicate, missing or unparseable sequence numbers of the files and chapters in `dir`
+fn check_sequence(dir: &Path, diagnostics: &mut Vec<Diagnostic>) -> Result<(), Box<dyn Error>> {
+    let mut numbered: Vec<(usize, PathBuf)> = Vec::new();
+    let mut from_file_names: Vec<usize> = Vec::new();
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            check_sequence(&path, diagnostics)?;
+        }
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let number = file_name
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .parse::<usize>()
+            .ok();
+        // Files may be numbered by the order key of their front-matter instead
+        let order = match path.is_dir() {
+            true => None,
+            false => read_to_string(&path)
+                .ok()
+                .and_then(|content| front_matter::parse(&content).ok().map(|(meta, _)| meta))
+                .and_then(|meta| meta.get(ORDER_KEY).cloned()),
+        };
+        match (number, order) {
+            (Some(id), None) => {
+                from_file_names.push(id);
+                numbered.push((id, path));
+            }
+            (None, Some(Value::Integer(order))) if order > 0 => {
+                numbered.push((order as usize, path))
+            }
+            (None, Some(_)) => diagnostics.push(Diagnostic::error(
+                path.clone(),
+                0,
+                format!("{ORDER_KEY} has to be a positive integer"),
+            )),
+            (Some(_), Some(_)) => diagnostics.push(Diagnostic::error(
+                path.clone(),
+                0,
+                format!("Sequence number in the file name and {ORDER_KEY} in the front-matter conflict, use only one of them"),
+            )),
+            (None, None) => diagnostics.push(Diagnostic::error(
+                path.clone(),
+                0,
+                format!("File name doesn't start with a sequence number and the front-matter sets no {ORDER_KEY}"),
+            )),
+        }
+    }
+    numbered.sort();
+
+    for (id, path) in &numbered {
+        if numbered.iter().filter(|(other, _)| other == id).count() > 1 {
+            diagnostics.push(Diagnostic::error(
+                path.to_path_buf(),
+                0,
+                format!("Duplicate sequence number {id}"),
+            ));
+        }
+    }
+
+    // Gaps left by removing a file only matter to numbered file names, order
+    // keys are spaced out on purpose
+    let max = from_file_names.iter().max().copied().unwrap_or(0);
+    let missing: Vec<String> = (1..=max)
+        .filter(|id| !from_file_names.contains(id))
+        .map(|id| id.to_string())
+        .collect();
+    if !missing.is_empty() {
+        diagnostics.push(Diagnostic {
+            level: Level::Warning,
+            ..Diagnostic::error(
+                dir.to_path_buf(),
+                0,
+                format!(
+                    "Missing sequence number(s) {}, `reorder` closes the gaps",
+                    missing.join(", ")
+                ),
+            )
+        });
+    }
+
+    Ok(())
+}
+
+/// Required metadata keys that are missing or empty
+fn check_metadata(report_path: &Path, diagnostics: &mut Vec<Diagnostic>) {
+    let path = report_path.join("metadata.typ");
+    let metadata = match read_to_string(&path) {
+        Ok(content) => report::parse_metadata(&content),
+        Err(e) => {
+            diagnostics.push(Diagnostic::error(
+                path,
+                0,
+                format!("Failed to read file: {e}"),
+            ));
+            return;
+        }
+    };
+
+    for key in METADATA_KEYS {
+        let message = match metadata.iter().find(|(k, _)| k == key.name) {
+            None => format!("Missing metadata key \"{}\"", key.name),
+            Some((_, value)) if value.trim().is_empty() => {
+                format!("Empty metadata key \"{}\"", key.name)
+            }
+            Some(_) => continue,
+        };
+        diagnostics.push(Diagnostic::error(path.clone(), 0, message));
+    }
+}
+
+/// Leftover template text and images that don't exist
+fn check_content(report_path: &Path, path: &Path, diagnostics: &mut Vec<Diagnostic>) {
+    // Unreadable files are already reported by check
+    let Ok(text) = read_to_string(path) else {
+        return;
+    };
+
+    for (line_number, line) in text.lines().enumerate() {
+        for placeholder in PLACEHOLDER_TEXT {
+            if line.contains(placeholder) {
+                diagnostics.push(Diagnostic::error(
+                    path.to_path_buf(),
+                    line_number,
+                    format!("Placeholder text \"{placeholder}\" still present"),
+                ));
+            }
+        }
+
+        // Typst resolves images relative to the compiled file, i.e. the report directory
+        let mut rest = line;
+        while let Some(start) = rest.find("image(\"") {
+            rest = &rest[start + "image(\"".len()..];
+            let Some(end) = rest.find('"') else {
+                break;
+            };
+            let image = &rest[..end];
+            if !report_path.join(image.trim_start_matches('/')).exists() {
+                diagnostics.push(Diagnostic::error(
+                    path.to_path_buf(),
+                    line_number,
+                    format!("Image \"{image}\" doesn't exist"),
+                ));
+            }
+            rest = &rest[end..];
+        }
+    }
+}
+
+pub fn validate(report_dir: Option<PathBuf>, format: Option<String>) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+
+    let files = check::report_files(&report_path)?;
+    let mut diagnostics = check::check_report(&report_path)?;
+    for dir in ["sections", "findings", "appendices"] {
+        let dir = report_path.join(dir);
+        if dir.is_dir() {
+            check_sequence(&dir, &mut diagnostics)?;
+        }
+    }
+    check_metadata(&report_path, &mut diagnostics);
+    for path in &files {
+        check_content(&report_path, path, &mut diagnostics);
+    }
+
+    check::print_diagnostics(&report_path, &diagnostics, format.as_deref())?;
+
+    if diagnostics.iter().any(|d| d.level == Level::Error) {
+        exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf, process::exit};
+
+use chrono::Local;
+
+use crate::changes::Changes;
+use crate::report::{self, Report};
+use crate::utils::typst_escape;
+
+// Version history kept in `metadata.typ`, one `versions` line per release
+// with version, date, author and changes separated by `|`:
+//
+//  version:1.1
+//  versions:1.0 | 2026-10-01 | Jane Doe | Initial report
+//  versions:1.1 | 2026-10-15 | Jane Doe | Added retest results
+
+const SEPARATOR: &str = " | ";
+
+pub struct Version {
+    pub version: String,
+    pub date: String,
+    pub author: String,
+    pub changes: String,
+}
+
+/// Version history in the order of the metadata file
+pub fn history(metadata: &[(String, String)]) -> Vec<Version> {
+    metadata
+        .iter()
+        .filter(|(key, _)| key == "versions")
+        .map(|(_, value)| {
+            let mut fields = value.splitn(4, '|').map(|field| field.trim().to_string());
+            Version {
+                version: fields.next().unwrap_or_default(),
+                date: fields.next().unwrap_or_default(),
+                author: fields.next().unwrap_or_default(),
+                changes: fields.next().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Document control table for `{{ document_control }}`, empty without a version history
+pub fn render_document_control(report: &Report) -> String {
+    let history = history(&report.metadata);
+    if history.is_empty() {
+        return String::new();
+    }
+
+    // Values are Typst markup like all metadata
+    let mut table = String::from(
+        "= Document Control\n#table(\n  columns: (auto, auto, auto, 1fr),\n  [*Version*], [*Date*], [*Author*], [*Changes*],\n",
+    );
+    for version in history {
+        table.push_str(&format!(
+            "  [{}], [{}], [{}], [{}],\n",
+            version.version, version.date, version.author, version.changes
+        ));
+    }
+    table.push_str(")\n");
+
+    table
+}
+
+/// Next `major.minor` version, the first bump results in 1.0
+fn next_version(current: Option<&str>, major: bool) -> Result<String, String> {
+    let Some(current) = current else {
+        return Ok("1.0".to_string());
+    };
+    let parse = |part: Option<&str>| part.unwrap_or("0").trim().parse::<u32>();
+    let mut parts = current.split('.');
+    let (Ok(major_part), Ok(minor_part)) = (parse(parts.next()), parse(parts.next())) else {
+        return Err(format!("Invalid version \"{current}\", expected e.g. 1.2"));
+    };
+    Ok(if major {
+        format!("{}.0", major_part + 1)
+    } else {
+        format!("{major_part}.{}", minor_part + 1)
+    })
+}
+
+/// Bumps `version` in the metadata and appends an entry to the version history
+pub fn bump(
+    report_dir: Option<PathBuf>,
+    major: bool,
+    note: Option<String>,
+    author: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let Some(note) = note else {
+        eprintln!("ERROR: Describe the changes of the new version with --note");
+        exit(1);
+    };
+
+    let report_path = report::resolve_path(report_dir);
+    let report = Report::load_or_exit(&report_path);
+
+    let version = next_version(report.metadata_value("version"), major).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    });
+    // The report author unless someone else made the changes
+    let author = author
+        .map(|author| typst_escape(&author))
+        .or(report.metadata_value("prepared_by").map(str::to_string))
+        .unwrap_or_default();
+    let entry = [
+        version.clone(),
+        Local::now().format("%Y-%m-%d").to_string(),
+        author,
+        typst_escape(&note),
+    ]
+    .join(SEPARATOR);
+
+    // Replace the version line, the new history entry follows the last one
+    let path = report_path.join("metadata.typ");
+    let content = report::set_metadata(&read_to_string(&path)?, "version", &version);
+    let mut lines: Vec<&str> = content.lines().collect();
+    let position = lines
+        .iter()
+        .rposition(|line| line.starts_with("versions:"))
+        .map_or(lines.len(), |i| i + 1);
+    let entry = format!("versions:{entry}");
+    lines.insert(position, &entry);
+
+    let mut changes = Changes::for_report(&report_path);
+    changes.write(path, &(lines.join("\n") + "\n"));
+    changes.apply(dry_run)?;
+
+    if !dry_run {
+        println!("Bumped version to {version}");
+    }
+
+    Ok(())
+}
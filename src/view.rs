@@ -0,0 +1,262 @@
+use std::{
+    env,
+    error::Error,
+    fs::{read_dir, remove_dir_all},
+    io::{stdin, stdout, Write},
+    path::{Path, PathBuf},
+    process::{self, exit},
+};
+
+use ::toml::Value;
+
+use crate::desktop;
+use crate::encrypt;
+use crate::evidence;
+use crate::pack;
+use crate::report::{Entry, Report};
+use crate::review;
+use crate::toml::TableExt;
+use crate::workspace;
+
+// Read-only browser over a delivered report, a packed archive or its unpacked
+// directory, to look up an old engagement without extracting and grepping it.
+// Archives are extracted to a temporary directory removed on exit, nothing is
+// ever written to the report.
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+const HELP: &str = "Commands:
+  <s|f|a><id>     show a section/finding/appendix
+  / <text>        search all sections, findings and appendices
+  o <n>           open the n-th evidence of the shown entry
+  o               open the compiled report
+  l               back to the list
+  q               quit";
+
+/// What the viewer shows
+enum Screen {
+    List,
+    Entry(String),
+    Search(String),
+}
+
+/// Report directory and compiled PDF of a report or an unpacked archive
+fn locate(dir: &Path) -> Option<(PathBuf, Option<PathBuf>)> {
+    if workspace::is_report(dir) {
+        return Some((dir.to_path_buf(), None));
+    }
+    // Archives contain the report directory next to the PDF
+    let mut report_path = None;
+    let mut pdf = None;
+    for entry in read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() && workspace::is_report(&path) {
+            report_path = Some(path);
+        } else if path.extension().is_some_and(|e| e == "pdf") {
+            pdf = Some(path);
+        }
+    }
+    Some((report_path?, pdf))
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::Array(values) => values.iter().map(value_text).collect::<Vec<_>>().join(", "),
+        Value::Datetime(d) => d.to_string(),
+        Value::Table(_) => "…".to_string(),
+    }
+}
+
+fn draw_list(report: &Report) {
+    for (title, prefix, entries) in [
+        ("Sections", 's', &report.sections),
+        ("Findings", 'f', &report.findings),
+        ("Appendices", 'a', &report.appendices),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        println!("{BOLD}{title}{RESET}");
+        for entry in entries {
+            println!(
+                "  {:<5} {:<50} {:<10} {}",
+                format!("{prefix}{}", entry.id),
+                entry.title(),
+                entry.meta.get_str("severity").unwrap_or("-"),
+                review::state(entry),
+            );
+        }
+        println!();
+    }
+}
+
+fn draw_entry(entry: &Entry) {
+    println!(
+        "{BOLD}{}{RESET} ({})\n",
+        entry.title(),
+        entry.path.display()
+    );
+    for key in entry.meta.keys() {
+        if let Some(value) = entry.meta.get(key) {
+            println!("  {key:<14} {}", value_text(value));
+        }
+    }
+    println!();
+    for (n, line) in entry.body.lines().enumerate() {
+        println!("{:>4}  {line}", entry.line + n);
+    }
+    let screenshots = evidence::screenshots(entry);
+    if !screenshots.is_empty() {
+        println!("\n{BOLD}Evidence{RESET}");
+        for (n, screenshot) in screenshots.iter().enumerate() {
+            println!("  {:<3} {}", n + 1, screenshot.path);
+        }
+    }
+    println!();
+}
+
+/// Lines of every entry containing `text`, ignoring case
+fn draw_search(report: &Report, text: &str) {
+    let needle = text.to_lowercase();
+    let mut matches = 0;
+    for (prefix, entries) in [
+        ('s', &report.sections),
+        ('f', &report.findings),
+        ('a', &report.appendices),
+    ] {
+        for entry in entries {
+            for (n, line) in entry.body.lines().enumerate() {
+                if line.to_lowercase().contains(&needle) {
+                    matches += 1;
+                    println!(
+                        "  {:<5} {:>4}  {}",
+                        format!("{prefix}{}", entry.id),
+                        entry.line + n,
+                        line.trim()
+                    );
+                }
+            }
+        }
+    }
+    println!("\n{matches} match(es) for \"{text}\"\n");
+}
+
+fn browse(report_path: &Path, pdf: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let report = Report::load(report_path)?;
+    let mut screen = Screen::List;
+    let mut message = String::new();
+    loop {
+        print!("{CLEAR_SCREEN}");
+        println!(
+            "{BOLD}{}{RESET} (read-only)\n",
+            report.metadata_value("report_title").unwrap_or("Report"),
+        );
+        let shown = match &screen {
+            Screen::List => {
+                draw_list(&report);
+                None
+            }
+            Screen::Entry(target) => report.find(target).map(|(kind, index)| {
+                let entry = &report.entries_of(kind)[index];
+                draw_entry(entry);
+                entry
+            }),
+            Screen::Search(text) => {
+                draw_search(&report, text);
+                None
+            }
+        };
+        println!("{HELP}\n");
+        if !message.is_empty() {
+            println!("{message}\n");
+        }
+
+        print!("> ");
+        stdout().flush()?;
+        let mut line = String::new();
+        if stdin().read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim();
+        message.clear();
+
+        match line.split_once(' ').unwrap_or((line, "")) {
+            ("q", _) => break,
+            ("l", _) | ("", _) => screen = Screen::List,
+            ("/", text) if !text.trim().is_empty() => {
+                screen = Screen::Search(text.trim().to_string())
+            }
+            ("o", "") => match pdf {
+                Some(pdf) => message = opened(desktop::open_path(pdf)),
+                None => message = "No compiled report to open".to_string(),
+            },
+            ("o", n) => {
+                let screenshot = shown.and_then(|entry| {
+                    let index = n.parse::<usize>().ok()?.checked_sub(1)?;
+                    evidence::screenshots(entry).into_iter().nth(index)
+                });
+                message = match screenshot {
+                    // Image paths are relative to the report
+                    Some(screenshot) => opened(desktop::open_path(
+                        &report_path.join(screenshot.path.trim_start_matches('/')),
+                    )),
+                    None => format!("No evidence \"{n}\""),
+                };
+            }
+            (target, "") if report.find(target).is_some() => {
+                screen = Screen::Entry(target.to_string())
+            }
+            _ => message = format!("Unknown command \"{line}\""),
+        }
+    }
+    Ok(())
+}
+
+fn opened(result: Result<(), String>) -> String {
+    match result {
+        Ok(()) => String::new(),
+        Err(e) => format!("ERROR: {e}"),
+    }
+}
+
+/// Browses a report directory, or a packed archive asking for its password
+pub fn view(target: String, password: Option<String>) -> Result<(), Box<dyn Error>> {
+    let path = PathBuf::from(&target);
+    if path.is_dir() {
+        let Some((report_path, pdf)) = locate(&path) else {
+            eprintln!("ERROR: {target} contains no report");
+            exit(1);
+        };
+        return browse(&report_path, pdf.as_deref());
+    }
+    if !path.is_file() {
+        eprintln!("ERROR: {target} doesn't exist");
+        exit(1);
+    }
+
+    let scratch = env::temp_dir().join(format!("reportgen-view-{}", process::id()));
+    let password = match password {
+        Some(password) => password,
+        None => encrypt::prompt_password("archive", false)?,
+    };
+    let result =
+        pack::extract(&env::current_dir()?.join(&path), &scratch, &password).and_then(|_| {
+            match locate(&scratch) {
+                Some((report_path, pdf)) => browse(&report_path, pdf.as_deref()),
+                None => Err(format!("{target} contains no report").into()),
+            }
+        });
+    let _ = remove_dir_all(&scratch);
+    if let Err(e) = result {
+        eprintln!("ERROR: {e}");
+        exit(1);
+    }
+
+    Ok(())
+}
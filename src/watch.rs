@@ -0,0 +1,179 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs::read_dir,
+    path::{Path, PathBuf},
+    process::Command,
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Local;
+
+use crate::config::Config;
+use crate::report::{self, Report};
+use crate::sla;
+
+// Recompiles the report whenever one of its files changes. Paths matching
+// `watch_ignore` of the config are left out, `watch_paths` adds files and
+// directories outside of the report, e.g. a shared snippet registry. Overdue
+// remediations of an open retest are warned about after every rebuild.
+//
+// Patterns use `*` for any text. Patterns without a `/` match file and
+// directory names anywhere, others the path relative to the watched directory.
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Version control and editor temp files
+const DEFAULT_IGNORE: &[&str] = &[".git", "*~", "*.swp", "*.swx", ".#*", "#*#", "4913"];
+
+/// Whether `text` matches `pattern` with `*` matching any text
+fn wildcard(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|i| text.is_char_boundary(*i))
+                .any(|i| wildcard(rest, &text[i..]))
+        }
+    }
+}
+
+fn is_ignored(relative: &Path, patterns: &[String]) -> bool {
+    let name = relative.file_name().unwrap_or_default().to_string_lossy();
+    let path = relative.to_string_lossy().replace('\\', "/");
+    patterns.iter().any(|pattern| match pattern.contains('/') {
+        true => wildcard(pattern.trim_matches('/'), &path),
+        false => wildcard(pattern, &name),
+    })
+}
+
+/// Modification times of the files below `root` that aren't ignored
+fn scan(root: &Path, dir: &Path, ignore: &[String], files: &mut HashMap<PathBuf, SystemTime>) {
+    let Ok(entries) = read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_ignored(path.strip_prefix(root).unwrap_or(&path), ignore) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            scan(root, &path, ignore, files);
+        } else if let Ok(modified) = metadata.modified() {
+            files.insert(path, modified);
+        }
+    }
+}
+
+fn snapshot(roots: &[PathBuf], ignore: &[String]) -> HashMap<PathBuf, SystemTime> {
+    let mut files = HashMap::new();
+    for root in roots {
+        match root.is_dir() {
+            true => scan(root, root, ignore, &mut files),
+            false => {
+                if let Ok(modified) = root.metadata().and_then(|m| m.modified()) {
+                    files.insert(root.clone(), modified);
+                }
+            }
+        }
+    }
+    files
+}
+
+/// A changed, added or removed file
+fn changed(
+    before: &HashMap<PathBuf, SystemTime>,
+    after: &HashMap<PathBuf, SystemTime>,
+) -> Option<PathBuf> {
+    after
+        .iter()
+        .find(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .or_else(|| {
+            before
+                .keys()
+                .find(|path| !after.contains_key(*path))
+                .cloned()
+        })
+}
+
+/// Warns about overdue remediations of an open retest after every rebuild
+fn alert_breaches(report_path: &Path) {
+    let breaches = Report::load(report_path)
+        .map_err(|e| e.to_string())
+        .and_then(|report| {
+            let config = Config::load(report_path)?;
+            sla::breaches(&report, &config, Local::now().date_naive())
+        });
+    match breaches {
+        Ok(breaches) => {
+            for breach in breaches {
+                eprintln!("WARNING: Overdue remediation: {}", breach.line());
+            }
+        }
+        Err(e) => eprintln!("WARNING: {e}"),
+    }
+}
+
+/// Calls `rebuild` with the changed file whenever a watched file changes, the
+/// files are taken after each rebuild so its output doesn't trigger another one
+pub fn on_change(report_path: &Path, config: &Config, mut rebuild: impl FnMut(&Path)) -> ! {
+    let mut ignore: Vec<String> = DEFAULT_IGNORE.iter().map(|p| p.to_string()).collect();
+    ignore.extend(config.watch_ignore.clone().unwrap_or_default());
+    let mut roots = vec![report_path.to_path_buf()];
+    roots.extend(config.watch_paths.clone().unwrap_or_default());
+    for root in &roots[1..] {
+        if !root.exists() {
+            eprintln!("WARNING: Watched path {} doesn't exist", root.display());
+        }
+    }
+
+    let mut files = snapshot(&roots, &ignore);
+    loop {
+        sleep(POLL_INTERVAL);
+        let current = snapshot(&roots, &ignore);
+        let Some(path) = changed(&files, &current) else {
+            continue;
+        };
+        rebuild(&path);
+        files = snapshot(&roots, &ignore);
+    }
+}
+
+/// Watches the report and recompiles it with `compile_args` on changes
+pub fn watch(report_dir: Option<PathBuf>, compile_args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let report_path = report::resolve_path(report_dir);
+    Report::load_or_exit(&report_path);
+    let config = Config::load_or_exit(&report_path);
+
+    let exe = env::current_exe()?;
+    let compile = || {
+        let status = Command::new(&exe)
+            .arg("compile")
+            .arg(&report_path)
+            .args(&compile_args)
+            .status();
+        if let Err(e) = status {
+            eprintln!("ERROR: Failed to run compile: {e}");
+        }
+        alert_breaches(&report_path);
+    };
+
+    compile();
+    println!(
+        "Watching {} for changes, Ctrl+C to stop",
+        report_path.display()
+    );
+    on_change(&report_path, &config, |path| {
+        println!("\n{} changed, recompiling", path.display());
+        compile();
+    })
+}
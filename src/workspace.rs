@@ -0,0 +1,43 @@
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+// A workspace is any directory holding several reports, possibly nested:
+//
+//  clients
+//  - acme
+//  - - webapp-2024
+//  - - - metadata.typ
+//  - - internal-2024
+//  - - - metadata.typ
+
+pub fn is_report(dir: &Path) -> bool {
+    dir.join("metadata.typ").exists()
+}
+
+fn collect_reports(dir: &Path, reports: &mut Vec<PathBuf>) {
+    if is_report(dir) {
+        reports.push(dir.to_path_buf());
+        // Reports don't contain further reports
+        return;
+    }
+    let Ok(entries) = read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if path.is_dir() && !hidden {
+            collect_reports(&path, reports);
+        }
+    }
+}
+
+/// Report directories in and below `dir`, sorted by path
+pub fn find_reports(dir: &Path) -> Vec<PathBuf> {
+    let mut reports = Vec::new();
+    collect_reports(dir, &mut reports);
+    reports.sort();
+    reports
+}
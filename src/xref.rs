@@ -0,0 +1,210 @@
+use std::ops::Range;
+
+use crate::report::{Entry, Report};
+use crate::utils::typst_escape;
+
+// Labels of findings (`FND-003` for finding 3) that sections and findings refer
+// to as `{{ ref:FND-003 }}`, compiled to links to the finding. Renumbering
+// findings rewrites the references, so they keep pointing at the same finding.
+
+const REFERENCE_PREFIX: &str = "ref:";
+const LABEL_PREFIX: &str = "FND-";
+const DEFAULT_ID_DIGITS: usize = 3;
+
+pub fn label(id: usize) -> String {
+    format!("{LABEL_PREFIX}{id:03}")
+}
+
+/// Finding number of a label like `FND-003`
+pub fn parse_label(label: &str) -> Option<usize> {
+    let digits = label.strip_prefix(LABEL_PREFIX)?;
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Whether a placeholder name is a finding reference like `ref:FND-003`
+pub fn is_reference(name: &str) -> bool {
+    name.strip_prefix(REFERENCE_PREFIX)
+        .is_some_and(|label| parse_label(label).is_some())
+}
+
+/// Byte ranges and labels of the `{{ ref:... }}` placeholders in a line
+pub fn references(line: &str) -> Vec<(Range<usize>, &str)> {
+    let mut references = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = line[offset..].find("{{") {
+        let start = offset + start;
+        let Some(end) = line[start..].find("}}") else {
+            break;
+        };
+        let end = start + end + 2;
+        if let Some(label) = line[start + 2..end - 2]
+            .trim()
+            .strip_prefix(REFERENCE_PREFIX)
+        {
+            references.push((start..end, label));
+        }
+        offset = end;
+    }
+    references
+}
+
+/// Replaces every reference of a line, keeping the ones `replace` returns `None` for
+fn replace_references(line: &str, mut replace: impl FnMut(&str) -> Option<String>) -> String {
+    let mut replaced = String::new();
+    let mut last = 0;
+    for (range, label) in references(line) {
+        if let Some(replacement) = replace(label) {
+            replaced.push_str(&line[last..range.start]);
+            replaced.push_str(&replacement);
+            last = range.end;
+        }
+    }
+    replaced.push_str(&line[last..]);
+    replaced
+}
+
+/// Finding IDs displayed in the deliverable, configured in the metadata:
+///
+///  finding_id_prefix:ACME-WEB-
+///  finding_id_digits:3
+///
+/// Labels stay `FND-003` so references don't depend on the scheme.
+pub struct IdScheme {
+    prefix: String,
+    digits: usize,
+    // Only configured IDs are added to finding headings
+    configured: bool,
+}
+
+impl IdScheme {
+    pub fn of(report: &Report) -> Self {
+        let prefix = report.metadata_value("finding_id_prefix");
+        let digits = report
+            .metadata_value("finding_id_digits")
+            .and_then(|d| d.trim().parse().ok());
+        Self {
+            configured: prefix.is_some() || digits.is_some(),
+            prefix: prefix.unwrap_or(LABEL_PREFIX).trim().to_string(),
+            digits: digits.unwrap_or(DEFAULT_ID_DIGITS),
+        }
+    }
+
+    /// Displayed ID of a finding, e.g. `ACME-WEB-001`
+    pub fn id(&self, finding: &Entry) -> String {
+        format!("{}{:0width$}", self.prefix, finding.id, width = self.digits)
+    }
+
+    /// Title of a finding prefixed with its ID, e.g. `ACME-WEB-001 SQL injection`
+    pub fn title(&self, finding: &Entry) -> String {
+        format!("{} {}", self.id(finding), finding_title(finding))
+    }
+
+    /// Title of a section, or of a finding with its ID, as listed in exports
+    pub fn entry_title(&self, report: &Report, entry: &Entry) -> String {
+        match report.findings.iter().any(|f| f.path == entry.path) {
+            true => self.title(entry),
+            false => entry.title(),
+        }
+    }
+}
+
+/// Title of a finding without the `Finding:` prefix of the template
+pub fn finding_title(finding: &Entry) -> String {
+    finding
+        .title()
+        .trim_start_matches("Finding:")
+        .trim()
+        .to_string()
+}
+
+/// Attaches the label of a finding to its first heading, or its start without one.
+/// A configured ID scheme also puts the ID in front of the heading.
+pub fn anchor(finding: &Entry, content: &str, ids: &IdScheme) -> String {
+    let label = format!("<{}>", label(finding.id));
+    let mut anchored = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if !anchored && line.starts_with('=') {
+                anchored = true;
+                let level = line.len() - line.trim_start_matches('=').len();
+                let heading = line[level..].trim_start();
+                match ids.configured {
+                    true => format!(
+                        "{} {}: {heading} {label}",
+                        &line[..level],
+                        typst_escape(&ids.id(finding))
+                    ),
+                    false => format!("{line} {label}"),
+                }
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    // Same line, so source locations of the following lines stay intact
+    if !anchored {
+        match lines.first_mut() {
+            Some(first) => first.insert_str(0, &format!("#metadata(none) {label} ")),
+            None => lines.push(format!("#metadata(none) {label}")),
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Replaces the references of the assembled report with links to the findings,
+/// the lines and labels of references to findings that don't exist otherwise
+pub fn resolve(document: &str, report: &Report) -> Result<String, Vec<(usize, String)>> {
+    let ids = IdScheme::of(report);
+    let mut dangling = Vec::new();
+    let resolved: Vec<String> = document
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            replace_references(line, |reference| {
+                let finding = parse_label(reference)
+                    .and_then(|id| report.findings.iter().find(|f| f.id == id));
+                match finding {
+                    Some(finding) => Some(format!(
+                        "#link(<{}>)[{} ({})]",
+                        label(finding.id),
+                        typst_escape(&finding_title(finding)),
+                        typst_escape(&ids.id(finding))
+                    )),
+                    None => {
+                        dangling.push((index + 1, reference.to_string()));
+                        None
+                    }
+                }
+            })
+        })
+        .collect();
+
+    if !dangling.is_empty() {
+        return Err(dangling);
+    }
+    Ok(resolved.join("\n") + "\n")
+}
+
+/// Rewrites references after findings were renumbered from the first to the
+/// second number of `ids`
+pub fn renumber(content: &str, ids: &[(usize, usize)]) -> String {
+    let renumbered: Vec<String> = content
+        .lines()
+        .map(|line| {
+            replace_references(line, |reference| {
+                let id = parse_label(reference)?;
+                let (_, new) = ids.iter().find(|(old, _)| *old == id)?;
+                Some(format!("{{{{ {REFERENCE_PREFIX}{} }}}}", label(*new)))
+            })
+        })
+        .collect();
+    let mut renumbered = renumbered.join("\n");
+    if content.ends_with('\n') {
+        renumbered.push('\n');
+    }
+    renumbered
+}
@@ -0,0 +1,5 @@
+= Example appendix
+Raw tool output referenced from the findings
+```
+CHANGE ME
+```
@@ -0,0 +1,40 @@
+{{> page }}
+{{> tables }}
+
+{{> cover }}
+
+#pagebreak()
+{{ document_control }}
+
+#if {{ toc }} {
+    outline(title: text(fill: rgb("{{ brand_accent_color }}"))[Table of Contents], depth: {{ toc_depth }})
+}
+
+#pagebreak()
+= Assessment Result
+
+#block(width: 100%, inset: 12pt, radius: 4pt, stroke: 1pt + green, fill: rgb("#eef8ee"))[
+  {{ assurance_statement }}
+]
+
+{{ sections }}
+
+{{ observations }}
+
+{{ appendices }}
+
+{{ remediation_roadmap }}
+
+{{ environment_matrix }}
+
+{{ host_appendix }}
+
+{{ asset_matrix }}
+
+{{ attack_surface }}
+
+{{ screenshot_gallery }}
+
+{{ attack_appendix }}
+
+{{ sign_off }}
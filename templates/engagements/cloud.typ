@@ -0,0 +1,14 @@
+= Methodology
+The cloud environment was reviewed with read-only access to its management plane, following the CIS Foundations Benchmarks of the provider and the cloud penetration testing policy of the provider.
+
+== Identity and Access Management
+Users, roles, policies and federation were reviewed for excessive permissions, missing multi-factor authentication and paths to privilege escalation.
+
+== Configuration Review
+Storage, compute, networking, logging and key management were checked against the benchmark with automated tooling and manual verification.
+
+== Exposed Services
+Publicly reachable endpoints, storage buckets and serverless functions were identified and tested from the internet.
+
+== Risk Rating Definitions
+{{ severity_legend }}
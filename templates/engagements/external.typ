@@ -0,0 +1,17 @@
+= Methodology
+The externally exposed infrastructure was assessed from the internet without prior access, following the PTES methodology.
+
+== Reconnaissance
+Public sources, DNS, certificate transparency logs and search engines were used to map the attack surface of the organization.
+
+== Discovery
+The hosts in scope were scanned for open ports and services, and exposed applications were identified.
+
+== Vulnerability Analysis
+Services were checked for known vulnerabilities, weak TLS configurations, default credentials and exposed administrative interfaces.
+
+== Exploitation
+Identified weaknesses were exploited where approved to demonstrate their impact.
+
+== Risk Rating Definitions
+{{ severity_legend }}
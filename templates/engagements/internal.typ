@@ -0,0 +1,17 @@
+= Methodology
+The internal network was assessed from the perspective of an attacker with access to the corporate network, e.g. through a compromised workstation, following the PTES methodology.
+
+== Discovery
+Hosts and services were discovered by network scans, passive traffic analysis and directory service enumeration.
+
+== Vulnerability Analysis
+Services were checked for known vulnerabilities, default credentials and insecure protocols such as SMBv1, LLMNR and NetBIOS name resolution.
+
+== Active Directory
+Domain configuration, group policies, delegation, certificate services and privileged groups were reviewed for paths to domain administrator privileges.
+
+== Exploitation and Lateral Movement
+Identified weaknesses were exploited where approved, and credentials gained were used to move to further systems.
+
+== Risk Rating Definitions
+{{ severity_legend }}
@@ -0,0 +1,14 @@
+= Methodology
+The mobile application was tested following the OWASP Mobile Application Security Testing Guide (MASTG) against the requirements of the OWASP MASVS.
+
+== Static Analysis
+The application package was decompiled and reviewed for hardcoded secrets, insecure cryptography, exported components and third-party libraries with known vulnerabilities.
+
+== Dynamic Analysis
+The application was run on instrumented devices to review local data storage, certificate pinning, inter-process communication and runtime protections.
+
+== Backend
+The API the application communicates with was tested for authentication, authorization and input validation weaknesses.
+
+== Risk Rating Definitions
+{{ severity_legend }}
@@ -0,0 +1,17 @@
+= Methodology
+The red team engagement emulated a targeted attacker pursuing agreed objectives against the organization, testing prevention, detection and response, following the MITRE ATT&CK framework.
+
+== Reconnaissance
+Employees, technologies and the external attack surface were mapped from public sources.
+
+== Initial Access
+Access was sought through phishing, exposed services and physical means as agreed in the rules of engagement.
+
+== Persistence and Lateral Movement
+Footholds were established and extended towards the objectives while avoiding detection.
+
+== Objectives
+Actions on the agreed objectives were carried out and documented with timestamps so the blue team can correlate them with their alerts.
+
+== Risk Rating Definitions
+{{ severity_legend }}
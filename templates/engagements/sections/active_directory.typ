@@ -0,0 +1,3 @@
+= Active Directory Review
+// Domains, trusts, privileged groups and attack paths found
+#lorem(100)
@@ -0,0 +1,3 @@
+= Application Overview
+// Purpose, technology stack, roles and environments of the application
+#lorem(100)
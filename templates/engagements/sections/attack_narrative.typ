@@ -0,0 +1,3 @@
+= Attack Narrative
+// Timeline of the engagement from initial access to the objectives
+#lorem(100)
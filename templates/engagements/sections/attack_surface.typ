@@ -0,0 +1,3 @@
+= Attack Surface
+// Hosts, services and applications exposed to the internet
+#lorem(100)
@@ -0,0 +1,3 @@
+= Authentication and Authorization
+// Login, session handling and the access control model as tested
+#lorem(100)
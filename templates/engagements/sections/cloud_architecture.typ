@@ -0,0 +1,3 @@
+= Cloud Architecture
+// Accounts, subscriptions or projects, regions and the main services in use
+#lorem(100)
@@ -0,0 +1,3 @@
+= Detection and Response
+// Actions detected by the blue team, response times and gaps
+#lorem(100)
@@ -0,0 +1,3 @@
+= Dynamic Analysis
+// Devices, instrumentation and the flows exercised at runtime
+#lorem(100)
@@ -0,0 +1,3 @@
+= IAM Review
+// Users, roles and policies reviewed, privileged identities and escalation paths
+#lorem(100)
@@ -0,0 +1,3 @@
+= Network Overview
+// Segments, starting point of the test and notable hosts
+#lorem(100)
@@ -0,0 +1,3 @@
+= Static Analysis
+// Platforms, versions and packages analyzed, tools used
+#lorem(100)
@@ -0,0 +1,20 @@
+= Methodology
+The web application was tested following the OWASP Web Security Testing Guide (WSTG) and the OWASP Application Security Verification Standard (ASVS), combining automated scans with manual testing of the business logic.
+
+== Information Gathering
+The application was mapped by crawling it with an intercepting proxy, reviewing client-side code and enumerating endpoints, parameters and roles.
+
+== Authentication and Session Management
+Login, password reset, multi-factor authentication and session handling were tested for weaknesses such as credential stuffing, session fixation and insufficient expiry.
+
+== Authorization
+Every role was tested for horizontal and vertical privilege escalation, including direct object references in the API.
+
+== Input Validation
+Inputs were tested for injection (SQL, command, template), cross-site scripting, server-side request forgery and file upload weaknesses.
+
+== Configuration
+TLS, security headers, error handling and exposed files were reviewed.
+
+== Risk Rating Definitions
+{{ severity_legend }}
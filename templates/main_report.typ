@@ -1,54 +1,23 @@
-#set text(font: "Noto Sans")
-#set page(
-    header: [
-        #set align(right)
-        #set text(8pt)
-        Penetration Test Report Logo
-    ],
-    footer: [
-        #set text(8pt)
-
-        #place(
-            left,
-            text("Page No. " + counter(page).display("1 of 1", both: true))
-        )
-
-        #place(
-            center,
-            text("Client Confidential")
-        )
-
-        #place(
-            right,
-            text("{{ company_website }}")
-        )
-    ]
-)
-
-#block(height: 100pt)
-
-#set align(right)
-#set text(16pt)
-
-#text(size: 24pt, weight: 900)[{{ report_title }}] \
-{{ current_date }} \
-#text(fill: blue)[Prepared for: ]{{ prepared_for }}
-
-#set align(left)
-#set text(14pt)
-
-#block(height: 100pt)
-
-#text(fill: blue, weight: 600, size: 20pt)[Prepared by:] \
-{{ prepared_by }} \
-{{ company_website }} \
-#text(fill: blue)[E-mail: ]{{ company_email }} \
-#text(fill: blue)[Phone: ]{{ company_phone }} \
-
-#set align(left)
+{{> page }}
+{{> tables }}
+
+// Long tokens like JWTs, base64 blobs or URLs in evidence would overflow the
+// page, typst only breaks lines at spaces. Their chunks are boxed so lines
+// break between them while the text, and what's copied from the PDF, stays
+// the same.
+#show regex("\S{30,}"): token => {
+    show regex("\S{16}"): box
+    token
+}
+
+{{> cover }}
 
 #pagebreak()
-#outline(title: text(fill: blue)[Table of Contents])
+{{ document_control }}
+
+#if {{ toc }} {
+    outline(title: text(fill: rgb("{{ brand_accent_color }}"))[Table of Contents], depth: {{ toc_depth }})
+}
 
 {{ sections }}
 
@@ -57,6 +26,20 @@
 
 {{ findings }}
 
-#pagebreak()
-#set align(center)
-= TODO: LAST PAGE CHANGE ME
+{{ appendices }}
+
+{{ remediation_roadmap }}
+
+{{ environment_matrix }}
+
+{{ host_appendix }}
+
+{{ asset_matrix }}
+
+{{ attack_surface }}
+
+{{ screenshot_gallery }}
+
+{{ attack_appendix }}
+
+{{ sign_off }}
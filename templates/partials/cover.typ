@@ -0,0 +1,23 @@
+#block(height: 100pt)
+
+#set align(right)
+#set text(16pt)
+
+{{ cover_logo }}
+
+#text(size: 24pt, weight: 900)[{{ report_title }}] \
+{{ current_date }} \
+#text(fill: rgb("{{ brand_accent_color }}"))[Prepared for: ]{{ prepared_for }}
+
+#set align(left)
+#set text(14pt)
+
+#block(height: 100pt)
+
+#text(fill: rgb("{{ brand_accent_color }}"), weight: 600, size: 20pt)[Prepared by:] \
+{{ prepared_by }} \
+{{ company_website }} \
+#text(fill: rgb("{{ brand_accent_color }}"))[E-mail: ]{{ company_email }} \
+#text(fill: rgb("{{ brand_accent_color }}"))[Phone: ]{{ company_phone }} \
+
+#set align(left)
@@ -0,0 +1 @@
+{{ finding }}
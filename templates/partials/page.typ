@@ -0,0 +1,34 @@
+#set text(font: {{ brand_font }})
+#show heading: set text(font: {{ brand_heading_font }})
+#set page(
+    paper: {{ paper }},
+    margin: {{ page_margin }},
+    header: [
+        #set align(right)
+        #set text(8pt)
+        {{ brand_logo }}
+    ],
+    footer: [
+        #set text(8pt)
+
+        #place(
+            left,
+            text("Page No. " + counter(page).display({{ page_numbering }}, both: true))
+        )
+
+        #place(
+            center,
+            text[{{ brand_footer }}]
+        )
+
+        #place(
+            center + bottom,
+            text(6pt, fill: gray, "{{ git_commit }}")
+        )
+
+        #place(
+            right,
+            text("{{ company_website }}")
+        )
+    ]
+)
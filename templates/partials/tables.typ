@@ -0,0 +1 @@
+#set table(inset: 5pt)
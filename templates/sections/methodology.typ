@@ -1,3 +1,6 @@
 = Methodology
 Example methodology
 #lorem(200)
+
+== Risk Rating Definitions
+{{ severity_legend }}
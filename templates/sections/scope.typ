@@ -1,3 +1,6 @@
 = Scope
 Example scope
+
+{{ scope_definition }}
+
 #lorem(200)
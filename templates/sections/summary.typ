@@ -1,3 +1,13 @@
 = Summary
+{{ executive_summary }}
+
+{{ finding_count_by_severity }}
+
+{{ findings_overview }}
+
+{{ risk_matrix }}
+
+{{ severity_trend }}
+
 Example summary content
 #lorem(200)
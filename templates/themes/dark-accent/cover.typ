@@ -0,0 +1,22 @@
+#block(
+    width: 100%,
+    height: 60%,
+    inset: 32pt,
+    fill: rgb("#1e1e24"),
+)[
+    #set text(fill: white)
+    {{ cover_logo }}
+    #v(1fr)
+    #text(size: 30pt, weight: 900)[{{ report_title }}]
+    #line(length: 30%, stroke: 3pt + rgb("{{ brand_accent_color }}"))
+    #text(size: 14pt)[Prepared for {{ prepared_for }}] \
+    #text(size: 12pt, fill: luma(200))[{{ current_date }}]
+]
+
+#v(1fr)
+
+#text(fill: rgb("{{ brand_accent_color }}"), weight: 600, size: 16pt)[Prepared by] \
+{{ prepared_by }} \
+{{ company_website }} \
+{{ company_email }} \
+{{ company_phone }}
@@ -0,0 +1,33 @@
+#set text(font: {{ brand_font }})
+#show heading: set text(font: {{ brand_heading_font }})
+#show heading.where(level: 1): it => block(
+    width: 100%,
+    inset: (left: 8pt, y: 4pt),
+    stroke: (left: 4pt + rgb("{{ brand_accent_color }}")),
+    it,
+)
+#set page(
+    paper: {{ paper }},
+    margin: {{ page_margin }},
+    header: [
+        #block(width: 100%, fill: rgb("#1e1e24"), inset: 6pt)[
+            #set text(8pt, fill: white)
+            #grid(
+                columns: (1fr, auto),
+                align: (left + horizon, right + horizon),
+                text[{{ company_website }}],
+                [{{ brand_logo }}]
+            )
+        ]
+    ],
+    footer: [
+        #set text(8pt)
+        #grid(
+            columns: (1fr, auto, 1fr),
+            align: (left, center, right),
+            text[{{ brand_footer }}],
+            text(6pt, fill: gray, "{{ git_commit }}"),
+            text(fill: rgb("{{ brand_accent_color }}"), weight: 600, counter(page).display({{ page_numbering }}, both: true))
+        )
+    ]
+)
@@ -0,0 +1,6 @@
+#set table(
+    stroke: 0.5pt + luma(200),
+    inset: 6pt,
+    fill: (_, y) => if y == 0 { rgb("#1e1e24") },
+)
+#show table.cell.where(y: 0): set text(fill: white)
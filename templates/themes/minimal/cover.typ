@@ -0,0 +1,14 @@
+{{ cover_logo }}
+
+#v(30%)
+
+#text(size: 26pt, weight: 600)[{{ report_title }}]
+
+#line(length: 40%, stroke: 0.5pt + gray)
+
+{{ prepared_for }} \
+{{ current_date }}
+
+#v(1fr)
+
+#text(9pt, fill: gray)[{{ prepared_by }} · {{ company_website }} · {{ company_email }} · {{ company_phone }}]
@@ -0,0 +1,12 @@
+#set text(font: {{ brand_font }}, size: 10pt)
+#show heading: set text(font: {{ brand_heading_font }}, weight: 600)
+#set page(
+    paper: {{ paper }},
+    margin: {{ page_margin }},
+    footer: [
+        #set text(8pt, fill: gray)
+        #set align(center)
+        #counter(page).display({{ page_numbering }}, both: true)
+        #place(right, text(6pt, "{{ git_commit }}"))
+    ]
+)
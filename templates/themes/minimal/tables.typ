@@ -0,0 +1,4 @@
+#set table(
+    stroke: (_, y) => if y == 0 { (bottom: 0.5pt + gray) } else { none },
+    inset: 5pt,
+)
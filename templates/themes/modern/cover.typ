@@ -0,0 +1,25 @@
+{{ cover_logo }}
+
+#block(
+    width: 100%,
+    inset: (x: 24pt, y: 48pt),
+    fill: rgb("{{ brand_accent_color }}"),
+)[
+    #set text(fill: white)
+    #text(size: 28pt, weight: 800)[{{ report_title }}] \
+    #v(8pt)
+    #text(size: 14pt)[Prepared for {{ prepared_for }}] \
+    #text(size: 12pt)[{{ current_date }}]
+]
+
+#v(1fr)
+
+#grid(
+    columns: (auto, 1fr),
+    column-gutter: 16pt,
+    row-gutter: 8pt,
+    text(fill: rgb("{{ brand_accent_color }}"), weight: 600)[Prepared by], [{{ prepared_by }}],
+    text(fill: rgb("{{ brand_accent_color }}"), weight: 600)[Website], [{{ company_website }}],
+    text(fill: rgb("{{ brand_accent_color }}"), weight: 600)[E-mail], [{{ company_email }}],
+    text(fill: rgb("{{ brand_accent_color }}"), weight: 600)[Phone], [{{ company_phone }}],
+)
@@ -0,0 +1,27 @@
+#set text(font: {{ brand_font }}, size: 10.5pt)
+#show heading: set text(font: {{ brand_heading_font }}, fill: rgb("{{ brand_accent_color }}"))
+#show heading.where(level: 1): set block(below: 1em)
+#set page(
+    paper: {{ paper }},
+    margin: {{ page_margin }},
+    header: [
+        #set text(8pt)
+        #grid(
+            columns: (1fr, auto),
+            align: (left + horizon, right + horizon),
+            text(fill: gray)[{{ report_title }}],
+            [{{ brand_logo }}]
+        )
+        #line(length: 100%, stroke: 1.5pt + rgb("{{ brand_accent_color }}"))
+    ],
+    footer: [
+        #set text(8pt, fill: gray)
+        #grid(
+            columns: (1fr, auto, 1fr),
+            align: (left, center, right),
+            text[{{ brand_footer }}],
+            text(6pt, "{{ git_commit }}"),
+            counter(page).display({{ page_numbering }}, both: true)
+        )
+    ]
+)
@@ -0,0 +1,5 @@
+#set table(
+    stroke: none,
+    inset: 6pt,
+    fill: (_, y) => if y == 0 { rgb("{{ brand_accent_color }}").lighten(75%) } else if calc.even(y) { luma(245) },
+)
@@ -0,0 +1,123 @@
+use std::{
+    env,
+    fs::{create_dir_all, read_to_string, remove_dir_all, write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{self, Child, Command, Output, Stdio},
+    thread,
+    time::Duration,
+};
+
+// Posts reports to a compile server that read files outside of the upload,
+// skipped where 7z or curl isn't installed
+
+const BIN: &str = env!("CARGO_BIN_EXE_report-generator");
+const TOKEN: &str = "test-token";
+
+fn command(args: &[&str], config_home: &Path) -> Command {
+    let mut command = Command::new(BIN);
+    command
+        .args(args)
+        // Leave the config of whoever runs the tests out
+        .env("XDG_CONFIG_HOME", config_home)
+        .env("REPORTGEN_TOKEN", TOKEN)
+        .stdin(Stdio::null());
+    command
+}
+
+fn run(args: &[&str], config_home: &Path) -> Output {
+    command(args, config_home)
+        .output()
+        .expect("Failed to run report-generator")
+}
+
+fn installed(program: &str, arg: &str) -> bool {
+    Command::new(program)
+        .arg(arg)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|address| address.port())
+        .unwrap()
+}
+
+/// The compile server, killed when dropped
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn start_server(port: u16, config_home: &Path) -> Server {
+    let server = command(
+        &["serve", "--api", "--port", &port.to_string()],
+        config_home,
+    )
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()
+    .expect("Failed to start the compile server");
+    for _ in 0..50 {
+        if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Server(server)
+}
+
+#[test]
+fn metadata_paths_outside_the_upload_are_refused() {
+    if !installed("7z", "i") || !installed("curl", "--version") {
+        eprintln!("7z or curl not on PATH, skipping the remote test");
+        return;
+    }
+    let dir: PathBuf = env::temp_dir().join(format!("reportgen-remote-test-{}", process::id()));
+    let _ = remove_dir_all(&dir);
+    let config_home = dir.join("config");
+    create_dir_all(&config_home).unwrap();
+    let report = dir.join("report");
+    let report = report.to_str().unwrap();
+
+    let output = run(&["new", report], &config_home);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let metadata = Path::new(report).join("metadata.typ");
+    let content = read_to_string(&metadata).unwrap();
+    write(&metadata, format!("{content}logo:/etc/passwd\n")).unwrap();
+
+    let port = free_port();
+    let _server = start_server(port, &config_home);
+    let pdf = dir.join("report.pdf");
+    let output = run(
+        &[
+            "compile",
+            report,
+            "--remote",
+            &format!("http://127.0.0.1:{port}"),
+            "--allow-placeholders",
+            "-o",
+            pdf.to_str().unwrap(),
+        ],
+        &config_home,
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let _ = remove_dir_all(&dir);
+    assert!(!output.status.success(), "compiled with logo:/etc/passwd");
+    assert!(
+        stderr.contains("logo \"/etc/passwd\" has to be a file of the report"),
+        "{stderr}"
+    );
+}
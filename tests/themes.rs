@@ -0,0 +1,92 @@
+use std::{
+    env,
+    fs::{create_dir_all, remove_dir_all},
+    path::{Path, PathBuf},
+    process::{self, Command, Output, Stdio},
+};
+
+// Compiles the sample report of `new` with every built-in theme, skipped
+// where typst isn't installed
+
+const BIN: &str = env!("CARGO_BIN_EXE_report-generator");
+
+fn run(args: &[&str], config_home: &Path) -> Output {
+    Command::new(BIN)
+        .args(args)
+        // Leave the config of whoever runs the tests out
+        .env("XDG_CONFIG_HOME", config_home)
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to run report-generator")
+}
+
+fn has_typst() -> bool {
+    Command::new("typst")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Themes listed as the possible values of `compile --theme`
+fn themes(config_home: &Path) -> Vec<String> {
+    let help = String::from_utf8(run(&["compile", "--help"], config_home).stdout).unwrap();
+    let line = help
+        .lines()
+        .find(|line| line.trim_start().starts_with("--theme"))
+        .expect("compile --help lists --theme");
+    let values = line
+        .split_once("[possible values: ")
+        .and_then(|(_, values)| values.strip_suffix(']'))
+        .expect("--theme lists its possible values");
+    values.split(", ").map(str::to_string).collect()
+}
+
+#[test]
+fn sample_compiles_with_every_theme() {
+    if !has_typst() {
+        eprintln!("typst not on PATH, skipping the theme test");
+        return;
+    }
+    let dir: PathBuf = env::temp_dir().join(format!("reportgen-themes-{}", process::id()));
+    let _ = remove_dir_all(&dir);
+    let config_home = dir.join("config");
+    create_dir_all(&config_home).unwrap();
+    let report = dir.join("report");
+    let report = report.to_str().unwrap();
+
+    let output = run(&["new", report], &config_home);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let themes = themes(&config_home);
+    assert!(themes.len() > 1, "only {themes:?} found");
+    let mut failed = Vec::new();
+    for theme in &themes {
+        let pdf = dir.join(format!("{theme}.pdf"));
+        let output = run(
+            &[
+                "compile",
+                report,
+                "--theme",
+                theme,
+                "--allow-placeholders",
+                "--no-notify",
+                "--force",
+                "-o",
+                pdf.to_str().unwrap(),
+            ],
+            &config_home,
+        );
+        if !output.status.success() || !pdf.is_file() {
+            failed.push(format!(
+                "{theme}:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+    }
+    let _ = remove_dir_all(&dir);
+    assert!(failed.is_empty(), "{}", failed.join("\n"));
+}